@@ -0,0 +1,114 @@
+"""Benchmarks for parsing and for verifying commits with Git.
+
+Run with `just bench`, or directly with `python benches/bench_validate.py --help`.
+"""
+
+import argparse
+import random
+import subprocess
+import tempfile
+import timeit
+from collections.abc import Callable
+from pathlib import Path
+
+from validate_git_blame_ignore_revs import ValidationOptions, validate_git_blame_ignore_revs
+
+
+def write_synthetic_file(path: Path, hashes: list[str], invalid_every: int = 0) -> None:
+    """Write an ignore file with a comment above each hash and, optionally, some invalid lines."""
+    with path.open("w", encoding="utf-8") as f:
+        for i, commit_hash in enumerate(hashes):
+            f.write(f"# Synthetic formatting commit {i}\n")
+            f.write(f"{commit_hash}\n")
+            if invalid_every and i % invalid_every == 0:
+                f.write("not-a-hash\n")
+            f.write("\n")
+
+
+def make_repository(path: Path, num_commits: int) -> list[str]:
+    """Create a repository with `num_commits` commits using `git fast-import`."""
+    subprocess.run(["git", "init", "--quiet", str(path)], check=True)
+    stream: list[bytes] = []
+    for i in range(num_commits):
+        message = f"Commit {i}\n".encode()
+        content = f"{i}\n".encode()
+        stream.append(b"commit refs/heads/main\n")
+        stream.append(f"mark :{i + 1}\n".encode())
+        stream.append(b"committer Bench <bench@example.com> 1700000000 +0000\n")
+        stream.append(b"data %d\n%s" % (len(message), message))
+        if i:
+            stream.append(f"from :{i}\n".encode())
+        stream.append(b"M 644 inline file.txt\n")
+        stream.append(b"data %d\n%s\n" % (len(content), content))
+    subprocess.run(["git", "fast-import", "--quiet"], cwd=path, input=b"".join(stream), check=True)
+    subprocess.run(["git", "symbolic-ref", "HEAD", "refs/heads/main"], cwd=path, check=True)
+    subprocess.run(["git", "reset", "--quiet", "--hard"], cwd=path, check=True)
+    log = subprocess.run(
+        ["git", "log", "--pretty=format:%H"], cwd=path, check=True, capture_output=True, text=True
+    )
+    return log.stdout.split()
+
+
+def report(name: str, function: Callable[[], object], repeat: int, number: int) -> None:
+    times = timeit.repeat(function, repeat=repeat, number=number)
+    best = min(times) / number
+    print(f"{name}: best of {repeat}: {best * 1000:.2f} ms per run")
+
+
+def main() -> None:
+    parser = argparse.ArgumentParser(description=__doc__)
+    _ = parser.add_argument(
+        "--parse-entries",
+        type=int,
+        nargs="+",
+        default=[1_000, 10_000, 100_000],
+        help="Numbers of entries in the synthetic files to parse (default: %(default)s).",
+    )
+    _ = parser.add_argument(
+        "--git-entries",
+        type=int,
+        nargs="+",
+        default=[10, 100, 500],
+        help="Numbers of commits to verify with Git (default: %(default)s).",
+    )
+    _ = parser.add_argument("--repeat", type=int, default=5, help="Timing repetitions.")
+    args = parser.parse_args()
+
+    rng = random.Random(0)
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        tmp = Path(tmpdir)
+
+        for num_entries in args.parse_entries:
+            file_path = tmp / f"parse-{num_entries}"
+            hashes = [f"{rng.getrandbits(160):040x}" for _ in range(num_entries)]
+            write_synthetic_file(file_path, hashes, invalid_every=100)
+            report(
+                f"parse {num_entries} entries",
+                lambda: validate_git_blame_ignore_revs(
+                    file_path, ValidationOptions(strict_comments=True)
+                ),
+                repeat=args.repeat,
+                number=1,
+            )
+
+        for num_entries in args.git_entries:
+            repo = tmp / f"repo-{num_entries}"
+            hashes = make_repository(repo, num_entries)
+            file_path = repo / ".git-blame-ignore-revs"
+            write_synthetic_file(file_path, hashes)
+            report(
+                f"verify {num_entries} commits with git",
+                lambda: validate_git_blame_ignore_revs(
+                    file_path,
+                    ValidationOptions(
+                        call_git=True, strict_comments=True, strict_comments_git=True
+                    ),
+                ),
+                repeat=args.repeat,
+                number=1,
+            )
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,46 @@
+"""Fuzz the parser and the Git-free checks with arbitrary bytes.
+
+Run with `just fuzz`, passing any libFuzzer options, for example
+`just fuzz -max_total_time=60`.
+"""
+
+import io
+import sys
+import tempfile
+from pathlib import Path
+
+import atheris  # type: ignore[import-not-found]
+
+with atheris.instrument_imports():
+    from validate_git_blame_ignore_revs.lib import (
+        LineKind,
+        ValidationOptions,
+        parse_lines,
+        validate_git_blame_ignore_revs,
+    )
+
+file_path = Path(tempfile.mkdtemp()) / ".git-blame-ignore-revs"
+
+
+def test_one_input(data: bytes) -> None:
+    # The parser itself works on text, so anything decodable must parse
+    text = data.decode("utf-8", errors="surrogateescape")
+    parsed_lines = list(parse_lines(io.StringIO(text)))
+    for expected_line_number, parsed_line in enumerate(parsed_lines, start=1):
+        assert parsed_line.line_number == expected_line_number
+        assert parsed_line.text == parsed_line.text.strip()
+        assert (parsed_line.kind is LineKind.Blank) == (not parsed_line.text)
+
+    # The validator reads the file itself and only rejects invalid UTF-8
+    file_path.write_bytes(data)
+    try:
+        result = validate_git_blame_ignore_revs(file_path, ValidationOptions(strict_comments=True))
+    except UnicodeDecodeError:
+        return
+    assert set(result["strict_comment_errors"]) <= set(result["valid_hashes"])
+    assert not set(result["valid_hashes"]) & set(result["errors"])
+
+
+if __name__ == "__main__":
+    atheris.Setup(sys.argv, test_one_input)
+    atheris.Fuzz()
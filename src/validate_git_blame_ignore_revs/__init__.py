@@ -1,3 +1,31 @@
-from .lib import validate_git_blame_ignore_revs
+from .lib import (
+    ValidationOptions,
+    add_finding,
+    empty_result,
+    error_count,
+    extend_result,
+    info_count,
+    is_clean,
+    merge_result,
+    merge_results,
+    result_summary,
+    validate_files,
+    validate_git_blame_ignore_revs,
+    warning_count,
+)
 
-__all__ = ("validate_git_blame_ignore_revs",)
+__all__ = (
+    "ValidationOptions",
+    "add_finding",
+    "empty_result",
+    "error_count",
+    "extend_result",
+    "info_count",
+    "is_clean",
+    "merge_result",
+    "merge_results",
+    "result_summary",
+    "validate_files",
+    "validate_git_blame_ignore_revs",
+    "warning_count",
+)
@@ -1,9 +1,151 @@
 import argparse
+import configparser
+import json
+import os
+import re
+import sys
+import textwrap
+from collections.abc import Callable, Generator, Iterable, Sequence
+from concurrent.futures import ProcessPoolExecutor
+from contextlib import AbstractContextManager, closing, nullcontext
+from datetime import datetime, timezone
 from enum import Enum
+from functools import partial
 from pathlib import Path
-from sys import exit
+from subprocess import CalledProcessError
+from typing import Optional
 
-from validate_git_blame_ignore_revs.lib import validate_git_blame_ignore_revs
+from validate_git_blame_ignore_revs.annotate import annotate_entries, annotated_text
+from validate_git_blame_ignore_revs.blame import compare_blame, compute_blame_impact
+from validate_git_blame_ignore_revs.cache import (
+    cache_dir,
+    cache_stats,
+    cached_validate_git_blame_ignore_revs,
+)
+from validate_git_blame_ignore_revs.checks import CHECKS, rule_id
+from validate_git_blame_ignore_revs.comments import MatchMode
+from validate_git_blame_ignore_revs.config import (
+    CONFIG_FILE_NAME,
+    DEFAULT_FILE_NAME,
+    Config,
+    default_config,
+    find_config,
+    load_config,
+)
+from validate_git_blame_ignore_revs.denylist import Denylist
+from validate_git_blame_ignore_revs.diff import (
+    check_added_entries,
+    compare_entries,
+    ordering_problems,
+    read_at_revision,
+)
+from validate_git_blame_ignore_revs.explain import AmbiguousRevisionError, explain_entry
+from validate_git_blame_ignore_revs.export import (
+    EXPORT_FORMATS,
+    export_entries,
+    render_export_json,
+    render_export_markdown,
+)
+from validate_git_blame_ignore_revs.fixes import EDIT_FORMATS, apply_edits, find_fixes
+from validate_git_blame_ignore_revs.forge import (
+    BITBUCKET_HOST,
+    ForgeRepository,
+    code_insights,
+    commit_url,
+    parse_remote_url,
+    publish_code_insights,
+)
+from validate_git_blame_ignore_revs.forge_api import ForgeClient, ResponseCache, UnknownForgeError
+from validate_git_blame_ignore_revs.formats import (
+    FORMATS,
+    PORCELAIN_VERSION,
+    RENDERERS,
+    render_json,
+    render_markdown,
+)
+from validate_git_blame_ignore_revs.git import (
+    HistoryRange,
+    fetch_remote_head,
+    head_commit,
+    is_valid_trailer,
+    prefetch_diffs,
+    pin_repository_environment,
+    promisor_remote,
+    remote_head,
+    remote_url,
+    repository_path,
+    trace_git,
+)
+from validate_git_blame_ignore_revs.history import (
+    FORMATTERS,
+    audit_formatter_commits,
+    compute_coverage,
+)
+from validate_git_blame_ignore_revs.i18n import LANGUAGES, translator
+from validate_git_blame_ignore_revs.importer import (
+    IMPORT_FORMATS,
+    detect_format,
+    format_entries,
+    parse_entries,
+    resolve_entries,
+)
+from validate_git_blame_ignore_revs.interrupt import Interrupted, interruptible
+from validate_git_blame_ignore_revs.introductions import (
+    Introduction,
+    file_versions,
+    find_introductions,
+    finding_keys,
+)
+from validate_git_blame_ignore_revs.lib import (
+    GitNotFoundError,
+    LineKind,
+    MissingReason,
+    ValidationOptions,
+    ValidationResult,
+    commit_hash_regex,
+    parse_lines,
+    tracking_problems,
+    validate_git_blame_ignore_revs,
+)
+from validate_git_blame_ignore_revs.metrics import Metrics
+from validate_git_blame_ignore_revs.output import append_output, write_output
+from validate_git_blame_ignore_revs.paths import strip_long_path_prefix
+from validate_git_blame_ignore_revs.profiles import PROFILES, profile_config
+from validate_git_blame_ignore_revs.progress import Progress, progress_bar
+from validate_git_blame_ignore_revs.remote import parse_repository, validate_remote
+from validate_git_blame_ignore_revs.report import (
+    GROUP_BY,
+    SORT_BY,
+    FileReport,
+    Finding,
+    Section,
+    Severity,
+    exit_code,
+    hyperlink,
+    line_finding,
+    new_findings,
+    render_report,
+)
+from validate_git_blame_ignore_revs.retry import RetryPolicy, set_default_policy
+from validate_git_blame_ignore_revs.server import BadRequest, ValidationServer, Validator
+from validate_git_blame_ignore_revs.sources import (
+    ENCODINGS,
+    ContentSource,
+    GitIndex,
+    GitRevision,
+    InMemory,
+    RevisionNotFoundError,
+    WorkingTree,
+    content_encoding,
+    line_ending,
+)
+from validate_git_blame_ignore_revs.spelling import (
+    SYSTEM_DICTIONARIES,
+    find_dictionary,
+    load_dictionary,
+)
+from validate_git_blame_ignore_revs.template import DEFAULT_COMMENT_TEMPLATE
+from validate_git_blame_ignore_revs.workspace import workspace_ignore_files
 
 
 class ErrorCode(Enum):
@@ -13,20 +155,2068 @@ class ErrorCode(Enum):
     MissingComments = 0b1000
     MissingCommitMessageComments = 0b10000
     MissingPreCommitCICommits = 0b100000
+    FailedAdditionalChecks = 0b1000000
+    GitNotFound = 0b10000000
 
 
-def main() -> None:
-    parser = argparse.ArgumentParser(description="Validate a .git-blame-ignore-revs file.")
+# Exit code bits for problems that make a file unusable rather than breaking a convention
+ERROR_CODES = (
+    ErrorCode.FileNotFound.value
+    | ErrorCode.SyntaxProblem.value
+    | ErrorCode.CommitsNotPresent.value
+    | ErrorCode.GitNotFound.value
+)
+
+
+def display_path(path: Path) -> str:
+    """Return `path` as printable text, replacing any bytes in it that aren't valid UTF-8.
+
+    The prefix Windows adds to long paths is left out.
+    """
+    return os.fsencode(strip_long_path_prefix(path)).decode("utf-8", errors="replace")
+
+
+def add_history_range_arguments(parser: argparse.ArgumentParser, scans: str) -> None:
+    """Add the options that limit the commits `git log` scans, described as `scans`."""
+    _ = parser.add_argument(
+        "--since",
+        metavar="DATE",
+        help=f"Only scan commits more recent than DATE for {scans}, such as when the file was adopted.",
+    )
+    _ = parser.add_argument(
+        "--until",
+        metavar="DATE",
+        help=f"Only scan commits older than DATE for {scans}.",
+    )
+    _ = parser.add_argument(
+        "--max-count",
+        type=int,
+        metavar="N",
+        help=f"Only scan the N most recent commits for {scans}.",
+    )
+
+
+def history_range(args: argparse.Namespace) -> HistoryRange:
+    """Return the limits given by the options from `add_history_range_arguments`."""
+    return HistoryRange(since=args.since, until=args.until, max_count=args.max_count)
+
+
+def content_source(args: argparse.Namespace) -> ContentSource:
+    """Return where the options say to read the file from."""
+    if args.at_rev is not None:
+        return GitRevision(args.at_rev)
+    if args.staged:
+        return GitIndex()
+    return WorkingTree()
+
+
+def git_tracing(args: argparse.Namespace) -> AbstractContextManager[None]:
+    """Return a context that logs Git commands to standard error if `--trace-git` is given."""
+    return trace_git(sys.stderr) if args.trace_git else nullcontext()
+
+
+def report_sink(value: str) -> tuple[str, str]:
+    """Parse a `FORMAT=PATH` argument of `--report`."""
+    report_format, separator, destination = value.partition("=")
+    if not separator or not destination:
+        raise argparse.ArgumentTypeError(f"expected FORMAT=PATH, got {value!r}")
+    if report_format not in FORMATS:
+        raise argparse.ArgumentTypeError(
+            f"unknown format {report_format!r} (choose from {', '.join(FORMATS)})"
+        )
+    return report_format, destination
+
+
+# Unit of a duration -> Seconds in it, with months and years on average
+_DURATION_UNITS = {"h": 3600, "d": 86400, "w": 7 * 86400, "mo": 30 * 86400, "y": 365 * 86400}
+
+
+def duration_seconds(value: str) -> float:
+    """Return the number of seconds in a duration such as `90d`, `6mo`, or `2y`."""
+    match = re.fullmatch(r"(\d+(?:\.\d+)?)\s*([a-z]+)", value.strip())
+    if match is None or match[2] not in _DURATION_UNITS:
+        units = ", ".join(_DURATION_UNITS)
+        raise ValueError(f"expected a number and a unit ({units}), such as 2y, got {value!r}")
+    return float(match[1]) * _DURATION_UNITS[match[2]]
+
+
+def impact(argv: list[str]) -> int:
+    parser = argparse.ArgumentParser(
+        prog="validate-git-blame-ignore-revs impact",
+        description="Report how many lines of `git blame` output each entry changes.",
+    )
+    _ = parser.add_argument("file_path", type=Path, help="Path to the .git-blame-ignore-revs file.")
+
+    args = parser.parse_args(argv)
+
+    retval = 0
+
+    try:
+        valid_hashes = validate_git_blame_ignore_revs(args.file_path)["valid_hashes"]
+        result = compute_blame_impact(args.file_path)
+    except FileNotFoundError as e:
+        print(e)
+        return ErrorCode.FileNotFound.value
+    except CalledProcessError as e:
+        print(f"Failed to run {' '.join(e.cmd)}: {e.stderr.strip()}")
+        return ErrorCode.CommitsNotPresent.value
+
+    print("Blame impact:")
+    for line_number, commit_hash in valid_hashes.items():
+        if line_number in result:
+            print(f"  Line {line_number}: {commit_hash} changes {result[line_number]} line(s)")
+        else:
+            print(f"  Line {line_number}: {commit_hash} is not present in the Git history")
+            retval |= ErrorCode.CommitsNotPresent.value
+
+    return retval
+
+
+def explain(argv: list[str]) -> int:
+    parser = argparse.ArgumentParser(
+        prog="validate-git-blame-ignore-revs explain",
+        description="Print everything known about one commit listed in the file.",
+    )
+    _ = parser.add_argument("file_path", type=Path, help="Path to the .git-blame-ignore-revs file.")
+    _ = parser.add_argument("rev", help="The commit to explain, as a full or abbreviated hash.")
+
+    args = parser.parse_args(argv)
+
+    try:
+        result = explain_entry(args.file_path, args.rev)
+    except FileNotFoundError as e:
+        print(e)
+        return ErrorCode.FileNotFound.value
+    except AmbiguousRevisionError as e:
+        print(f"{args.rev} is ambiguous. It could be any of:")
+        for candidate, description in e.candidates.items():
+            print(f"  {candidate} {description}")
+        return ErrorCode.CommitsNotPresent.value
+    except CalledProcessError:
+        print(f"{args.rev} is not present in the Git history")
+        return ErrorCode.CommitsNotPresent.value
+
+    commit = result["commit"]
+    print(f"Commit {commit['hash']}:")
+    if result["line_number"] is None:
+        print(f"  Entry: not listed in {display_path(args.file_path)}")
+    else:
+        print(f"  Entry: line {result['line_number']}")
+        print(f"  Comment: {result['comment'] if result['comment'] is not None else '(none)'}")
+    print(f"  Author: {commit['author_name']} <{commit['author_email']}>")
+    print(f"  Date: {commit['author_date']}")
+    print(f"  Subject: {commit['subject']}")
+    print(f"  Merge commit: {'yes' if result['is_merge'] else 'no'}")
+    print(f"  Reachable from HEAD: {'yes' if result['reachable'] else 'no'}")
+    print(f"  Reverted by: {', '.join(result['reverted_by']) or '(none)'}")
+    print(f"  Whitespace-only changes: {result['whitespace_ratio']:.1%}")
+    print("  Diffstat:")
+    for line in result["diffstat"].splitlines():
+        print(f"    {line.strip()}")
+    if result["checks"]:
+        print("  Checks:")
+        for check, passed in result["checks"].items():
+            print(f"    {check}: {'pass' if passed else 'fail'}")
+
+    return 0 if all(result["checks"].values()) else ErrorCode.FailedAdditionalChecks.value
+
+
+def explain_check(argv: list[str]) -> int:
+    parser = argparse.ArgumentParser(
+        prog="validate-git-blame-ignore-revs explain-check",
+        description="Describe what a check reports and why, with examples and the options that enable it. Without an ID, list every check.",
+    )
+    _ = parser.add_argument(
+        "check_id",
+        metavar="ID",
+        nargs="?",
+        help="The ID of the check, as in the JSON and SARIF reports, such as missing-commits.",
+    )
+
+    args = parser.parse_args(argv)
+
+    if args.check_id is None:
+        for check_id, check in CHECKS.items():
+            print(f"{check_id}: {check.title}")
+        return 0
+    if args.check_id not in CHECKS:
+        parser.error(f"Unknown check {args.check_id!r}. Run without an ID to list every check.")
+
+    check = CHECKS[args.check_id]
+    print(f"{args.check_id}: {check.title}")
+    print(f"  Severity: {check.severity.value}")
+    print(f"  Options: {', '.join(check.options) or '(always run)'}")
+    for paragraph in (check.description, check.rationale):
+        print()
+        print(textwrap.fill(paragraph, width=80, initial_indent="  ", subsequent_indent="  "))
+    print("\n  Reported:")
+    print(textwrap.indent(check.failing, "    "))
+    print("\n  Passes:")
+    print(textwrap.indent(check.passing, "    "))
+    return 0
+
+
+def annotate(argv: list[str]) -> int:
+    parser = argparse.ArgumentParser(
+        prog="validate-git-blame-ignore-revs annotate",
+        description="Print each entry with the author, date, subject, and diffstat of its commit.",
+    )
     _ = parser.add_argument("file_path", type=Path, help="Path to the .git-blame-ignore-revs file.")
+    _ = parser.add_argument(
+        "--write",
+        action="store_true",
+        help="Also add the author, date, and diffstat to the file as comments above each entry that doesn't have them yet.",
+    )
+
+    args = parser.parse_args(argv)
+
+    try:
+        result = annotate_entries(args.file_path)
+    except FileNotFoundError as e:
+        print(e)
+        return ErrorCode.FileNotFound.value
+
+    retval = 0
+    for line_number, annotation in result.items():
+        commit = annotation["commit"]
+        if commit is None:
+            print(f"Line {line_number}: not a commit in the Git history")
+            retval |= ErrorCode.CommitsNotPresent.value
+            continue
+        print(f"Line {line_number}: {commit['hash']}")
+        print(f"  Author: {commit['author_name']} <{commit['author_email']}>")
+        print(f"  Date: {commit['author_date']}")
+        print(f"  Subject: {commit['subject']}")
+        print(f"  Diffstat: {annotation['shortstat'] or '(no changes)'}")
+
+    if args.write:
+        text = annotated_text(args.file_path, result)
+        # Replace the file only once the new contents are complete, in case of interruption.
+        # The text already has the line breaks the file uses.
+        write_output(str(args.file_path.resolve()), text, newline="")
+        print(f"\nWrote the annotations to {display_path(args.file_path)}")
+
+    return retval
+
+
+def import_revs(argv: list[str]) -> int:
+    parser = argparse.ArgumentParser(
+        prog="validate-git-blame-ignore-revs import",
+        description="Convert a list of commits exported from another tool into commented entries for the file.",
+    )
+    _ = parser.add_argument(
+        "source", help="Path to the list of commits to convert, or - for standard input."
+    )
+    _ = parser.add_argument(
+        "--format",
+        choices=IMPORT_FORMATS,
+        default=IMPORT_FORMATS[0],
+        help="The format of the list: plain text with a hash on each line, a JSON or YAML list, or a Markdown changelog linking to commits. auto guesses it from the file name and contents (default: %(default)s).",
+    )
+    _ = parser.add_argument(
+        "--append",
+        metavar="FILE",
+        type=Path,
+        help="Append the entries to this .git-blame-ignore-revs file, skipping commits it already lists, rather than printing them. Commits are looked up in its repository rather than the one in the current directory.",
+    )
+
+    args = parser.parse_args(argv)
+
+    template = DEFAULT_COMMENT_TEMPLATE
+    config_path = find_config(args.append or Path(DEFAULT_FILE_NAME))
+    if config_path is not None:
+        try:
+            template = load_config(config_path)["comment_template"] or template
+        except (OSError, ValueError, configparser.Error) as e:
+            parser.error(f"Could not read {display_path(config_path)}: {e}")
+
+    try:
+        if args.source == "-":
+            text = sys.stdin.read()
+        else:
+            text = Path(args.source).read_text(encoding="utf-8")
+    except OSError as e:
+        print(e)
+        return ErrorCode.FileNotFound.value
+    source_format = args.format
+    if source_format == "auto":
+        source_format = detect_format(args.source, text)
+    try:
+        entries = parse_entries(text, source_format)
+    except ValueError as e:
+        print(f"Could not read {args.source} as {source_format}: {e}")
+        return ErrorCode.SyntaxProblem.value
+
+    existing_text = ""
+    if args.append is not None and args.append.exists():
+        existing_text = args.append.read_bytes().decode("utf-8")
+    listed = {
+        parsed_line.text
+        for parsed_line in parse_lines(existing_text.splitlines())
+        if parsed_line.kind is LineKind.Hash
+    }
+    repository = Path.cwd() if args.append is None else args.append.resolve().parent
+    resolved, problems = resolve_entries(entries, repository, template)
+
+    retval = 0
+    # Problems go to standard error, so that the entries printed can be redirected to a file
+    for location, problem in problems.items():
+        print(f"{args.source}, {location}: {problem}", file=sys.stderr)
+        retval |= ErrorCode.CommitsNotPresent.value
+    new_entries = [entry for entry in resolved if entry.commit not in listed]
+
+    if args.append is None:
+        sys.stdout.write(format_entries(new_entries))
+        return retval
+
+    # Keep the line breaks the file uses, such as `\r\n` in a file checked out on Windows
+    newline = line_ending(existing_text)
+    if existing_text and not existing_text.endswith("\n"):
+        existing_text += newline
+    # The imported entries form a section of their own
+    separator = newline if existing_text.strip() and new_entries else ""
+    new_text = format_entries(new_entries).replace("\n", newline)
+    write_output(str(args.append.resolve()), existing_text + separator + new_text, newline="")
+    print(f"Appended {len(new_entries)} entry(s) to {display_path(args.append)}.")
+    if len(resolved) > len(new_entries):
+        print(f"Skipped {len(resolved) - len(new_entries)} commit(s) that were already listed.")
+    return retval
+
+
+def export(argv: list[str]) -> int:
+    parser = argparse.ArgumentParser(
+        prog="validate-git-blame-ignore-revs export",
+        description="Render the entries as a table of their commits' hash, date, author, and subject and their comments, for documentation or dashboards.",
+    )
+    _ = parser.add_argument("file_path", type=Path, help="Path to the .git-blame-ignore-revs file.")
+    _ = parser.add_argument(
+        "--format",
+        choices=EXPORT_FORMATS,
+        default=EXPORT_FORMATS[0],
+        help="Render a Markdown table or a JSON list (default: %(default)s).",
+    )
+    _ = parser.add_argument(
+        "--output",
+        metavar="PATH",
+        default="-",
+        help="Write the table to this file instead of standard output, creating its directory if needed. Use - for standard output.",
+    )
+
+    args = parser.parse_args(argv)
+    # Looking up the commits changes the working directory
+    output = args.output if args.output == "-" else str(Path(args.output).resolve())
+
+    try:
+        entries = export_entries(args.file_path)
+    except FileNotFoundError as e:
+        print(e)
+        return ErrorCode.FileNotFound.value
+
+    render = render_export_json if args.format == "json" else render_export_markdown
+    write_output(output, render(entries))
+
+    retval = 0
+    for entry in entries:
+        if entry["commit"] is None:
+            print(f"Line {entry['line']}: not a commit in the Git history", file=sys.stderr)
+            retval |= ErrorCode.CommitsNotPresent.value
+    return retval
+
+
+def diff(argv: list[str]) -> int:
+    parser = argparse.ArgumentParser(
+        prog="validate-git-blame-ignore-revs diff",
+        description="Compare the entries of two versions of the file and validate the added ones.",
+    )
+    _ = parser.add_argument(
+        "paths",
+        type=Path,
+        nargs="+",
+        metavar="file_path",
+        help="The old and new versions of the .git-blame-ignore-revs file, or only the new one with --base.",
+    )
+    _ = parser.add_argument(
+        "--base",
+        metavar="REV",
+        help="Compare with the file as of this revision, such as the base branch of a pull request.",
+    )
+
+    args = parser.parse_args(argv)
+
+    if args.base is None and len(args.paths) != 2:
+        parser.error("Give the old and new files, or the new file and --base.")
+    if args.base is not None and len(args.paths) != 1:
+        parser.error("Give only the new file with --base.")
+    new_path = args.paths[-1]
+
+    try:
+        if args.base is None:
+            old_lines = args.paths[0].read_text(encoding="utf-8").splitlines()
+        else:
+            old_lines = read_at_revision(new_path, args.base)
+        new_lines = new_path.read_text(encoding="utf-8").splitlines()
+        result = compare_entries(old_lines, new_lines)
+        checks = check_added_entries(new_path, result["added"])
+    except FileNotFoundError as e:
+        print(e)
+        return ErrorCode.FileNotFound.value
+    except CalledProcessError as e:
+        print(f"Failed to run {' '.join(e.cmd)}: {e.stderr.strip()}")
+        return ErrorCode.CommitsNotPresent.value
+
+    retval = 0
+    if result["added"]:
+        print(f"Added ({len(result['added'])}):")
+        for line_number, commit in result["added"].items():
+            failed = [check for check, passed in checks[line_number].items() if not passed]
+            if failed:
+                print(f"  Line {line_number}: {commit} (fails {', '.join(failed)})")
+                retval |= ErrorCode.FailedAdditionalChecks.value
+            else:
+                print(f"  Line {line_number}: {commit} (passes all checks)")
+    else:
+        print("No entries added.")
+    if result["removed"]:
+        print(f"\nRemoved ({len(result['removed'])}):")
+        for line_number, commit in result["removed"].items():
+            print(f"  Line {line_number}: {commit}")
+    if result["recommented"]:
+        print(f"\nComments changed ({len(result['recommented'])}):")
+        for line_number, (old_comments, new_comments) in result["recommented"].items():
+            print(f"  Line {line_number}: {new_lines[line_number - 1].strip()}")
+            print(f"    Old: {' / '.join(old_comments) or '(none)'}")
+            print(f"    New: {' / '.join(new_comments) or '(none)'}")
+    if result["reordered"]:
+        print(f"\nReordered ({len(result['reordered'])}):")
+        for line_number, commit in result["reordered"].items():
+            print(f"  Line {line_number}: {commit}")
+
+    return retval
+
+
+def coverage(argv: list[str]) -> int:
+    parser = argparse.ArgumentParser(
+        prog="validate-git-blame-ignore-revs coverage",
+        description="Estimate how many formatting sweeps in history are listed in the file.",
+    )
+    _ = parser.add_argument("file_path", type=Path, help="Path to the .git-blame-ignore-revs file.")
+    _ = parser.add_argument(
+        "--min-ratio",
+        type=float,
+        default=0.9,
+        help="Minimum fraction of whitespace-only changed lines for a commit to count as a formatting sweep (default: %(default)s).",
+    )
+    _ = parser.add_argument(
+        "--min-lines",
+        type=int,
+        default=10,
+        help="Minimum number of changed lines for a commit to count as a formatting sweep (default: %(default)s).",
+    )
+    add_history_range_arguments(parser, "formatting sweeps")
+
+    args = parser.parse_args(argv)
+
+    try:
+        result = compute_coverage(
+            args.file_path, args.min_ratio, args.min_lines, history_range(args)
+        )
+    except FileNotFoundError as e:
+        print(e)
+        return ErrorCode.FileNotFound.value
+
+    covered = len(result["covered"])
+    total = covered + len(result["uncovered"])
+    if total:
+        print(f"Formatting sweep coverage: {covered} of {total} ({covered / total:.1%})")
+    else:
+        print("No formatting sweeps found in the Git history.")
+
+    if result["covered"]:
+        print(f"\nCovered ({covered}):")
+        for commit_hash, commit_message in result["covered"].items():
+            print(f"  Commit {commit_hash}: {commit_message}")
+    if result["uncovered"]:
+        print(f"\nUncovered ({len(result['uncovered'])}):")
+        for commit_hash, commit_message in result["uncovered"].items():
+            print(f"  Commit {commit_hash}: {commit_message}")
+
+    return 0
+
+
+def audit(argv: list[str]) -> int:
+    parser = argparse.ArgumentParser(
+        prog="validate-git-blame-ignore-revs audit",
+        description="Find formatter runs in history that are missing from the file.",
+    )
+    _ = parser.add_argument("file_path", type=Path, help="Path to the .git-blame-ignore-revs file.")
+    _ = parser.add_argument(
+        "--tools",
+        type=lambda value: [tool.strip() for tool in value.split(",") if tool.strip()],
+        default=list(FORMATTERS),
+        help=f"Comma-separated names of the formatters to look for (default: {','.join(FORMATTERS)}).",
+    )
+    _ = parser.add_argument(
+        "--min-ratio",
+        type=float,
+        default=0.9,
+        help="Minimum fraction of whitespace-only changed lines for a commit to count as a formatting sweep (default: %(default)s).",
+    )
+    _ = parser.add_argument(
+        "--min-lines",
+        type=int,
+        default=10,
+        help="Minimum number of changed lines for a commit to count as a formatting sweep (default: %(default)s).",
+    )
+    _ = parser.add_argument(
+        "--trailer",
+        metavar="TRAILER",
+        help="Also find commits whose message carries this trailer, such as 'Blame-Ignore: true', or only its key, whatever they change. Overrides trailer in the configuration file next to the file.",
+    )
+    add_history_range_arguments(parser, "formatter runs")
+
+    args = parser.parse_args(argv)
+
+    trailer = args.trailer
+    config_path = find_config(args.file_path)
+    if trailer is None and config_path is not None:
+        try:
+            trailer = load_config(config_path)["trailer"]
+        except (OSError, ValueError, configparser.Error) as e:
+            parser.error(f"Could not read {display_path(config_path)}: {e}")
+    if trailer is not None and not is_valid_trailer(trailer):
+        parser.error(f"Invalid --trailer {trailer!r}: expected Key or Key: value.")
+
+    try:
+        result = audit_formatter_commits(
+            args.file_path,
+            args.tools,
+            args.min_ratio,
+            args.min_lines,
+            history_range(args),
+            trailer,
+        )
+    except FileNotFoundError as e:
+        print(e)
+        return ErrorCode.FileNotFound.value
+
+    if not result:
+        print("All formatter commits are present in the file!")
+        return 0
+
+    print(f"Missing formatter commits ({len(result)}):")
+    for commit_hash, (tool, commit_message) in result.items():
+        print(f"  Commit {commit_hash} ({tool}): {commit_message}")
+    return ErrorCode.FailedAdditionalChecks.value
+
+
+def verify_blame(argv: list[str]) -> int:
+    parser = argparse.ArgumentParser(
+        prog="validate-git-blame-ignore-revs verify-blame",
+        description="Compare `git blame` of a source file with and without the ignore file.",
+    )
+    _ = parser.add_argument("source_path", type=Path, help="Path to the file to blame.")
+    _ = parser.add_argument(
+        "--ignore-revs-file",
+        type=Path,
+        help="Path to the .git-blame-ignore-revs file. Defaults to blame.ignoreRevsFile, or .git-blame-ignore-revs at the top level of the repository.",
+    )
+
+    args = parser.parse_args(argv)
+
+    try:
+        result = compare_blame(args.source_path, args.ignore_revs_file)
+    except FileNotFoundError as e:
+        print(e)
+        return ErrorCode.FileNotFound.value
+    except CalledProcessError as e:
+        print(f"Failed to run {' '.join(e.cmd)}: {e.stderr.strip()}")
+        return ErrorCode.CommitsNotPresent.value
+
+    changes = result["changes"]
+    print(
+        f"Ignoring the revisions in {display_path(result['ignore_revs_file'])} changes the "
+        f"attribution of {len(changes)} of {result['total_lines']} line(s) in "
+        f"{display_path(args.source_path)}."
+    )
+
+    # (commit without ignoring, commit with ignoring) -> number of lines
+    moves: dict[tuple[str, str], int] = {}
+    for move in changes.values():
+        moves[move] = moves.get(move, 0) + 1
+    if moves:
+        print("\nReattributed lines:")
+        for (commit_before, commit_after), count in moves.items():
+            print(f"  {commit_before} -> {commit_after}: {count} line(s)")
+
+    return 0
+
+
+def remote_validate(argv: list[str]) -> int:
+    parser = argparse.ArgumentParser(
+        prog="validate-git-blame-ignore-revs remote-validate",
+        description="Validate the .git-blame-ignore-revs file of repositories on GitHub or GitLab with the forge API alone, without cloning them, such as to audit every repository of an organization.",
+    )
+    _ = parser.add_argument(
+        "repositories",
+        metavar="owner/name",
+        nargs="+",
+        help="The repositories to validate, as owner/name on the --host, or as URLs.",
+    )
+    _ = parser.add_argument(
+        "--host",
+        default="github.com",
+        help="The host of the forge the repositories given as owner/name are on. Hosts other than github.com and gitlab.com must be ones gh or glab is logged in to, or named in GH_HOST or GITLAB_HOST (default: %(default)s).",
+    )
+    _ = parser.add_argument(
+        "--path",
+        default=DEFAULT_FILE_NAME,
+        help="The path of the file from the top level of each repository (default: %(default)s).",
+    )
+    _ = parser.add_argument(
+        "--ref",
+        help="Read the file from this branch, tag, or commit instead of the default branch.",
+    )
+    _ = parser.add_argument(
+        "--forge-cache-ttl",
+        metavar="SECONDS",
+        type=int,
+        default=86400,
+        help="Reuse the responses of the forge API for this long. Use 0 to always make the requests (default: %(default)s).",
+    )
+    _ = parser.add_argument(
+        "--retries",
+        metavar="N",
+        type=int,
+        default=3,
+        help="Make requests that fail with a server or network error up to this many more times (default: %(default)s).",
+    )
+    _ = parser.add_argument(
+        "--retry-delay",
+        metavar="SECONDS",
+        type=float,
+        default=1.0,
+        help="How long to wait before the first retry. Each retry after that waits twice as long as the one before, up to 30 seconds (default: %(default)s).",
+    )
+
+    args = parser.parse_args(argv)
+
+    repositories: list[ForgeRepository] = []
+    for value in args.repositories:
+        repository = parse_repository(value, args.host)
+        if repository is None:
+            parser.error(f"Invalid repository {value!r}: expected owner/name or a URL.")
+        repositories.append(repository)
+    if args.forge_cache_ttl < 0:
+        parser.error("--forge-cache-ttl must not be negative.")
+    if args.retries < 0 or args.retry_delay < 0:
+        parser.error("--retries and --retry-delay must not be negative.")
+    set_default_policy(retry_policy(args))
+    cache = None
+    if args.forge_cache_ttl:
+        cache = ResponseCache(cache_dir() / "forge", args.forge_cache_ttl)
+
+    retval = 0
+    # Repositories on the same host share a client, which looks up the token once
+    clients: dict[str, ForgeClient] = {}
+    for index, repository in enumerate(repositories):
+        name = f"{repository.owner}/{repository.name}"
+        if len(repositories) > 1:
+            # Head the report on each repository with its name, like `head` does
+            if index:
+                print()
+            print(f"==> {name} <==")
+        try:
+            if repository.host not in clients:
+                clients[repository.host] = ForgeClient(repository.host, cache=cache)
+            result = validate_remote(repository, args.path, args.ref, clients[repository.host])
+        except (OSError, UnicodeDecodeError, UnknownForgeError) as e:
+            print(f"Could not check {name} on {repository.host}: {e}")
+            retval |= ErrorCode.FileNotFound.value
+            continue
+        if result is None:
+            print(f"{name} on {repository.host} has no {args.path}.")
+            retval |= ErrorCode.FileNotFound.value
+            continue
+
+        if result["errors"]:
+            print(f"Errors ({len(result['errors'])}):")
+            for line_number, line in result["errors"].items():
+                print(f"  Line {line_number}: {line}")
+            retval |= ErrorCode.SyntaxProblem.value
+        if result["missing_commits"]:
+            print(f"Missing commits ({len(result['missing_commits'])}):")
+            for line_number, commit_hash in result["missing_commits"].items():
+                print(f"  Line {line_number}: {commit_hash}")
+            retval |= ErrorCode.CommitsNotPresent.value
+        if not result["errors"] and not result["missing_commits"]:
+            print(f"All {len(result['valid_hashes'])} entries are commits in {name}!")
+    return retval
+
+
+def prefetch_commits(file_path: Path, source: ContentSource) -> Optional[int]:
+    """
+    Fetch the objects the checks read for each commit a file lists, if it is in a partial clone.
+
+    Returns:
+        Optional[int]: The number of commits listed, or None if the file isn't in a partial clone.
+    """
+    if promisor_remote(file_path.parent) is None:
+        return None
+    # The hashes are ASCII, whatever the comments are encoded as
+    lines = source.read_text(file_path, "auto").splitlines()
+    commits = [line.strip() for line in lines if commit_hash_regex.match(line.strip())]
+    cwd = os.getcwd()
+    os.chdir(file_path.parent)
+    try:
+        prefetch_diffs(commits)
+    finally:
+        os.chdir(cwd)
+    return len(commits)
+
+
+def warm_cache(argv: list[str]) -> int:
+    parser = build_parser()
+    parser.prog = "validate-git-blame-ignore-revs warm-cache"
+    parser.description = "Validate files with --cache and the given options without printing the report, so that validating them again with the same options, such as in a hook or an editor, is instant. In partial clones, the objects the checks read for each listed commit are fetched first, a commit at a time rather than an object at a time."
+    args = parse_arguments(parser, argv)
+    check_arguments(parser, args)
+    args.cache = True
+    # No report is printed or published
+    args.hyperlinks = False
+    args.bitbucket_insights = False
+    if args.workspace is not None:
+        if not args.workspace.is_dir():
+            parser.error(f"--workspace {display_path(args.workspace)} is not a directory.")
+        args.file_paths += workspace_ignore_files(args.workspace)
+    if not args.file_paths:
+        parser.error("Give the path to a .git-blame-ignore-revs file, or --workspace.")
+    configs = [file_config(parser, args, file_path) for file_path in args.file_paths]
+    configure_git_environment(args)
+    _ = skip_git_checks(args)
+
+    retval = 0
+    with git_tracing(args):
+        for file_path in [] if args.no_git else args.file_paths:
+            try:
+                listed = prefetch_commits(Path(os.path.abspath(file_path)), content_source(args))
+            except FileNotFoundError:
+                # Reported when the file is validated
+                continue
+            except CalledProcessError as e:
+                print(f"Could not fetch the objects for {display_path(file_path)}: {e.stderr}")
+                retval |= ErrorCode.CommitsNotPresent.value
+                continue
+            if listed is not None:
+                print(
+                    f"Fetched the objects for the {listed} commit(s) in {display_path(file_path)}."
+                )
+        for _, file_report in check_files(args, configs, []):
+            if not file_report.sections:
+                print("\n".join(file_report.notes))
+                retval |= file_report.exit_code
+            else:
+                print(f"Cached the results for {file_report.path}.")
+    return retval
+
+
+def introduced_by(introduction: Introduction) -> str:
+    """Describe the commit that introduced a finding, and who made it, for `history`."""
+    version = introduction.version
+    if version is None:
+        return "Not committed yet"
+    where = "At or before" if introduction.at_or_before else "Introduced in"
+    return f"{where} {version.commit[:12]} on {version.date} by {version.author}: {version.subject}"
+
+
+def report_introductions(args: argparse.Namespace, file_path: Path, config: Config) -> int:
+    """Print where each finding of a file was introduced in its history, returning the exit code."""
+    cwd = os.getcwd()
+    translate = translator(args.lang)
+
+    def check(source: Optional[ContentSource] = None) -> FileReport:
+        try:
+            return check_file(args, file_path, config, [], source=source)[1]
+        finally:
+            # The checks that use Git change directory, which would break a relative path
+            os.chdir(cwd)
+
+    file_report = check()
+    if not file_report.sections:
+        print("\n".join(file_report.notes))
+        return file_report.exit_code
+    try:
+        versions = file_versions(file_path, args.max_versions)
+    except CalledProcessError as e:
+        print(f"Failed to run {' '.join(e.cmd)}: {e.stderr.strip()}")
+        return ErrorCode.CommitsNotPresent.value
+
+    # Each version is only checked if a finding may be older still
+    version_findings = (
+        (version, finding_keys(check(GitRevision(version.commit)).sections))
+        for version in versions
+    )
+    complete = args.max_versions is None or len(versions) < args.max_versions
+    introductions = find_introductions(
+        finding_keys(file_report.sections), version_findings, complete
+    )
+
+    sections = [section for section in file_report.sections if section.findings]
+    if not sections:
+        print(f"{file_report.path} has no findings!")
+    for index, section in enumerate(sections):
+        if index:
+            print()
+        print(f"{translate(section.title)} ({len(section.findings)}):")
+        for finding in section.findings:
+            print(f"  {finding.label}: {finding.text}" if finding.text else f"  {finding.label}:")
+            print(f"    {introduced_by(introductions[(section.title, finding.commit)])}")
+    return file_report.exit_code
+
+
+def history(argv: list[str]) -> int:
+    parser = build_parser()
+    parser.prog = "validate-git-blame-ignore-revs history"
+    parser.description = "Validate each version of the file in its Git history with the given options, and report the commit that introduced each finding of the current version and who made it, so it can be followed up with them or reverted. A finding was introduced by the oldest commit whose version of the file has it without a break since."
+    _ = parser.add_argument(
+        "--max-versions",
+        metavar="N",
+        type=int,
+        help="Only validate the N most recent versions of the file. Findings that all of them have are reported as introduced at or before the oldest.",
+    )
+    args = parse_arguments(parser, argv)
+    check_arguments(parser, args)
+    if args.no_git:
+        parser.error("The versions of the file are read with Git, so --no-git can't be used.")
+    if (
+        args.staged
+        or args.at_rev is not None
+        or args.diff_base is not None
+        or args.append_only is not None
+    ):
+        parser.error(
+            "--staged, --at-rev, --diff-base, and --append-only choose other versions of the file, "
+            "so they can't be used."
+        )
+    if args.max_versions is not None and args.max_versions < 1:
+        parser.error("--max-versions must be at least 1.")
+    if args.workspace is not None:
+        if not args.workspace.is_dir():
+            parser.error(f"--workspace {display_path(args.workspace)} is not a directory.")
+        args.file_paths += workspace_ignore_files(args.workspace)
+    if not args.file_paths:
+        parser.error("Give the path to a .git-blame-ignore-revs file, or --workspace.")
+    # The result for each old version would replace that of the current one
+    args.cache = False
+    # Only the findings are printed, and no report is published
+    args.hyperlinks = False
+    args.bitbucket_insights = False
+    configs = [file_config(parser, args, file_path) for file_path in args.file_paths]
+    configure_git_environment(args)
+
+    retval = 0
+    with git_tracing(args):
+        for index, (file_path, config) in enumerate(zip(args.file_paths, configs)):
+            if len(args.file_paths) > 1:
+                # Head the findings of each file with its path, like `head` does
+                if index:
+                    print()
+                print(f"==> {display_path(file_path)} <==")
+            retval |= report_introductions(args, file_path, config)
+    return retval
+
+
+def repository_argument(value: str) -> tuple[str, Path]:
+    """Parse a `NAME=PATH` argument of `serve --repository`."""
+    name, separator, path = value.partition("=")
+    if not separator or not name or not path:
+        raise argparse.ArgumentTypeError(f"expected NAME=PATH, got {value!r}")
+    return name, Path(path)
+
+
+def serve_validator(
+    args: argparse.Namespace,
+    repositories: dict[str, Path],
+    configs: dict[Optional[str], Config],
+    metrics: Optional[Metrics] = None,
+) -> Validator:
+    """
+    Return how `serve` validates the contents sent for a repository with the checks in `args`.
+
+    The contents are validated as the file named by the configuration at the
+    top level of the repository, or in the working directory if the request
+    names no repository, which is only allowed when no check uses Git. Each
+    file validated and its findings are counted in `metrics`, if given.
+    """
+    cwd = Path.cwd()
+
+    def validate(repository: Optional[str], content: str) -> str:
+        if repository is None:
+            if args.call_git:
+                raise BadRequest("A repository is required for the checks that use Git")
+            directory = cwd
+        elif repository in repositories:
+            directory = repositories[repository]
+        else:
+            raise BadRequest(f"Unknown repository: {repository}")
+        config = configs[repository]
+        file_path = directory / config["file_name"]
+        try:
+            _, file_report = check_file(
+                args, file_path, config, [], source=InMemory({file_path: content})
+            )
+        finally:
+            # The checks that use Git change directory
+            os.chdir(cwd)
+        if metrics is not None:
+            metrics.record_validation(repository, file_report.sections, file_report.exit_code)
+        return render_json([file_report])
+
+    return validate
+
+
+def serve(argv: list[str]) -> int:
+    parser = argparse.ArgumentParser(
+        prog="validate-git-blame-ignore-revs serve",
+        description="Validate files sent over HTTP, such as by bots and webhooks for many repositories. POST a JSON object with the contents of the file as `content` and the name of its repository as `repository` to /validate to get the JSON report on it. GET /metrics for counts of validations, findings, and requests, in the OpenMetrics format Prometheus scrapes.",
+    )
+    _ = parser.add_argument(
+        "--host",
+        default="127.0.0.1",
+        help="The address to listen on (default: %(default)s).",
+    )
+    _ = parser.add_argument(
+        "--port",
+        type=int,
+        default=8000,
+        help="The port to listen on, or 0 for any free port (default: %(default)s).",
+    )
+    _ = parser.add_argument(
+        "--repository",
+        metavar="NAME=PATH",
+        type=repository_argument,
+        action="append",
+        default=[],
+        help="A local clone that requests can name as NAME. The file sent is validated as if it were at the top level of the clone, using its configuration file. Can be repeated.",
+    )
+    _ = parser.add_argument(
+        "options",
+        nargs=argparse.REMAINDER,
+        help="The checks to run and their options, as when validating a file, after --.",
+    )
+
+    args = parser.parse_args(argv)
+
+    options = args.options[1:] if args.options[:1] == ["--"] else args.options
+    check_parser = build_parser()
+    check_args = parse_arguments(check_parser, options)
+    if check_args.file_paths or check_args.workspace is not None:
+        parser.error("Files are sent in requests, so they can't be given as options.")
+    if (
+        check_args.staged
+        or check_args.at_rev is not None
+        or check_args.diff_base is not None
+        or check_args.append_only is not None
+    ):
+        parser.error(
+            "--staged, --at-rev, --diff-base, and --append-only read the file with Git, "
+            "so they can't be used."
+        )
+    check_arguments(check_parser, check_args)
+    configure_git_environment(check_args)
+    # The report is JSON rather than for a terminal
+    check_args.hyperlinks = False
+
+    repositories = dict(args.repository)
+    for name, path in repositories.items():
+        if not path.is_dir():
+            parser.error(f"The repository {name} at {display_path(path)} is not a directory.")
+        repositories[name] = path.resolve()
+    configs: dict[Optional[str], Config] = {
+        name: file_config(check_parser, check_args, path / DEFAULT_FILE_NAME)
+        for name, path in repositories.items()
+    }
+    configs[None] = file_config(check_parser, check_args, Path.cwd() / DEFAULT_FILE_NAME)
+
+    metrics = Metrics(cache_stats if check_args.cache else None)
+    try:
+        server = ValidationServer(
+            (args.host, args.port),
+            serve_validator(check_args, repositories, configs, metrics),
+            metrics,
+        )
+    except OSError as e:
+        parser.error(f"Could not listen on {args.host}:{args.port}: {e}")
+    host, port = server.server_address[:2]
+    print(
+        f"Serving on http://{host}:{port}/validate, with metrics on http://{host}:{port}/metrics",
+        flush=True,
+    )
+    with server:
+        try:
+            server.serve_forever()
+        except KeyboardInterrupt:
+            pass
+    return 0
+
+
+SUBCOMMANDS: dict[str, Callable[[list[str]], int]] = {
+    "impact": impact,
+    "explain": explain,
+    "explain-check": explain_check,
+    "annotate": annotate,
+    "import": import_revs,
+    "export": export,
+    "diff": diff,
+    "coverage": coverage,
+    "audit": audit,
+    "verify-blame": verify_blame,
+    "serve": serve,
+    "warm-cache": warm_cache,
+    "history": history,
+    "remote-validate": remote_validate,
+}
+
+
+def forge_repository(file_path: Path, remote: str) -> Optional[ForgeRepository]:
+    """Return the repository on a forge that a remote of the file's repository points to."""
+    try:
+        return parse_remote_url(remote_url(remote, file_path.parent))
+    except (CalledProcessError, GitNotFoundError):
+        return None
+
+
+def publish_bitbucket_insights(
+    file_path: Path, repository: Optional[ForgeRepository], file: FileReport
+) -> str:
+    """
+    Publish the findings on a file as a Code Insights report on the commit checked out.
+
+    In Bitbucket Pipelines, the report is on the commit being built.
+
+    Returns:
+        str: What happened, for the notes on the file.
+
+    Raises:
+        OSError: If the report could not be published.
+    """
+    if repository is None or repository.host != BITBUCKET_HOST:
+        raise OSError(f"the remote isn't on {BITBUCKET_HOST}")
+    commit_hash = os.environ.get("BITBUCKET_COMMIT") or head_commit(file_path.parent)
+    path = repository_path(file_path)
+    report, annotations = code_insights(file, path)
+    report_id = f"validate-git-blame-ignore-revs-{rule_id(path)}"
+    publish_code_insights(repository, commit_hash, report_id, report, annotations)
+    return f"Published a Code Insights report on {commit_hash[:12]} to Bitbucket."
+
+
+def link_commits(sections: list[Section], repository: ForgeRepository) -> list[Section]:
+    """Add the URL of the commit on the forge to each finding about a commit."""
+    return [
+        section._replace(
+            findings=[
+                finding._replace(url=commit_url(repository, finding.commit))
+                if commit_hash_regex.match(finding.commit)
+                else finding
+                for finding in section.findings
+            ]
+        )
+        for section in sections
+    ]
+
+
+def error_details(result: ValidationResult, line_number: int) -> tuple[str, ...]:
+    """Return what is wrong with an invalid line that is almost a hash, and what it likely meant."""
+    details: tuple[str, ...] = ()
+    problem = result["near_misses"].get(line_number)
+    if problem is not None:
+        details += (problem[0].upper() + problem[1:],)
+    if line_number in result["suggested_hashes"]:
+        details += (f"Did you mean {result['suggested_hashes'][line_number]}?",)
+    return details
+
+
+def reason_finding(line_number: int, commit: str, reason: tuple[MissingReason, str]) -> Finding:
+    """Return a finding about an entry that isn't a usable commit, giving the reason."""
+    code, explanation = reason
+    return line_finding(
+        line_number, commit, f"{commit} ({explanation}) [{code.value}]", reason=code.value
+    )
+
+
+def unreachable_finding(
+    line_number: int,
+    commit: str,
+    text: str,
+    problems: Iterable[str],
+    details: tuple[str, ...] = (),
+) -> Finding:
+    """Return a finding about an entry's reachability, giving the reason if a ref was checked."""
+    # The other problems are "could not be checked: ..." and "no ref matches ..."
+    if any(problem.startswith("not ") for problem in problems):
+        reason = MissingReason.UnreachableFromRef.value
+        return line_finding(line_number, commit, f"{text} [{reason}]", details, reason)
+    return line_finding(line_number, commit, text, details)
+
+
+def unreferenced_finding(line_number: int, commit: str, in_reflog: bool) -> Finding:
+    """Return a finding about an entry for a commit no ref reaches, with when Git deletes it."""
+    if in_reflog:
+        reason = MissingReason.ReflogOnly
+        explanation = "only reachable through the reflog"
+        hint = "git gc will delete it once the reflog entry expires."
+    else:
+        reason = MissingReason.Dangling
+        explanation = "not reachable from any ref or the reflog"
+        hint = "git gc will delete it."
+    hint += " List the commit that replaced it, such as the amended or squashed one, instead."
+    return line_finding(
+        line_number, commit, f"{commit} ({explanation}) [{reason.value}]", (hint,), reason.value
+    )
+
+
+def placeholder_text(line: str, comment: Optional[str]) -> str:
+    """Return the text of a strict comment error, with why the comment above is no use, if any."""
+    if comment is None:
+        return line
+    if not comment:
+        return f"{line} (empty comment)"
+    return f"{line} (placeholder comment {comment!r})"
+
+
+def result_sections(
+    args: argparse.Namespace,
+    result: ValidationResult,
+    ticket_pattern: Optional[str],
+    trailer: Optional[str],
+    reachable_from: Sequence[str],
+    comment_template: Optional[str],
+    denylist: Optional[Denylist],
+    tracking: list[Finding],
+    ordering: list[Finding],
+    drift: list[Finding],
+) -> list[Section]:
+    """Return the findings of each check selected by `args`, in the order they are reported."""
+    valid_hashes = result["valid_hashes"]
+    sections = [
+        Section(
+            "Errors",
+            Severity.Error,
+            ErrorCode.SyntaxProblem.value,
+            [
+                line_finding(n, line, line, error_details(result, n))
+                for n, line in result["errors"].items()
+            ],
+            "No errors found!",
+        )
+    ]
+
+    if denylist is not None:
+        sections.append(
+            Section(
+                "Denied commits",
+                Severity.Error,
+                ErrorCode.FailedAdditionalChecks.value,
+                [
+                    line_finding(n, valid_hashes[n], f"{valid_hashes[n]} ({reason})")
+                    for n, reason in result["denied_entries"].items()
+                ],
+                "No denied commits are listed!",
+            )
+        )
+
+    if args.call_git:
+        all_present = not result["missing_commits"] and not result["non_commit_objects"]
+        sections.append(
+            Section(
+                "Missing commits",
+                Severity.Error,
+                ErrorCode.CommitsNotPresent.value,
+                [
+                    reason_finding(n, commit, result["missing_reasons"][n])
+                    for n, commit in result["missing_commits"].items()
+                ],
+                "All commits are present in the Git history!" if all_present else None,
+            )
+        )
+        sections.append(
+            Section(
+                "Objects that aren't commits",
+                Severity.Error,
+                ErrorCode.CommitsNotPresent.value,
+                [
+                    reason_finding(n, valid_hashes[n], result["missing_reasons"][n])
+                    for n in result["non_commit_objects"]
+                ],
+                None,
+            )
+        )
+        sections.append(
+            Section(
+                "Replaced commits",
+                Severity.Warning,
+                ErrorCode.FailedAdditionalChecks.value,
+                [
+                    line_finding(
+                        n, valid_hashes[n], f"{valid_hashes[n]} (replaced by {replacement})"
+                    )
+                    for n, replacement in result["replaced_commits"].items()
+                ],
+                None,
+            )
+        )
+
+    if args.check_tracked:
+        sections.append(
+            Section(
+                "Tracking problems",
+                Severity.Warning,
+                ErrorCode.FailedAdditionalChecks.value,
+                tracking,
+                "The file is committed and not ignored by Git!",
+            )
+        )
+
+    if args.append_only is not None:
+        sections.append(
+            Section(
+                "Ordering problems",
+                Severity.Warning,
+                ErrorCode.FailedAdditionalChecks.value,
+                ordering,
+                f"Entries were only appended since {args.append_only}!",
+            )
+        )
+
+    if args.compare_remote:
+        sections.append(
+            Section(
+                "Differences from the default branch",
+                Severity.Warning,
+                ErrorCode.FailedAdditionalChecks.value,
+                drift,
+                f"The entries match the default branch of {args.remote}!",
+            )
+        )
+
+    if args.strict_comments:
+        sections.append(
+            Section(
+                "Strict comment errors",
+                Severity.Warning,
+                ErrorCode.MissingComments.value,
+                [
+                    line_finding(
+                        n, line, placeholder_text(line, result["placeholder_comments"].get(n))
+                    )
+                    for n, line in result["strict_comment_errors"].items()
+                ],
+                "All commit lines have comments above them!",
+            )
+        )
+
+    if args.strict_comments_git:
+        comment_diffs: list[Finding] = []
+        for n, (comment, commit_message) in result["comment_diffs"].items():
+            expected = "Expected" if comment_template is not None else "Commit message"
+            details = (f"Comment: {comment}", f"{expected}: {commit_message}")
+            if n in result["comment_similarities"]:
+                details += (f"Similarity: {result['comment_similarities'][n]:.2f}",)
+            comment_diffs.append(line_finding(n, valid_hashes[n], details=details))
+        sections.append(
+            Section(
+                "Comment diffs",
+                Severity.Warning,
+                ErrorCode.MissingCommitMessageComments.value,
+                comment_diffs,
+                "All comments match the corresponding commit messages!",
+            )
+        )
+
+    if args.pre_commit_ci:
+        sections.append(
+            Section(
+                "Missing pre-commit-ci commits",
+                Severity.Warning,
+                ErrorCode.MissingPreCommitCICommits.value,
+                [
+                    Finding(f"Commit {commit_hash}", commit_hash, commit_message)
+                    for commit_hash, commit_message in result[
+                        "missing_pre_commit_ci_commits"
+                    ].items()
+                ],
+                "All pre-commit-ci commits are present in the file!",
+            )
+        )
+
+    if trailer is not None:
+        sections.append(
+            Section(
+                "Missing trailer commits",
+                Severity.Warning,
+                ErrorCode.FailedAdditionalChecks.value,
+                [
+                    Finding(f"Commit {commit_hash}", commit_hash, commit_message)
+                    for commit_hash, commit_message in result["missing_trailer_commits"].items()
+                ],
+                f"All commits carrying {trailer} are present in the file!",
+            )
+        )
+
+    if args.report_unused_entries:
+        sections.append(
+            Section(
+                "Unused entries",
+                Severity.Warning,
+                ErrorCode.FailedAdditionalChecks.value,
+                [line_finding(n, commit, commit) for n, commit in result["unused_entries"].items()],
+                "All entries still affect the blame output!",
+            )
+        )
+
+    if args.check_duplicate_patches:
+        sections.append(
+            Section(
+                "Duplicate patches",
+                Severity.Warning,
+                ErrorCode.FailedAdditionalChecks.value,
+                [
+                    line_finding(
+                        n,
+                        valid_hashes[n],
+                        f"{valid_hashes[n]} (same change as line {first}: {valid_hashes[first]})",
+                    )
+                    for n, first in result["duplicate_patches"].items()
+                ],
+                "No two entries make the same change!",
+            )
+        )
+
+    if args.check_default_branch:
+        sections.append(
+            Section(
+                "Commits not on the default branch",
+                Severity.Warning,
+                ErrorCode.FailedAdditionalChecks.value,
+                [
+                    unreachable_finding(
+                        n, valid_hashes[n], f"{valid_hashes[n]} ({problem})", [problem]
+                    )
+                    for n, problem in result["default_branch_errors"].items()
+                ],
+                "All commits are on the default branch!",
+            )
+        )
+
+    if reachable_from:
+        sections.append(
+            Section(
+                "Commits not reachable from required refs",
+                Severity.Warning,
+                ErrorCode.FailedAdditionalChecks.value,
+                [
+                    unreachable_finding(n, valid_hashes[n], valid_hashes[n], problems, problems)
+                    for n, problems in result["reachability_errors"].items()
+                ],
+                f"All commits are reachable from {', '.join(reachable_from)}!",
+            )
+        )
+
+    if args.check_unreferenced:
+        sections.append(
+            Section(
+                "Unreferenced commits",
+                Severity.Warning,
+                ErrorCode.FailedAdditionalChecks.value,
+                [
+                    unreferenced_finding(n, valid_hashes[n], in_reflog)
+                    for n, in_reflog in result["unreferenced_commits"].items()
+                ],
+                "Every commit is reachable from a ref!",
+            )
+        )
+
+    if args.check_semantic_changes:
+        sections.append(
+            Section(
+                "Likely semantic changes",
+                Severity.Warning,
+                ErrorCode.FailedAdditionalChecks.value,
+                [
+                    line_finding(n, valid_hashes[n], f"{valid_hashes[n]} (confidence {score:.0%})")
+                    for n, score in result["semantic_changes"].items()
+                ],
+                "No commits appear to contain semantic changes!",
+            )
+        )
+
+    if args.check_sections:
+        sections.append(
+            Section(
+                "Entries in the wrong section",
+                Severity.Warning,
+                ErrorCode.FailedAdditionalChecks.value,
+                [
+                    line_finding(n, valid_hashes[n], f"{valid_hashes[n]} ({problem})")
+                    for n, problem in result["section_errors"].items()
+                ],
+                "All entries are in the section for their formatter!",
+            )
+        )
+
+    if args.check_renames:
+        sections.append(
+            Section(
+                "Rename-heavy commits",
+                Severity.Warning,
+                ErrorCode.FailedAdditionalChecks.value,
+                [
+                    line_finding(
+                        n,
+                        valid_hashes[n],
+                        f"{valid_hashes[n]} ({renamed} of {changed} changed files renamed)",
+                    )
+                    for n, (renamed, changed) in result["rename_heavy_commits"].items()
+                ],
+                "No commits mostly rename files!",
+            )
+        )
+
+    if args.score_formatting:
+        sections.append(
+            Section(
+                "Unlikely formatting commits",
+                Severity.Warning,
+                ErrorCode.FailedAdditionalChecks.value,
+                [
+                    line_finding(
+                        n, valid_hashes[n], f"{valid_hashes[n]} (formatting likelihood {score:.0%})"
+                    )
+                    for n, score in result["formatting_scores"].items()
+                    if score < args.formatting_threshold
+                ],
+                "All commits look like formatting changes!",
+            )
+        )
+
+    if args.check_entry_dates:
+        sections.append(
+            Section(
+                "Entries added before their commits",
+                Severity.Warning,
+                ErrorCode.FailedAdditionalChecks.value,
+                [
+                    line_finding(
+                        n,
+                        valid_hashes[n],
+                        f"{valid_hashes[n]} (added in {commit}, which is older)",
+                        ("The listed commit was probably rewritten after it was listed.",),
+                    )
+                    for n, commit in result["entries_added_early"].items()
+                ],
+                "Every entry was added after its commit!",
+            )
+        )
+
+    if args.warn_older_than:
+        sections.append(
+            Section(
+                "Old entries",
+                Severity.Info,
+                0,
+                [
+                    line_finding(
+                        n,
+                        valid_hashes[n],
+                        f"{valid_hashes[n]} (committed on "
+                        f"{datetime.fromtimestamp(committed, timezone.utc).date().isoformat()})",
+                        (
+                            "Check whether ignoring it still makes a difference, "
+                            "such as with --report-unused-entries.",
+                        ),
+                    )
+                    for n, committed in result["old_entries"].items()
+                ],
+                f"No entries are older than {args.warn_older_than}!",
+            )
+        )
+
+    if args.check_metadata:
+        sections.append(
+            Section(
+                "Metadata errors",
+                Severity.Warning,
+                ErrorCode.FailedAdditionalChecks.value,
+                [
+                    line_finding(n, valid_hashes[n], f"{valid_hashes[n]} ({'; '.join(problems)})")
+                    for n, problems in result["metadata_errors"].items()
+                ],
+                "All comments have the required metadata!",
+            )
+        )
+
+    if args.require_pr_link:
+        sections.append(
+            Section(
+                "Pull request reference errors",
+                Severity.Warning,
+                ErrorCode.FailedAdditionalChecks.value,
+                [
+                    line_finding(n, valid_hashes[n], f"{valid_hashes[n]} ({problem})")
+                    for n, problem in result["pr_link_errors"].items()
+                ],
+                "All comments reference a pull request!",
+            )
+        )
+
+    if ticket_pattern is not None:
+        sections.append(
+            Section(
+                "Missing ticket references",
+                Severity.Warning,
+                ErrorCode.FailedAdditionalChecks.value,
+                [line_finding(n, commit, commit) for n, commit in result["ticket_errors"].items()],
+                f"All comments reference a ticket matching {ticket_pattern}!",
+            )
+        )
+
+    if args.check_commented_out:
+        sections.append(
+            Section(
+                "Commented-out entries",
+                Severity.Warning,
+                ErrorCode.FailedAdditionalChecks.value,
+                [
+                    line_finding(
+                        n,
+                        commit,
+                        commit,
+                        (
+                            f"To ignore the commit again, uncomment the line: {commit}",
+                            "Otherwise, delete the line.",
+                        ),
+                    )
+                    for n, commit in result["commented_out_hashes"].items()
+                ],
+                "No entries are commented out!",
+            )
+        )
+
+    if args.check_blank_lines:
+        sections.append(
+            Section(
+                "Blank line problems",
+                Severity.Warning,
+                ErrorCode.FailedAdditionalChecks.value,
+                [
+                    line_finding(n, "", problem)
+                    for n, problem in result["blank_line_errors"].items()
+                ],
+                "Blank lines separate every group of entries!",
+            )
+        )
+
+    if args.check_spelling:
+        sections.append(
+            Section(
+                "Possible misspellings",
+                Severity.Info,
+                0,
+                [
+                    line_finding(
+                        n,
+                        ", ".join(words),
+                        ", ".join(words),
+                        (
+                            "If a word is right, add it to words in the [spelling] section "
+                            "of the configuration file.",
+                        ),
+                    )
+                    for n, words in result["spelling_errors"].items()
+                ],
+                "No misspellings in the comments!",
+            )
+        )
+
+    return sections
+
+
+def fix_files(args: argparse.Namespace) -> tuple[list[str], int]:
+    """
+    Make the fixes that need no judgement to each file, or print their edits for --emit-edits.
+
+    Returns the lines of the human-readable report on what was fixed, and the exit code.
+    Files that can't be read are left for the checks to report.
+    """
+    cwd = os.getcwd()
+    report: list[str] = []
+    # The edits of every fix, for --emit-edits
+    edits: list[dict[str, object]] = []
+    for file_path in args.file_paths:
+        try:
+            content = Path(file_path).read_bytes()
+            # Written back in the same encoding, which for --encoding auto depends on the contents
+            encoding = content_encoding(content, args.encoding)
+            contents = content.decode(encoding)
+            fixes = find_fixes(file_path, contents, call_git=args.call_git)
+        except (OSError, UnicodeDecodeError):
+            continue
+        except GitNotFoundError as e:
+            print(e)
+            return report, ErrorCode.GitNotFound.value
+        finally:
+            # Looking up commits changes directory, which would break the next relative path
+            os.chdir(cwd)
+        if args.emit_edits is not None:
+            edits.extend(
+                {"path": display_path(file_path), "line": fix.line_number, "title": fix.title}
+                | edit.to_lsp()
+                for fix in fixes
+                for edit in fix.edits
+            )
+            continue
+        if not fixes:
+            continue
+        fixed = apply_edits(contents, [edit for fix in fixes for edit in fix.edits])
+        try:
+            # Replace the file only once the new contents are complete, in case of interruption,
+            # keeping the line breaks the file uses
+            write_output(
+                str(Path(file_path).resolve()),
+                fixed,
+                newline=line_ending(contents),
+                encoding=encoding,
+            )
+        except OSError as e:
+            print(f"Could not fix {display_path(file_path)}: {e}", file=sys.stderr)
+            return report, ErrorCode.FileNotFound.value
+        report.append(f"Fixed {display_path(file_path)}:")
+        report.extend(f"{fix.line_number}: {fix.title}" for fix in fixes)
+        report.append("")
+    if args.emit_edits is not None:
+        sys.stdout.write(json.dumps(edits, indent=2) + "\n")
+    return report, 0
+
+
+def default_branch_revision(directory: Path, remote: str, offline: bool) -> Optional[str]:
+    """
+    Return the commit the default branch of a remote points to, fetching it unless `offline`.
+
+    When it can't be fetched, the default branch as last fetched is used, if it is known.
+    """
+    if not offline:
+        try:
+            return fetch_remote_head(remote, directory)
+        except CalledProcessError:
+            pass
+    return remote_head(remote, directory)
+
+
+def drift_findings(remote_lines: list[str], local_lines: list[str], remote: str) -> list[Finding]:
+    """Return the entries only in the file on the default branch of a remote, or only locally."""
+    changes = compare_entries(remote_lines, local_lines)
+    return [
+        *(
+            line_finding(n, commit, f"{commit} (not on the default branch of {remote})")
+            for n, commit in changes["added"].items()
+        ),
+        *(
+            Finding(
+                f"Commit {commit}",
+                commit,
+                f"only on the default branch of {remote}, on line {n}",
+            )
+            for n, commit in changes["removed"].items()
+        ),
+    ]
+
+
+def check_file(
+    args: argparse.Namespace,
+    file_path: Path,
+    config: Config,
+    run_notes: list[str],
+    progress: Optional[Progress] = None,
+    source: Optional[ContentSource] = None,
+) -> tuple[list[str], FileReport]:
+    """Run the checks selected by `args` on one file, returning its report and its findings.
+
+    The file is read from `source` if it is given, and otherwise from where `args` selects.
+    """
+    retval = 0
+    if source is None:
+        source = content_source(args)
+    report_path = file_path.resolve() if args.report_path == "resolved" else file_path
+    # The checks that use Git change directory, so later reads mustn't depend on it
+    file_path = Path(os.path.abspath(file_path))
+    # The lines of the human-readable report on this file
+    report: list[str] = []
+    # The findings of each check, for the machine-readable formats
+    sections: list[Section] = []
+    # Messages about the file as a whole, which are also part of the human-readable report
+    notes = list(run_notes)
+    translate = translator(args.lang)
+
+    def note(message: str, **fields: object) -> str:
+        """Add a message to the notes in English, returning it in the language of the report."""
+        notes.append(message.format(**fields))
+        return translate(message).format(**fields)
+
+    ticket_pattern = args.ticket_pattern or config["ticket_pattern"]
+    # Finding the commits that carry the trailer needs Git
+    trailer = (args.trailer or config["trailer"]) if args.call_git else None
+    reachable_from = (args.reachable_from or config["reachable_from"]) if args.call_git else []
+    comment_match = MatchMode(args.comment_match) if args.comment_match else config["comment_match"]
+    similarity_threshold = config["similarity_threshold"]
+    if args.similarity_threshold is not None:
+        similarity_threshold = args.similarity_threshold
+
+    try:
+        validate = (
+            cached_validate_git_blame_ignore_revs if args.cache else validate_git_blame_ignore_revs
+        )
+        options = ValidationOptions(
+            call_git=args.call_git,
+            strict_comments=args.strict_comments,
+            strict_comments_git=args.strict_comments_git,
+            pre_commit_ci=args.pre_commit_ci,
+            trailer=trailer,
+            denylist=config["denylist"],
+            spelling_dictionary=args.spelling_dictionary if args.check_spelling else None,
+            spelling_words=config["spelling_words"],
+            report_unused_entries=args.report_unused_entries,
+            check_semantic_changes=args.check_semantic_changes,
+            semantic_threshold=args.semantic_threshold,
+            metadata_policy=config["metadata"] if args.check_metadata else None,
+            require_pr_link=args.require_pr_link,
+            verify_pr_links=args.verify_pr_links,
+            forge_cache=(
+                ResponseCache(cache_dir() / "forge", args.forge_cache_ttl)
+                if args.forge_cache_ttl
+                else None
+            ),
+            ticket_pattern=ticket_pattern,
+            comment_match=comment_match,
+            similarity_threshold=similarity_threshold,
+            comment_template=config["comment_template"],
+            normalization=args.normalize or config["normalization"],
+            casefold=args.casefold or config["casefold"],
+            history_range=history_range(args),
+            check_duplicate_patches=args.check_duplicate_patches,
+            check_commented_out=args.check_commented_out,
+            check_blank_lines=args.check_blank_lines,
+            remote=args.remote,
+            check_default_branch=args.check_default_branch,
+            reachable_from=reachable_from,
+            check_unreferenced=args.check_unreferenced,
+            check_sections=args.check_sections,
+            check_renames=args.check_renames,
+            score_formatting=args.score_formatting,
+            check_entry_dates=args.check_entry_dates,
+            # A duration rather than a time, so the results can be cached
+            older_than=(duration_seconds(args.warn_older_than) if args.warn_older_than else None),
+            encoding=args.encoding,
+            # Stopping early in either version would hide findings that are new
+            fail_fast=args.fail_fast and args.diff_base is None,
+        )
+        # The same checks are run on the base version for --diff-base
+        validate_file = partial(validate, file_path, options, progress=progress)
+        result = validate_file(source=source)
+
+        repository = None if args.no_git else forge_repository(file_path, args.remote)
+        report.append(translate("Validation Results:"))
+        valid_count = len(result["valid_hashes"])
+        report.append(translate("Valid hashes ({count}):").format(count=valid_count))
+        for line_number, hash in result["valid_hashes"].items():
+            if args.hyperlinks and repository is not None:
+                hash = hyperlink(hash, commit_url(repository, hash))
+            report.append(f"  {translate('Line {line}').format(line=line_number)}: {hash}")
+
+        expected_name = args.expected_name or config["file_name"]
+        if file_path.name != expected_name:
+            message = note(
+                "Warning: {path} is not named {name}, "
+                "so tools that look for the file by name won't find it.",
+                path=display_path(report_path),
+                name=expected_name,
+            )
+            report.append(f"\n{message}")
+
+        empty_file = args.empty_file or config["empty_file"]
+        if not result["valid_hashes"] and not result["errors"] and empty_file != "allow":
+            has_comments = bool(source.read_text(file_path, args.encoding).strip())
+            if has_comments:
+                message = "{path} has only comments, so it ignores no commits."
+            else:
+                message = "{path} has no entries, so it ignores no commits."
+            if empty_file == "error":
+                message = "Empty file: " + message
+                retval |= ErrorCode.FailedAdditionalChecks.value
+            else:
+                message = "Warning: " + message
+            report.append(f"\n{note(message, path=display_path(report_path))}")
+
+        tracking: list[Finding] = []
+        if args.check_tracked:
+            path = display_path(report_path)
+            tracking = [Finding(path, "", problem) for problem in tracking_problems(file_path)]
+        ordering: list[Finding] = []
+        if args.append_only is not None:
+            try:
+                old_lines = (
+                    GitRevision(args.append_only).read_text(file_path, args.encoding).splitlines()
+                )
+            except RevisionNotFoundError:
+                raise
+            except FileNotFoundError:
+                # The file is new, so every entry in it is appended
+                old_lines = []
+            new_lines = source.read_text(file_path, args.encoding).splitlines()
+            ordering = [
+                line_finding(n, commit, f"{commit} ({problem})")
+                for n, (commit, problem) in ordering_problems(old_lines, new_lines).items()
+            ]
+        drift: list[Finding] = []
+        if args.compare_remote:
+            remote_rev = default_branch_revision(file_path.parent, args.remote, args.offline)
+            if remote_rev is None:
+                message = note(
+                    "Warning: {path} could not be compared with the "
+                    "default branch of {remote}, which isn't known.",
+                    path=display_path(report_path),
+                    remote=args.remote,
+                )
+                report.append(f"\n{message}")
+            else:
+                try:
+                    remote_lines = (
+                        GitRevision(remote_rev).read_text(file_path, args.encoding).splitlines()
+                    )
+                except FileNotFoundError:
+                    # Every entry is missing from the default branch
+                    remote_lines = []
+                local_lines = source.read_text(file_path, args.encoding).splitlines()
+                drift = drift_findings(remote_lines, local_lines, args.remote)
+        sections = result_sections(
+            args,
+            result,
+            ticket_pattern,
+            trailer,
+            reachable_from,
+            config["comment_template"],
+            config["denylist"],
+            tracking,
+            ordering,
+            drift,
+        )
+        if repository is not None:
+            sections = link_commits(sections, repository)
+        existing = 0
+        if args.diff_base is not None:
+            try:
+                base_result = validate_file(source=GitRevision(args.diff_base))
+            except RevisionNotFoundError:
+                raise
+            except FileNotFoundError:
+                # The file is new, so every problem in it is too
+                base_sections = []
+            else:
+                # Tracking and ordering problems are about the file as it is now
+                base_sections = result_sections(
+                    args,
+                    base_result,
+                    ticket_pattern,
+                    trailer,
+                    reachable_from,
+                    config["comment_template"],
+                    config["denylist"],
+                    [],
+                    [],
+                    [],
+                )
+            sections, existing = new_findings(sections, base_sections)
+
+        stopped = False
+        if args.fail_fast:
+            for index, section in enumerate(sections):
+                if section.severity is Severity.Error and section.findings:
+                    # The checks after the first error weren't run, so don't report them as passing
+                    del sections[index + 1 :]
+                    stopped = True
+                    break
+
+        report.extend(
+            render_report(
+                sections,
+                args.group_by,
+                args.sort,
+                args.max_errors,
+                args.hyperlinks,
+                translate,
+            )
+        )
+        retval |= exit_code(sections)
+        if existing:
+            message = note(
+                "Left out {count} problem(s) that are also in {base} because of --diff-base.",
+                count=existing,
+                base=args.diff_base,
+            )
+            report.append(f"\n{message}")
+        if stopped:
+            report.append(f"\n{note('Stopped at the first error because of --fail-fast.')}")
+        if args.bitbucket_insights:
+            try:
+                notes.append(
+                    publish_bitbucket_insights(
+                        file_path,
+                        repository,
+                        FileReport(display_path(report_path), sections, notes, retval),
+                    )
+                )
+                report.append(f"\n{notes[-1]}")
+            except (OSError, CalledProcessError) as e:
+                message = note(
+                    "Could not publish the Code Insights report to Bitbucket: {error}", error=e
+                )
+                report.append(f"\n{message}")
+                retval |= ErrorCode.FileNotFound.value
+    except RevisionNotFoundError as e:
+        # About the revision rather than the file, such as one given to --diff-base
+        notes.append(f"{e.strerror}, so {display_path(report_path)} can't be read from it.")
+        report.append(notes[-1])
+        retval += ErrorCode.FileNotFound.value
+    except FileNotFoundError as e:
+        notes.append(str(FileNotFoundError(e.errno, e.strerror, str(report_path))))
+        report.append(notes[-1])
+        retval += ErrorCode.FileNotFound.value
+    except UnicodeDecodeError as e:
+        message = note(
+            "{path} is not valid UTF-8: {error}. "
+            "Pass --encoding auto for a file with Latin-1 comments.",
+            path=display_path(report_path),
+            error=e,
+        )
+        report.append(message)
+        retval += ErrorCode.SyntaxProblem.value
+    except GitNotFoundError as e:
+        notes.append(str(e))
+        report.append(notes[-1])
+        retval |= ErrorCode.GitNotFound.value
+
+    return report, FileReport(display_path(report_path), sections, notes, retval)
+
+
+def _check_file_from(
+    directory: str, args: argparse.Namespace, file_path: Path, config: Config, run_notes: list[str]
+) -> tuple[list[str], FileReport]:
+    # Worker processes are reused, so start each file from the directory relative paths are in
+    os.chdir(directory)
+    # Processes that are spawned rather than forked don't inherit the policy
+    set_default_policy(retry_policy(args))
+    return check_file(args, file_path, config, run_notes)
+
+
+def check_files(
+    args: argparse.Namespace, configs: list[Config], run_notes: list[str]
+) -> Generator[tuple[list[str], FileReport], None, None]:
+    """
+    Run the checks selected by `args` on each file, yielding the results in the order of the files.
+
+    With `--jobs`, several files are checked at once in separate processes,
+    since the checks change the working directory. Files that haven't been
+    started yet are skipped if the caller stops early, such as for `--fail-fast`.
+    When files are checked one at a time, slow checks show a progress bar on
+    standard error if it is a terminal.
+    """
+    cwd = os.getcwd()
+    tasks = list(zip(args.file_paths, configs))
+    jobs = min(args.jobs or os.cpu_count() or 1, len(tasks))
+    if jobs <= 1:
+        # Processes checking files at once would draw over each other's progress bars
+        bar = progress_bar(sys.stderr)
+        for file_path, config in tasks:
+            try:
+                yield check_file(args, file_path, config, run_notes, bar)
+            finally:
+                if bar is not None:
+                    bar.clear()
+                # The checks that use Git change directory, which would break the next relative path
+                os.chdir(cwd)
+        return
+
+    with ProcessPoolExecutor(max_workers=jobs) as executor:
+        futures = [
+            executor.submit(_check_file_from, cwd, args, file_path, config, run_notes)
+            for file_path, config in tasks
+        ]
+        try:
+            for future in futures:
+                yield future.result()
+        finally:
+            for future in futures:
+                future.cancel()
+
+
+def build_parser() -> argparse.ArgumentParser:
+    """Return the parser of the arguments for validating files, shared with `serve`."""
+    parser = argparse.ArgumentParser(
+        description="Validate a .git-blame-ignore-revs file.",
+        epilog=f"Additional subcommands: {', '.join(SUBCOMMANDS)}. "
+        "Run `validate-git-blame-ignore-revs <subcommand> --help` for details.",
+    )
+    _ = parser.add_argument(
+        "file_paths",
+        metavar="file_path",
+        type=Path,
+        nargs="*",
+        help="Path to the .git-blame-ignore-revs file. Give several to validate them in one run.",
+    )
+    _ = parser.add_argument(
+        "--workspace",
+        metavar="DIR",
+        type=Path,
+        help="Also validate the .git-blame-ignore-revs files of every Git repository under this directory: those named by blame.ignoreRevsFile and the one at the top level.",
+    )
+    _ = parser.add_argument(
+        "--profile",
+        choices=list(PROFILES),
+        help="Run a preset combination of checks, each profile adding to the one before. Checks given as options are run as well, and other options and the configuration file override the profile.",
+    )
     _ = parser.add_argument(
         "--call-git",
         action="store_true",
         help="Ensure each commit is in the history of the checked-out branch.",
     )
+    _ = parser.add_argument(
+        "--no-replace-objects",
+        action="store_true",
+        help="Ignore replacements made with `git replace` when looking up commits, like `git --no-replace-objects`. Otherwise, listed commits that have been replaced are reported.",
+    )
     _ = parser.add_argument(
         "--strict-comments",
         action="store_true",
-        help="Require each commit line to have one or more comment lines above it.",
+        help="Require each commit line to have one or more comment lines above it, at least one of which isn't empty or a placeholder such as TODO, fix, or formatting.",
+    )
+    _ = parser.add_argument(
+        "--check-commented-out",
+        action="store_true",
+        help="Report comments that start with a commit hash, which are usually entries that were disabled and forgotten, with how to fix them.",
+    )
+    _ = parser.add_argument(
+        "--check-blank-lines",
+        action="store_true",
+        help="Report blank lines that break the style of exactly one blank line between groups of entries, where a group is a block of comments and the entries below it, and none between a comment and its entry or at the start or end of the file.",
+    )
+    _ = parser.add_argument(
+        "--check-spelling",
+        action="store_true",
+        help="Report words in comments that are in neither the dictionary nor the words of the project in the configuration file, as info that doesn't fail the run, since comments end up in documentation exported from the file.",
+    )
+    _ = parser.add_argument(
+        "--spelling-dictionary",
+        metavar="PATH",
+        type=Path,
+        help=f"The word list, with one word on each line, for --check-spelling (default: {' or '.join(SYSTEM_DICTIONARIES)}).",
     )
     _ = parser.add_argument(
         "--strict-comments-git",
@@ -38,82 +2228,625 @@ def main() -> None:
         action="store_true",
         help="Ensure all commits authored by pre-commit-ci[bot] are present in the file. Requires --call-git.",
     )
+    _ = parser.add_argument(
+        "--trailer",
+        metavar="TRAILER",
+        help="Ensure all commits whose message carries this trailer, such as 'Blame-Ignore: true', or only its key, are present in the file. Overrides trailer in the configuration file. Requires --call-git.",
+    )
+    add_history_range_arguments(parser, "--pre-commit-ci and --trailer")
+    _ = parser.add_argument(
+        "--report-unused-entries",
+        action="store_true",
+        help="Report commits that no line at HEAD is attributed to anymore, so they can be pruned. Requires --call-git.",
+    )
+    _ = parser.add_argument(
+        "--check-tracked",
+        action="store_true",
+        help="Ensure the file is committed to the repository and not matched by .gitignore, since otherwise it has no effect for anyone else. Requires --call-git.",
+    )
+    _ = parser.add_argument(
+        "--check-duplicate-patches",
+        action="store_true",
+        help="Report commits that make the same change as a commit listed earlier, such as a backport listed under a different hash. Requires --call-git.",
+    )
+    _ = parser.add_argument(
+        "--check-default-branch",
+        action="store_true",
+        help="Ensure each commit is on the default branch of the --remote, catching commits from a feature branch that was squashed when it was merged. Fetch first so the remote-tracking branch is up to date. Requires --call-git.",
+    )
+    _ = parser.add_argument(
+        "--reachable-from",
+        metavar="REF",
+        action="append",
+        default=[],
+        help="Ensure each commit is reachable from this branch or tag, or from every ref matching this glob pattern, such as 'release/*', reporting each ref it isn't reachable from. Can be repeated. Overrides reachable_from in the configuration file. Requires --call-git.",
+    )
+    _ = parser.add_argument(
+        "--check-unreferenced",
+        action="store_true",
+        help="Report commits that no branch, tag, or other ref reaches, such as the original of an amended or squashed commit, telling those only the reflog still reaches from dangling ones. git gc deletes them, so the entries would stop working. Requires --call-git.",
+    )
+    _ = parser.add_argument(
+        "--check-semantic-changes",
+        action="store_true",
+        help="Report commits whose diffs add or change identifiers or literals rather than only reformatting code. Requires --call-git.",
+    )
+    _ = parser.add_argument(
+        "--semantic-threshold",
+        type=float,
+        metavar="SCORE",
+        default=0.0,
+        help="Only report commits whose confidence of containing semantic changes is above this value (default: %(default)s).",
+    )
+    _ = parser.add_argument(
+        "--check-sections",
+        action="store_true",
+        help="Report entries under a section header for one formatter, such as '## black' or '# --- prettier ---', whose commit looks like the work of another formatter, going by its subject or the files it changes. Requires --call-git.",
+    )
+    _ = parser.add_argument(
+        "--check-renames",
+        action="store_true",
+        help="Report commits where most changed files were renamed. Blame already follows files across renames, so ignoring such a commit usually achieves nothing. Requires --call-git.",
+    )
+    _ = parser.add_argument(
+        "--score-formatting",
+        action="store_true",
+        help="Score how likely each commit is to only reformat code, from its whitespace-only changes, the number of files it touches, keywords in its subject, and whether a bot authored it, and report commits scoring below --formatting-threshold. Requires --call-git.",
+    )
+    _ = parser.add_argument(
+        "--formatting-threshold",
+        type=float,
+        metavar="SCORE",
+        default=0.5,
+        help="Report commits whose formatting likelihood from --score-formatting is below this value from 0 to 1 (default: %(default)s).",
+    )
+    _ = parser.add_argument(
+        "--check-entry-dates",
+        action="store_true",
+        help="Report entries that were added to the file in a commit older than the commit they list, a sign that the listed commit was rewritten by a force push afterwards. Requires --call-git.",
+    )
+    _ = parser.add_argument(
+        "--warn-older-than",
+        metavar="DURATION",
+        help="Report entries whose commits were committed longer ago than this, such as 90d, 6mo, or 2y, as info that doesn't fail the run, so that entries that may no longer make a difference are reviewed. Requires --call-git.",
+    )
+    _ = parser.add_argument(
+        "--check-metadata",
+        action="store_true",
+        help="Ensure the `Key: value` pairs in the comments above each commit satisfy the [metadata] section of the configuration file.",
+    )
+    _ = parser.add_argument(
+        "--require-pr-link",
+        action="store_true",
+        help="Require the comments above each commit to link to a pull request or reference one, such as #123.",
+    )
+    _ = parser.add_argument(
+        "--verify-pr-links",
+        action="store_true",
+        help="Ensure each referenced pull request exists on GitHub or GitLab and contains the commit, with a token from the environment or the configuration of gh or glab if there is one. Requires --require-pr-link and --call-git.",
+    )
+    _ = parser.add_argument(
+        "--ticket-pattern",
+        metavar="REGEX",
+        help="Require the comments above each commit to contain a match for this regular expression, such as JIRA-[0-9]+. Overrides ticket_pattern in the configuration file.",
+    )
+    _ = parser.add_argument(
+        "--comment-match",
+        choices=[mode.value for mode in MatchMode],
+        help="How --strict-comments-git compares each comment with the commit subject: the whole subject, its start, anywhere in it, as a regular expression, its start ignoring case, or resembling it (see --similarity-threshold). Overrides match in the configuration file, and a `# match: <mode>` comment overrides both for one entry (default: prefix).",
+    )
+    _ = parser.add_argument(
+        "--similarity-threshold",
+        type=float,
+        metavar="SCORE",
+        help="The score from 0 to 1 at or above which a comment resembles the commit subject in the similar mode. Overrides similarity_threshold in the configuration file (default: 0.8).",
+    )
+    _ = parser.add_argument(
+        "--normalize",
+        choices=["NFC", "NFKC"],
+        help="Apply this Unicode normalization form to comments and commit subjects before --strict-comments-git compares them. NFKC also replaces typographic quotes and dashes. Overrides normalize in the configuration file.",
+    )
+    _ = parser.add_argument(
+        "--casefold",
+        action="store_true",
+        help="Ignore case when --strict-comments-git compares comments and commit subjects.",
+    )
+    _ = parser.add_argument(
+        "--empty-file",
+        choices=["allow", "warn", "error"],
+        help="Whether a file without any entries, such as one with only comments, passes, passes with a warning, or fails. Overrides empty in the [file] section of the configuration file, which overrides the --profile (default: warn, or error with every profile but minimal).",
+    )
+    _ = parser.add_argument(
+        "--expected-name",
+        metavar="NAME",
+        help="Warn if the file isn't named NAME, such as after a typo. Overrides name in the [file] section of the configuration file (default: .git-blame-ignore-revs).",
+    )
+    _ = parser.add_argument(
+        "--config",
+        type=Path,
+        metavar="PATH",
+        help=f"Path to the configuration file. Defaults to {CONFIG_FILE_NAME} next to the .git-blame-ignore-revs file, if it exists.",
+    )
+    _ = parser.add_argument(
+        "--cache",
+        action="store_true",
+        help="Reuse the previous result when the file, HEAD, the refs that checks such as --reachable-from depend on, and the options are unchanged. The pull requests of --verify-pr-links are always checked again.",
+    )
+    _ = parser.add_argument(
+        "--forge-cache-ttl",
+        metavar="SECONDS",
+        type=int,
+        default=86400,
+        help="Reuse the responses of forge APIs, such as for --verify-pr-links, for this long, so that repeated runs don't use up the rate limit. Pull requests that weren't found are looked up again after 10 minutes at most. Use 0 to always make the requests (default: %(default)s).",
+    )
+    _ = parser.add_argument(
+        "--jobs",
+        metavar="N",
+        type=int,
+        default=1,
+        help="Check up to N files at once when several are given, or as many as there are CPUs for 0 (default: %(default)s). The report is the same as when they are checked one by one.",
+    )
+    _ = parser.add_argument(
+        "--fail-fast",
+        action="store_true",
+        help="Stop at the first invalid line, missing commit, or object that isn't a commit, skipping the rest of the checks and files, such as in a hook where a quick answer matters more than a complete report.",
+    )
+    _ = parser.add_argument(
+        "--at-rev",
+        metavar="TREE-ISH",
+        help="Validate the file as it is in this commit, tag, or tree rather than in the working tree, such as to audit a release branch without checking it out.",
+    )
+    _ = parser.add_argument(
+        "--staged",
+        action="store_true",
+        help="Validate the file as it is staged in the index rather than in the working tree, which is what will be committed, such as in a pre-commit hook.",
+    )
+    _ = parser.add_argument(
+        "--encoding",
+        choices=ENCODINGS,
+        default=ENCODINGS[0],
+        help="How to decode the file: utf-8, latin1 for legacy files with Latin-1 comments, or auto to use latin1 only if the file isn't valid UTF-8 (default: %(default)s).",
+    )
+    _ = parser.add_argument(
+        "--diff-base",
+        metavar="REV",
+        help="Only report problems that the file as it is in this revision doesn't already have, such as to gate merges on a repository whose file has known problems.",
+    )
+    _ = parser.add_argument(
+        "--append-only",
+        metavar="REV",
+        help="Ensure entries were only appended since this revision, such as the base branch of a pull request: existing entries keep their order and comments, and new entries come after the existing entries of their section, a block of lines separated by blank lines.",
+    )
+    _ = parser.add_argument(
+        "--compare-remote",
+        action="store_true",
+        help="Fetch the file on the default branch of the --remote and report the entries that are only in one of it and the local file, such as in a stale local copy. Without the network, such as with --offline, the default branch as last fetched is used.",
+    )
+    _ = parser.add_argument(
+        "--remote",
+        metavar="NAME",
+        default="origin",
+        help="The remote whose default branch --check-default-branch uses, and whose repository on a forge is used to verify pull requests and link commits, such as upstream when working in a fork (default: %(default)s).",
+    )
+    _ = parser.add_argument(
+        "--no-git",
+        action="store_true",
+        help="Skip the checks that require Git, such as when it isn't installed, and run the rest.",
+    )
+    _ = parser.add_argument(
+        "--offline",
+        action="store_true",
+        help="Guarantee that nothing is fetched over or sent to the network, such as in an air-gapped CI environment. Checks and reports that need the network, such as --verify-pr-links and --bitbucket-insights, are refused, and Git doesn't fetch objects missing from a partial clone.",
+    )
+    _ = parser.add_argument(
+        "--retries",
+        metavar="N",
+        type=int,
+        default=3,
+        help="Run Git commands and make forge API requests that fail for what is likely a passing problem, such as a network error, a lock held by another Git process, or a network file system that couldn't be read, up to this many more times. The failure is reported with how often it was tried if every attempt fails (default: %(default)s).",
+    )
+    _ = parser.add_argument(
+        "--retry-delay",
+        metavar="SECONDS",
+        type=float,
+        default=1.0,
+        help="How long to wait before the first retry. Each retry after that waits twice as long as the one before, up to 30 seconds (default: %(default)s).",
+    )
+    _ = parser.add_argument(
+        "--trace-git",
+        action="store_true",
+        help="Log each Git command to standard error with its exit status, how long it took, and the start of its output, such as to find out why a commit is reported missing in one environment but not another.",
+    )
+    _ = parser.add_argument(
+        "--fix",
+        action="store_true",
+        help="Before checking, fix the problems that need no judgement in place: lowercase hashes with uppercase letters and remove other invalid lines that aren't almost a hash, and with --call-git, expand abbreviated hashes and replace near misses that name a single commit, and add a comment from the commit subject above entries without one.",
+    )
+    _ = parser.add_argument(
+        "--emit-edits",
+        choices=EDIT_FORMATS,
+        help="With --fix, print the edits that make the fixes instead of changing the file and checking it: a JSON list of Language Server Protocol text edits, each with the path, line, and title of its fix, for editor plugins and other tools to apply.",
+    )
+    _ = parser.add_argument(
+        "--group-by",
+        choices=GROUP_BY,
+        default=GROUP_BY[0],
+        help="Group the problems found by check, by the line they are about, or by severity (default: %(default)s).",
+    )
+    _ = parser.add_argument(
+        "--sort",
+        choices=SORT_BY,
+        default=SORT_BY[0],
+        help="Order the problems in each group by line, by severity (errors first), or by hash (default: %(default)s).",
+    )
+    _ = parser.add_argument(
+        "--max-errors",
+        metavar="N",
+        type=int,
+        help="Show at most N problems for each file and only count the rest, so a badly broken file doesn't flood the log. The exit code still reflects every problem, and the reports from --report are complete.",
+    )
+    _ = parser.add_argument(
+        "--hyperlinks",
+        choices=("auto", "always", "never"),
+        default="auto",
+        help="Make commit hashes in the human-readable report links to the commit on the forge the --remote points to, for terminals that support them. With auto, only when the report is only printed to a terminal (default: %(default)s).",
+    )
+    _ = parser.add_argument(
+        "--lang",
+        metavar="LANG",
+        help=f"The language of the titles and headings of the human-readable report, such as de. Machine-readable formats stay in English. Defaults to the language of the environment, from LANGUAGE, LC_ALL, LC_MESSAGES, or LANG, and to English for languages other than {', '.join(LANGUAGES)}.",
+    )
+    _ = parser.add_argument(
+        "--report-path",
+        choices=["given", "resolved"],
+        default="given",
+        help="Refer to the file by the path as given or with symlinks resolved (default: %(default)s).",
+    )
+    _ = parser.add_argument(
+        "--output",
+        metavar="PATH",
+        help="Write the report to this file instead of standard output, creating its directory if needed. The file is replaced only once the report is complete. Use - for standard output.",
+    )
+    _ = parser.add_argument(
+        "--format",
+        choices=FORMATS,
+        default="human",
+        help="Print the report in this format instead of the human-readable one. compact prints a line `path:line:col: severity[check-id]: message` for each problem, which editors and CI log parsers pick up, and azure prints logging commands that Azure Pipelines shows as issues of the build (default: %(default)s).",
+    )
+    _ = parser.add_argument(
+        "--porcelain",
+        nargs="?",
+        const=f"v{PORCELAIN_VERSION}",
+        choices=[f"v{PORCELAIN_VERSION}"],
+        help="Print the report in a line-oriented format for scripts, which is stable across releases, instead of the human-readable report. Give the version of the format to make sure a script gets the one it parses (default: %(const)s).",
+    )
+    _ = parser.add_argument(
+        "--bitbucket-insights",
+        action="store_true",
+        help="Also publish the problems as a Code Insights report with annotations on the commit checked out, or BITBUCKET_COMMIT in Bitbucket Pipelines, for repositories on Bitbucket Cloud that the --remote points to. Authenticates with BITBUCKET_TOKEN if set, and otherwise through the proxy of Bitbucket Pipelines.",
+    )
+    _ = parser.add_argument(
+        "--github-summary",
+        action="store_true",
+        help="When GITHUB_STEP_SUMMARY is set, as it is in GitHub Actions, also append a Markdown summary of the problems to the summary of the job, with links to the commits.",
+    )
+    _ = parser.add_argument(
+        "--report",
+        metavar="FORMAT=PATH",
+        type=report_sink,
+        action="append",
+        default=[],
+        help=f"Also write the report in this format ({', '.join(FORMATS)}) to this file, or to standard output for -. Can be repeated to produce several reports from one run. The human-readable report is only printed when --output is given or there is no --report.",
+    )
 
-    args = parser.parse_args()
+    return parser
 
-    retval = 0
 
+def parse_arguments(parser: argparse.ArgumentParser, argv: list[str]) -> argparse.Namespace:
+    """Parse `argv`, with the options of the --profile as defaults that explicit ones override."""
+    args = parser.parse_args(argv)
+    if args.profile is not None:
+        parser.set_defaults(**PROFILES[args.profile].options)
+        args = parser.parse_args(argv)
+    return args
+
+
+def check_arguments(parser: argparse.ArgumentParser, args: argparse.Namespace) -> None:
+    """Exit with an error if the options of the checks are out of range or can't be combined."""
     if args.strict_comments_git and not (args.strict_comments and args.call_git):
         parser.error("--strict-comments-git requires --strict-comments and --call-git.")
     if args.pre_commit_ci and not args.call_git:
         parser.error("--pre-commit-ci requires --call-git.")
+    if args.trailer and not args.call_git:
+        parser.error("--trailer requires --call-git.")
+    if args.report_unused_entries and not args.call_git:
+        parser.error("--report-unused-entries requires --call-git.")
+    if args.check_tracked and not args.call_git:
+        parser.error("--check-tracked requires --call-git.")
+    if args.check_duplicate_patches and not args.call_git:
+        parser.error("--check-duplicate-patches requires --call-git.")
+    if args.check_default_branch and not args.call_git:
+        parser.error("--check-default-branch requires --call-git.")
+    if args.reachable_from and not args.call_git:
+        parser.error("--reachable-from requires --call-git.")
+    if args.check_unreferenced and not args.call_git:
+        parser.error("--check-unreferenced requires --call-git.")
+    if args.check_semantic_changes and not args.call_git:
+        parser.error("--check-semantic-changes requires --call-git.")
+    if args.check_sections and not args.call_git:
+        parser.error("--check-sections requires --call-git.")
+    if args.check_renames and not args.call_git:
+        parser.error("--check-renames requires --call-git.")
+    if args.score_formatting and not args.call_git:
+        parser.error("--score-formatting requires --call-git.")
+    if args.check_entry_dates and not args.call_git:
+        parser.error("--check-entry-dates requires --call-git.")
+    if args.warn_older_than is not None:
+        if not args.call_git:
+            parser.error("--warn-older-than requires --call-git.")
+        try:
+            duration_seconds(args.warn_older_than)
+        except ValueError as e:
+            parser.error(f"Invalid --warn-older-than: {e}")
+    if args.verify_pr_links and not (args.require_pr_link and args.call_git):
+        parser.error("--verify-pr-links requires --require-pr-link and --call-git.")
+    if args.offline and args.verify_pr_links:
+        parser.error("--verify-pr-links needs the network, so it can't be used with --offline.")
+    if args.offline and args.bitbucket_insights:
+        parser.error("--bitbucket-insights needs the network, so it can't be used with --offline.")
+    if args.spelling_dictionary is not None and not args.check_spelling:
+        parser.error("--spelling-dictionary requires --check-spelling.")
+    if args.check_spelling:
+        args.spelling_dictionary = args.spelling_dictionary or find_dictionary()
+        if args.spelling_dictionary is None:
+            parser.error("--check-spelling needs a word list: give one with --spelling-dictionary.")
+        try:
+            load_dictionary(args.spelling_dictionary)
+        except OSError as e:
+            parser.error(f"Could not read the dictionary for --check-spelling: {e}")
+    if args.emit_edits is not None and not args.fix:
+        parser.error("--emit-edits requires --fix.")
+    if args.forge_cache_ttl < 0:
+        parser.error("--forge-cache-ttl must not be negative.")
+    if args.retries < 0 or args.retry_delay < 0:
+        parser.error("--retries and --retry-delay must not be negative.")
+    if args.jobs < 0:
+        parser.error("--jobs must not be negative.")
+    if args.max_errors is not None and args.max_errors < 0:
+        parser.error("--max-errors must not be negative.")
+    if args.similarity_threshold is not None and not 0 <= args.similarity_threshold <= 1:
+        parser.error("--similarity-threshold must be from 0 to 1.")
+    if not 0 <= args.formatting_threshold <= 1:
+        parser.error("--formatting-threshold must be from 0 to 1.")
+    if args.trailer and not is_valid_trailer(args.trailer):
+        parser.error(f"Invalid --trailer {args.trailer!r}: expected Key or Key: value.")
+    if args.ticket_pattern:
+        try:
+            re.compile(args.ticket_pattern)
+        except re.error as e:
+            parser.error(f"Invalid regular expression for --ticket-pattern: {e}")
 
-    try:
-        result = validate_git_blame_ignore_revs(
-            args.file_path,
-            args.call_git,
-            args.strict_comments,
-            args.strict_comments_git,
-            args.pre_commit_ci,
+
+def skip_git_checks(args: argparse.Namespace) -> Optional[str]:
+    """Turn off the checks that require Git for --no-git, returning a note naming them if any."""
+    if not args.no_git:
+        return None
+    git_checks = [
+        "call_git",
+        "strict_comments_git",
+        "pre_commit_ci",
+        "trailer",
+        "report_unused_entries",
+        "check_tracked",
+        "check_duplicate_patches",
+        "check_default_branch",
+        "reachable_from",
+        "check_unreferenced",
+        "check_semantic_changes",
+        "check_sections",
+        "check_renames",
+        "score_formatting",
+        "check_entry_dates",
+        "warn_older_than",
+        "verify_pr_links",
+        "bitbucket_insights",
+    ]
+    skipped = [check for check in git_checks if getattr(args, check)]
+    if not skipped:
+        return None
+    for check in skipped:
+        setattr(args, check, False)
+    flags = ", ".join(f"--{check.replace('_', '-')}" for check in skipped)
+    return f"Skipping checks that require Git: {flags}"
+
+
+def file_config(
+    parser: argparse.ArgumentParser, args: argparse.Namespace, file_path: Path
+) -> Config:
+    """Return the configuration a file is validated with: --config, or the file next to it."""
+    config = default_config() if args.profile is None else profile_config(args.profile)
+    config_path = args.config or find_config(file_path)
+    if config_path is not None:
+        try:
+            config = load_config(config_path, config)
+        except (OSError, ValueError, configparser.Error) as e:
+            parser.error(f"Could not read {display_path(config_path)}: {e}")
+    if args.check_metadata and config["metadata"] is None:
+        parser.error("--check-metadata requires a [metadata] section in the configuration file.")
+    return config
+
+
+def retry_policy(args: argparse.Namespace) -> RetryPolicy:
+    """Return the retry policy of `--retries` and `--retry-delay`."""
+    return RetryPolicy(retries=args.retries, initial_delay=args.retry_delay)
+
+
+def configure_git_environment(args: argparse.Namespace) -> None:
+    """Apply the options that change how Git looks up objects and retries to every later command."""
+    set_default_policy(retry_policy(args))
+    if args.no_replace_objects:
+        os.environ["GIT_NO_REPLACE_OBJECTS"] = "1"
+    if args.offline:
+        # Otherwise, looking up a commit missing from a partial clone fetches it from the
+        # promisor remote
+        os.environ["GIT_NO_LAZY_FETCH"] = "1"
+
+
+def main(argv: Optional[list[str]] = None) -> None:
+    if argv is None:
+        argv = sys.argv[1:]
+    # Before anything changes the working directory that relative paths in them are resolved in
+    pin_repository_environment()
+    if argv and argv[0] in SUBCOMMANDS:
+        try:
+            sys.exit(SUBCOMMANDS[argv[0]](argv[1:]))
+        except GitNotFoundError as e:
+            print(e)
+            sys.exit(ErrorCode.GitNotFound.value)
+
+    parser = build_parser()
+    args = parse_arguments(parser, argv)
+
+    # The lines of the human-readable report, written out once every file has been checked
+    report: list[str] = []
+    # Messages about the run as a whole, which are also part of the report for each file
+    notes: list[str] = []
+
+    sinks: list[tuple[str, str]] = args.report
+    if args.porcelain:
+        if args.format != "human":
+            parser.error("--porcelain and --format can't be used together.")
+        args.format = "porcelain"
+    if args.output is not None or not sinks or args.format != "human":
+        sinks = [(args.format, args.output or "-"), *sinks]
+    destinations = [destination for _, destination in sinks]
+    for destination in set(destinations):
+        if destinations.count(destination) > 1:
+            name = "standard output" if destination == "-" else destination
+            parser.error(f"More than one report would be written to {name}.")
+    # Escape sequences would clutter a report that is saved to a file or read by another program
+    human = [destination for report_format, destination in sinks if report_format == "human"]
+    args.hyperlinks = args.hyperlinks == "always" or (
+        args.hyperlinks == "auto" and human == ["-"] and sys.stdout.isatty()
+    )
+
+    check_arguments(parser, args)
+
+    if args.workspace is not None:
+        if not args.workspace.is_dir():
+            parser.error(f"--workspace {display_path(args.workspace)} is not a directory.")
+        try:
+            with git_tracing(args):
+                found = workspace_ignore_files(args.workspace)
+        except GitNotFoundError as e:
+            print(e)
+            sys.exit(ErrorCode.GitNotFound.value)
+        if not found and not args.file_paths:
+            print(
+                "No .git-blame-ignore-revs files found in the repositories under "
+                f"{display_path(args.workspace)}."
+            )
+            sys.exit(ErrorCode.FileNotFound.value)
+        args.file_paths += found
+    if not args.file_paths:
+        parser.error("Give the path to a .git-blame-ignore-revs file, or --workspace.")
+
+    # Each file is validated with the configuration file next to it
+    configs = [file_config(parser, args, file_path) for file_path in args.file_paths]
+    if args.staged and args.at_rev is not None:
+        parser.error("--staged and --at-rev can't be used together.")
+    if args.no_git and args.at_rev is not None:
+        parser.error("--at-rev reads the file with Git, so it can't be used with --no-git.")
+    if args.no_git and args.staged:
+        parser.error("--staged reads the file with Git, so it can't be used with --no-git.")
+    if args.no_git and args.diff_base is not None:
+        parser.error("--diff-base reads the file with Git, so it can't be used with --no-git.")
+    if args.no_git and args.append_only is not None:
+        parser.error("--append-only reads the file with Git, so it can't be used with --no-git.")
+    if args.no_git and args.compare_remote:
+        parser.error("--compare-remote reads the file with Git, so it can't be used with --no-git.")
+    if args.fix and (args.staged or args.at_rev is not None):
+        parser.error(
+            "--fix changes the file in the working tree, so it can't be used with --staged or "
+            "--at-rev."
         )
+    configure_git_environment(args)
 
-        print("Validation Results:")
-        print(f"Valid hashes ({len(result['valid_hashes'])}):")
-        for line_number, hash in result["valid_hashes"].items():
-            print(f"  Line {line_number}: {hash}")
+    skipped = skip_git_checks(args)
+    if skipped is not None:
+        notes.append(skipped)
+        report.append(f"{notes[-1]}\n")
 
-        if result["errors"]:
-            print(f"\nErrors ({len(result['errors'])}):")
-            for line_number, line in result["errors"].items():
-                print(f"  Line {line_number}: {line}")
-            retval += ErrorCode.SyntaxProblem.value
+    if args.fix:
+        try:
+            with interruptible(), git_tracing(args):
+                fixed, retval = fix_files(args)
+        except Interrupted as e:
+            # The files fixed so far were replaced whole, and the rest are left as they were
+            print(f"{e} while fixing the files.")
+            sys.exit(e.exit_code)
+        if args.emit_edits is not None or retval:
+            sys.exit(retval)
+        report.extend(fixed)
+
+    files: list[FileReport] = []
+    interrupted: Optional[Interrupted] = None
+    try:
+        with interruptible(), git_tracing(args), closing(
+            check_files(args, configs, notes)
+        ) as results:
+            for lines, file_report in results:
+                if len(args.file_paths) > 1:
+                    # Head the report on each file with its path, like `head` does
+                    if files:
+                        report.append("")
+                    report.append(f"==> {file_report.path} <==")
+                report.extend(lines)
+                files.append(file_report)
+                if args.fail_fast and file_report.exit_code & ERROR_CODES:
+                    break
+    except Interrupted as e:
+        # Still write the report on the files that were checked before the signal
+        interrupted = e
+
+    retval = 0
+    for file_report in files:
+        retval |= file_report.exit_code
+    if len(args.file_paths) > 1:
+        failed = sum(bool(file_report.exit_code) for file_report in files)
+        # --fail-fast or a signal stops before the remaining files
+        checked = (
+            f"{len(files)} files"
+            if len(files) == len(args.file_paths)
+            else f"{len(files)} of {len(args.file_paths)} files"
+        )
+        report.append(f"\nChecked {checked}, {failed} with problems.")
+    if interrupted is not None:
+        report.append(f"\n{interrupted}, so the report is incomplete.")
+        # Like a shell, report the signal rather than the problems found before it
+        retval = interrupted.exit_code
+
+    for report_format, destination in sinks:
+        if report_format == "human":
+            text = "".join(f"{line}\n" for line in report)
         else:
-            print("\nNo errors found!")
-
-        if args.call_git:
-            if result["missing_commits"]:
-                print(f"\nMissing commits ({len(result['missing_commits'])}):")
-                for line_number, commit in result["missing_commits"].items():
-                    print(f"  Line {line_number}: {commit}")
-                retval += ErrorCode.CommitsNotPresent.value
-            else:
-                print("\nAll commits are present in the Git history!")
-
-        if args.strict_comments:
-            if result["strict_comment_errors"]:
-                print(f"\nStrict comment errors ({len(result['strict_comment_errors'])}):")
-                for line_number, line in result["strict_comment_errors"].items():
-                    print(f"  Line {line_number}: {line}")
-                retval += ErrorCode.MissingComments.value
-            else:
-                print("\nAll commit lines have comments above them!")
-
-        if args.strict_comments_git:
-            if result["comment_diffs"]:
-                print(f"\nComment diffs ({len(result['comment_diffs'])}):")
-                for line_number, (comment, commit_message) in result["comment_diffs"].items():
-                    print(f"  Line {line_number}:")
-                    print(f"    Comment: {comment}")
-                    print(f"    Commit message: {commit_message}")
-                retval += ErrorCode.MissingCommitMessageComments.value
-            else:
-                print("\nAll comments match the corresponding commit messages!")
+            text = RENDERERS[report_format](files)
+        try:
+            write_output(destination, text)
+        except OSError as e:
+            print(f"Could not write the report to {destination}: {e}", file=sys.stderr)
+            retval |= ErrorCode.FileNotFound.value
 
-        if args.pre_commit_ci:
-            if result["missing_pre_commit_ci_commits"]:
-                print(
-                    f"\nMissing pre-commit-ci commits ({len(result['missing_pre_commit_ci_commits'])}):"
-                )
-                for commit_hash, commit_message in result["missing_pre_commit_ci_commits"].items():
-                    print(f"  Commit {commit_hash}: {commit_message}")
-                retval += ErrorCode.MissingPreCommitCICommits.value
-            else:
-                print("\nAll pre-commit-ci commits are present in the file!")
-    except FileNotFoundError as e:
-        print(e)
-        retval += ErrorCode.FileNotFound.value
+    summary = os.environ.get("GITHUB_STEP_SUMMARY")
+    if args.github_summary and summary:
+        try:
+            # Separated from whatever earlier steps wrote, which may not end with a blank line
+            append_output(summary, "\n" + render_markdown(files))
+        except OSError as e:
+            print(f"Could not write the job summary to {summary}: {e}", file=sys.stderr)
+            retval |= ErrorCode.FileNotFound.value
 
-    exit(retval)
+    sys.exit(retval)
 
 
 if __name__ == "__main__":
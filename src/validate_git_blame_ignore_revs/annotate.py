@@ -0,0 +1,109 @@
+from pathlib import Path
+from subprocess import CalledProcessError
+from typing import Optional, TypedDict, Union
+
+from validate_git_blame_ignore_revs.backends import GitBackend, SubprocessGit
+from validate_git_blame_ignore_revs.git import CommitInfo
+from validate_git_blame_ignore_revs.lib import LineKind, parse_lines
+from validate_git_blame_ignore_revs.metadata import parse_metadata
+from validate_git_blame_ignore_revs.sources import line_ending
+
+__all__ = (
+    "Annotation",
+    "annotate_entries",
+    "annotated_text",
+)
+
+
+class Annotation(TypedDict):
+    commit: Optional[CommitInfo]  # None if the hash doesn't name a commit in the repository
+    shortstat: str  # Such as "1 file changed, 2 insertions(+)", or "" for missing commits
+
+
+def annotate_entries(
+    file_path: Union[str, Path], git: Optional[GitBackend] = None
+) -> dict[int, Annotation]:
+    """
+    Looks up the commit listed on each line of a `.git-blame-ignore-revs` file.
+
+    Args:
+        file_path (Union[str, Path]): Path to the `.git-blame-ignore-revs` file.
+        git (Optional[GitBackend]): How the commits are looked up. Defaults to running `git`.
+
+    Returns:
+        dict[int, Annotation]: A dictionary mapping the line number of each entry to the commit's metadata and diffstat.
+    """
+    file_path = Path(file_path).resolve()
+    with file_path.open(encoding="utf-8") as lines:
+        hashes = {
+            parsed_line.line_number: parsed_line.text
+            for parsed_line in parse_lines(lines)
+            if parsed_line.kind is LineKind.Hash
+        }
+
+    if git is None:
+        git = SubprocessGit()
+    git.enter(file_path.parent)
+
+    annotations: dict[int, Annotation] = {}
+    for line_number, commit_hash in hashes.items():
+        try:
+            if git.object_type(commit_hash) == "commit":
+                annotations[line_number] = Annotation(
+                    commit=git.commit_info(commit_hash), shortstat=git.shortstat(commit_hash)
+                )
+                continue
+        except CalledProcessError:
+            pass
+        annotations[line_number] = Annotation(commit=None, shortstat="")
+    return annotations
+
+
+def annotated_text(file_path: Union[str, Path], annotations: dict[int, Annotation]) -> str:
+    """
+    Returns the contents of a `.git-blame-ignore-revs` file with the annotations added as comments.
+
+    Each entry gets `Author: `, `Date: `, and `Stat: ` comments above the
+    comments directly above it, so the comment describing the commit stays
+    last. Entries that already have an `Author: ` comment are left alone, so
+    annotating a file twice changes nothing. The comments end with the line
+    break the file uses, and the file's own line breaks are kept.
+
+    Args:
+        file_path (Union[str, Path]): Path to the `.git-blame-ignore-revs` file.
+        annotations (dict[int, Annotation]): The result of `annotate_entries` for the file.
+
+    Returns:
+        str: The annotated contents.
+    """
+    with Path(file_path).open(encoding="utf-8", newline="") as f:
+        text = f.read()
+    lines = text.splitlines(keepends=True)
+    newline = line_ending(text)
+
+    # Index of the first of the comment lines directly above each entry, or of
+    # the entry itself -> Line number of the entry
+    insert_at: dict[int, int] = {}
+    block_start: Optional[int] = None
+    for parsed_line in parse_lines(lines):
+        index = parsed_line.line_number - 1
+        if parsed_line.kind is LineKind.Comment:
+            if block_start is None:
+                block_start = index
+        elif parsed_line.kind is LineKind.Hash:
+            if "Author" not in parse_metadata(parsed_line.comments):
+                insert_at[index if block_start is None else block_start] = parsed_line.line_number
+            block_start = None
+        else:
+            block_start = None
+
+    output: list[str] = []
+    for index, line in enumerate(lines):
+        annotation = annotations.get(insert_at[index]) if index in insert_at else None
+        if annotation is not None and annotation["commit"] is not None:
+            commit = annotation["commit"]
+            output.append(f"# Author: {commit['author_name']} <{commit['author_email']}>{newline}")
+            output.append(f"# Date: {commit['author_date']}{newline}")
+            output.append(f"# Stat: {annotation['shortstat']}{newline}")
+        output.append(line)
+    return "".join(output)
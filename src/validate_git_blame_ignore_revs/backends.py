@@ -0,0 +1,726 @@
+import fnmatch
+import hashlib
+import os
+import re
+from abc import ABC, abstractmethod
+from collections.abc import Sequence
+from datetime import datetime, timezone
+from pathlib import Path
+from subprocess import CalledProcessError
+from typing import NamedTuple, Optional, Union
+
+from validate_git_blame_ignore_revs import git
+from validate_git_blame_ignore_revs.git import CommitInfo, HistoryRange
+from validate_git_blame_ignore_revs.paths import strip_long_path_prefix
+
+__all__ = (
+    "FakeCommit",
+    "FakeGit",
+    "GitBackend",
+    "SubprocessGit",
+)
+
+
+class GitBackend(ABC):
+    """How the checks look things up in the repository that contains the file being validated.
+
+    Methods that look up an object raise `CalledProcessError` if there is no such
+    object, the way a failing Git command does. Methods that take a `repository`
+    look things up in the repository that contains it instead, without entering it.
+    """
+
+    @abstractmethod
+    def enter(self, directory: Path) -> None:
+        """Look up everything in the repository that contains `directory` from now on."""
+
+    @abstractmethod
+    def toplevel(self) -> str:
+        """Return the top level of the repository."""
+
+    @abstractmethod
+    def object_type(self, rev: str) -> str:
+        """Return the type of the object `rev` names: commit, tag, tree, or blob."""
+
+    @abstractmethod
+    def describe_object(self, rev: str) -> str:
+        """Return the type of the object `rev` names, followed by the subject of commits or the name of tags."""
+
+    @abstractmethod
+    def commit_subject(self, commit_hash: str) -> Optional[str]:
+        """Return the subject of a commit, or None if Git shows nothing for it."""
+
+    @abstractmethod
+    def commit_author(self, commit_hash: str) -> str:
+        """Return the author of a commit as `Name <email>`."""
+
+    @abstractmethod
+    def commit_time(self, commit_hash: str) -> int:
+        """Return when a commit was committed, in seconds since the epoch."""
+
+    @abstractmethod
+    def commit_info(self, commit_hash: str) -> CommitInfo:
+        """Return the metadata of a commit, such as for writing a comment describing it."""
+
+    @abstractmethod
+    def resolve_commit(self, rev: str) -> str:
+        """Return the full hash of the commit a revision, such as an abbreviated hash, names."""
+
+    @abstractmethod
+    def head_commit(self, repository: Union[str, Path]) -> str:
+        """Return the hash of the commit checked out in `repository`."""
+
+    @abstractmethod
+    def ref_targets(self, repository: Union[str, Path]) -> dict[str, str]:
+        """Return the object each ref of `repository` points to, or the ref for symbolic refs."""
+
+    @abstractmethod
+    def config_values(self, key: str, repository: Union[str, Path]) -> list[str]:
+        """Return every value of a configuration variable of `repository`, or [] if it isn't set."""
+
+    @abstractmethod
+    def object_contents(self, name: str, repository: Union[str, Path]) -> bytes:
+        """Return the contents of an object of `repository`, such as `HEAD:./file` or `:./file`."""
+
+    @abstractmethod
+    def file_history(
+        self, file_path: Path, max_count: Optional[int] = None
+    ) -> list[tuple[str, str, str, str]]:
+        """Return the commits that changed a file in the history of HEAD, newest first.
+
+        Each is given as its hash, its author as `Name <email>`, the date it was
+        authored as YYYY-MM-DD, and its subject.
+        """
+
+    @abstractmethod
+    def disambiguate(self, prefix: str) -> list[str]:
+        """Return the full hashes of every object whose hash starts with `prefix`."""
+
+    @abstractmethod
+    def replaced_objects(self) -> dict[str, str]:
+        """Return the replacement for each object replaced with `git replace`."""
+
+    @abstractmethod
+    def log_subjects(
+        self, author: Optional[str] = None, history_range: HistoryRange = HistoryRange()
+    ) -> dict[str, str]:
+        """Return the subject of each commit in the history of HEAD, newest first.
+
+        Only commits whose author matches the regular expression `author` are
+        returned, if it is given. `history_range.max_count` limits the commits
+        looked at before they are filtered by author.
+        """
+
+    @abstractmethod
+    def trailer_commits(
+        self, trailer: str, history_range: HistoryRange = HistoryRange()
+    ) -> dict[str, str]:
+        """Return the subject of each commit in the history of HEAD that carries a trailer.
+
+        `trailer` is a key, or a key and value such as `Blame-Ignore: true`.
+        Commits are returned newest first.
+        """
+
+    @abstractmethod
+    def log_messages(
+        self, history_range: HistoryRange = HistoryRange()
+    ) -> dict[str, tuple[str, str]]:
+        """Return the subject and body of each non-merge commit in the history of HEAD.
+
+        Commits are returned newest first.
+        """
+
+    @abstractmethod
+    def log_changed_lines(
+        self, ignore_whitespace: bool = False, history_range: HistoryRange = HistoryRange()
+    ) -> dict[str, tuple[int, str]]:
+        """Return how many lines each non-merge commit in the history of HEAD changes.
+
+        Each count is given with the subject of the commit, newest first. With
+        `ignore_whitespace`, lines that only change whitespace aren't counted.
+        """
+
+    @abstractmethod
+    def find_reverts(self, commit_hash: str) -> list[str]:
+        """Return the commits in the history of HEAD whose message says they revert a commit."""
+
+    @abstractmethod
+    def first_parent_diff(self, commit_hash: str) -> str:
+        """Return the changed lines of a commit against its first parent, without context lines."""
+
+    @abstractmethod
+    def diffstat(self, commit_hash: str) -> str:
+        """Return the diffstat of a commit against its first parent."""
+
+    @abstractmethod
+    def shortstat(self, commit_hash: str) -> str:
+        """Return the summary line of the diffstat of a commit, such as "1 file changed"."""
+
+    @abstractmethod
+    def patch_id(self, commit_hash: str) -> Optional[str]:
+        """Return an ID shared by commits making the same change, or None if it changes nothing."""
+
+    @abstractmethod
+    def changed_paths(self, commit_hash: str) -> list[str]:
+        """Return the paths a commit touched that still exist at HEAD, relative to the top level."""
+
+    @abstractmethod
+    def commit_paths(self, commit_hash: str) -> list[str]:
+        """Return every path a commit touched, including those deleted since."""
+
+    @abstractmethod
+    def rename_counts(self, commit_hash: str) -> tuple[int, int]:
+        """Return how many files a commit renamed and how many it changed in all."""
+
+    @abstractmethod
+    def whitespace_ratio(self, commit_hash: str) -> float:
+        """Return the fraction of changed lines in a commit that only change whitespace."""
+
+    @abstractmethod
+    def blame_attribution(
+        self,
+        path: str,
+        ignore_revs: Sequence[str] = (),
+        ignore_revs_file: Optional[Union[str, Path]] = None,
+    ) -> list[str]:
+        """Return the commit each line of `path` at HEAD is attributed to.
+
+        Only the given revisions, and those listed in `ignore_revs_file`, are
+        ignored, not those of the Git configuration.
+        """
+
+    @abstractmethod
+    def blame_contents(self, path: str, contents: str) -> list[str]:
+        """Return the commit each line of `contents`, a version of `path`, is attributed to.
+
+        Lines that aren't in HEAD are attributed to the null hash of forty zeros.
+        """
+
+    @abstractmethod
+    def remote_url(self, remote: str) -> str:
+        """Return the URL of a remote."""
+
+    @abstractmethod
+    def remote_head(self, remote: str) -> Optional[str]:
+        """Return the default branch of a remote, such as `origin/main`, or None if it isn't known."""
+
+    @abstractmethod
+    def is_ancestor(self, commit_hash: str, rev: str) -> bool:
+        """Return whether a commit is reachable from `rev`."""
+
+    @abstractmethod
+    def unreferenced_commits(self, commit_hashes: list[str]) -> dict[str, bool]:
+        """Return the commits no ref reaches, each with whether the reflog does."""
+
+    @abstractmethod
+    def is_shallow(self) -> bool:
+        """Return whether the repository is a shallow clone."""
+
+    @abstractmethod
+    def matching_refs(self, pattern: str) -> list[str]:
+        """Return the short names of the branches and tags matching a glob pattern."""
+
+    @abstractmethod
+    def is_tracked(self, path: str) -> bool:
+        """Return whether `path` is in the index."""
+
+    @abstractmethod
+    def is_committed(self, path: str) -> bool:
+        """Return whether `path` is in the commit checked out at HEAD."""
+
+    @abstractmethod
+    def ignore_rule(self, path: str) -> Optional[str]:
+        """Return the `.gitignore` rule matching `path` as `source:line:pattern`, if any."""
+
+    def __repr__(self) -> str:
+        # Also identifies the backend in the key of cached results
+        return f"{type(self).__name__}()"
+
+
+class SubprocessGit(GitBackend):
+    """Runs the `git` executable in the working directory, which `enter` changes."""
+
+    def enter(self, directory: Path) -> None:
+        git.pin_repository_environment()
+        # Git would run in the directory with the long-path prefix, which it doesn't handle
+        os.chdir(strip_long_path_prefix(directory))
+
+    def toplevel(self) -> str:
+        return git.toplevel()
+
+    def object_type(self, rev: str) -> str:
+        return git.object_type(rev)
+
+    def describe_object(self, rev: str) -> str:
+        return git.describe_object(rev)
+
+    def commit_subject(self, commit_hash: str) -> Optional[str]:
+        output = git.run_command(["git", "show", "--quiet", "--pretty=format:%H %s", commit_hash])
+        if not output:
+            return None
+        return output.partition(" ")[2]
+
+    def commit_author(self, commit_hash: str) -> str:
+        return git.run_command(["git", "show", "--quiet", "--pretty=format:%an <%ae>", commit_hash])
+
+    def commit_time(self, commit_hash: str) -> int:
+        return git.commit_time(commit_hash)
+
+    def commit_info(self, commit_hash: str) -> CommitInfo:
+        return git.commit_info(commit_hash)
+
+    def resolve_commit(self, rev: str) -> str:
+        return git.resolve_commit(rev)
+
+    def head_commit(self, repository: Union[str, Path]) -> str:
+        return git.head_commit(repository)
+
+    def ref_targets(self, repository: Union[str, Path]) -> dict[str, str]:
+        return git.ref_targets(repository)
+
+    def config_values(self, key: str, repository: Union[str, Path]) -> list[str]:
+        return git.config_values(key, repository)
+
+    def object_contents(self, name: str, repository: Union[str, Path]) -> bytes:
+        return git.object_contents(name, repository)
+
+    def file_history(
+        self, file_path: Path, max_count: Optional[int] = None
+    ) -> list[tuple[str, str, str, str]]:
+        return git.file_history(file_path, max_count)
+
+    def disambiguate(self, prefix: str) -> list[str]:
+        return git.disambiguate(prefix)
+
+    def replaced_objects(self) -> dict[str, str]:
+        return git.replaced_objects()
+
+    def log_subjects(
+        self, author: Optional[str] = None, history_range: HistoryRange = HistoryRange()
+    ) -> dict[str, str]:
+        if author is None:
+            return git.log_subjects(*history_range.log_args())
+        subjects = git.log_subjects(f"--author={author}", *history_range.log_args())
+        if history_range.max_count is not None:
+            # `--max-count` counts the commits left after filtering by author
+            recent = git.log_subjects(f"--max-count={history_range.max_count}")
+            subjects = {
+                commit_hash: subject
+                for commit_hash, subject in subjects.items()
+                if commit_hash in recent
+            }
+        return subjects
+
+    def trailer_commits(
+        self, trailer: str, history_range: HistoryRange = HistoryRange()
+    ) -> dict[str, str]:
+        return git.trailer_commits(trailer, *history_range.log_args())
+
+    def log_messages(
+        self, history_range: HistoryRange = HistoryRange()
+    ) -> dict[str, tuple[str, str]]:
+        return git.log_messages(*history_range.log_args())
+
+    def log_changed_lines(
+        self, ignore_whitespace: bool = False, history_range: HistoryRange = HistoryRange()
+    ) -> dict[str, tuple[int, str]]:
+        extra_args = ["--ignore-all-space"] if ignore_whitespace else []
+        return git.log_changed_lines(*extra_args, *history_range.log_args())
+
+    def find_reverts(self, commit_hash: str) -> list[str]:
+        return git.find_reverts(commit_hash)
+
+    def first_parent_diff(self, commit_hash: str) -> str:
+        return git.first_parent_diff(commit_hash)
+
+    def diffstat(self, commit_hash: str) -> str:
+        return git.diffstat(commit_hash)
+
+    def shortstat(self, commit_hash: str) -> str:
+        return git.shortstat(commit_hash)
+
+    def patch_id(self, commit_hash: str) -> Optional[str]:
+        return git.patch_id(commit_hash)
+
+    def changed_paths(self, commit_hash: str) -> list[str]:
+        return git.changed_paths(commit_hash)
+
+    def commit_paths(self, commit_hash: str) -> list[str]:
+        return git.commit_paths(commit_hash)
+
+    def rename_counts(self, commit_hash: str) -> tuple[int, int]:
+        return git.rename_counts(commit_hash)
+
+    def whitespace_ratio(self, commit_hash: str) -> float:
+        return git.whitespace_ratio(commit_hash)
+
+    def blame_attribution(
+        self,
+        path: str,
+        ignore_revs: Sequence[str] = (),
+        ignore_revs_file: Optional[Union[str, Path]] = None,
+    ) -> list[str]:
+        return git.blame_attribution(path, ignore_revs, ignore_revs_file)
+
+    def blame_contents(self, path: str, contents: str) -> list[str]:
+        return git.blame_contents(path, contents)
+
+    def remote_url(self, remote: str) -> str:
+        return git.remote_url(remote)
+
+    def remote_head(self, remote: str) -> Optional[str]:
+        return git.remote_head(remote)
+
+    def is_ancestor(self, commit_hash: str, rev: str) -> bool:
+        return git.is_ancestor(commit_hash, rev)
+
+    def unreferenced_commits(self, commit_hashes: list[str]) -> dict[str, bool]:
+        return git.unreferenced_commits(commit_hashes)
+
+    def is_shallow(self) -> bool:
+        return git.is_shallow()
+
+    def matching_refs(self, pattern: str) -> list[str]:
+        return git.matching_refs(pattern)
+
+    def is_tracked(self, path: str) -> bool:
+        return git.is_tracked(path)
+
+    def is_committed(self, path: str) -> bool:
+        return git.is_committed(path)
+
+    def ignore_rule(self, path: str) -> Optional[str]:
+        return git.ignore_rule(path)
+
+
+class FakeCommit(NamedTuple):
+    subject: str = ""
+    author: str = "A U Thor <author@example.com>"
+    diff: str = ""  # Changed lines against the first parent, as `first_parent_diff` shows them
+    paths: tuple[str, ...] = ()  # Changed paths that still exist at HEAD
+    renames: int = 0  # How many of the changed files were renamed
+    whitespace_ratio: float = 0.0  # The fraction of changed lines that only change whitespace
+    time: int = 0  # When it was committed, in seconds since the epoch
+    trailers: tuple[str, ...] = ()  # Trailers of the message, such as "Blame-Ignore: true"
+    body: str = ""  # The message after the subject, such as "This reverts commit ..."
+
+
+# A full hash, as listed in an ignore file
+_hash_regex = re.compile(r"^[0-9a-f]{40}$")
+
+
+def _not_found(*args: str) -> CalledProcessError:
+    return CalledProcessError(128, ["git", *args])
+
+
+class FakeGit(GitBackend):
+    """A repository held in memory, for testing checks without creating a real one.
+
+    Commits are keyed by hash and make up the history of HEAD in the order they
+    are given, newest first. Other objects, such as tags and trees, are keyed by
+    hash with their description, such as "tag v1.0" or "tree". `branches` maps
+    a revision, such as `origin/main`, to the commits reachable from it.
+    `unreferenced` maps the commits no ref reaches, such as the originals of
+    amended commits, to whether the reflog does. Commits with the same diff
+    have the same patch ID. `files` maps object names, such as `HEAD:./file`
+    or `:./file` for the index, to their contents. Blaming while ignoring a
+    commit attributes its lines to the commit before it in history.
+    """
+
+    def __init__(
+        self,
+        commits: Optional[dict[str, FakeCommit]] = None,
+        *,
+        objects: Optional[dict[str, str]] = None,
+        replacements: Optional[dict[str, str]] = None,
+        branches: Optional[dict[str, set[str]]] = None,
+        remotes: Optional[dict[str, str]] = None,
+        remote_heads: Optional[dict[str, str]] = None,
+        blame: Optional[dict[str, list[str]]] = None,
+        committed_files: Optional[set[str]] = None,
+        staged_files: Optional[set[str]] = None,
+        ignore_rules: Optional[dict[str, str]] = None,
+        unreferenced: Optional[dict[str, bool]] = None,
+        config: Optional[dict[str, list[str]]] = None,
+        files: Optional[dict[str, bytes]] = None,
+        shallow: bool = False,
+    ) -> None:
+        self.commits = commits or {}
+        self.objects = objects or {}
+        self.replacements = replacements or {}
+        self.branches = branches or {}
+        self.remotes = remotes or {}  # Remote name -> URL
+        self.remote_heads = remote_heads or {}  # Remote name -> Default branch
+        self.blame = blame or {}  # Path -> Commit each line is attributed to
+        self.committed_files = committed_files or set()
+        self.staged_files = staged_files or set()  # Files in the index but not in HEAD
+        self.ignore_rules = ignore_rules or {}  # Path -> Matching rule
+        self.unreferenced = unreferenced or {}  # Commit -> Whether the reflog reaches it
+        self.config = config or {}  # Variable -> Values
+        self.files = files or {}  # Object name -> Contents
+        self.shallow = shallow  # Whether the history stops short, as in a shallow clone
+        self.directory = Path(".")
+
+    def enter(self, directory: Path) -> None:
+        self.directory = directory
+
+    def toplevel(self) -> str:
+        return str(self.directory)
+
+    def object_type(self, rev: str) -> str:
+        if rev in self.commits:
+            return "commit"
+        if rev in self.objects:
+            return self.objects[rev].split(" ", 1)[0]
+        raise _not_found("cat-file", "-t", rev)
+
+    def describe_object(self, rev: str) -> str:
+        if rev in self.commits:
+            return f"commit {self.commits[rev].subject}"
+        if rev in self.objects:
+            return self.objects[rev]
+        raise _not_found("cat-file", "-t", rev)
+
+    def commit_subject(self, commit_hash: str) -> Optional[str]:
+        if commit_hash not in self.commits:
+            raise _not_found("show", commit_hash)
+        return self.commits[commit_hash].subject
+
+    def commit_author(self, commit_hash: str) -> str:
+        if commit_hash not in self.commits:
+            raise _not_found("show", commit_hash)
+        return self.commits[commit_hash].author
+
+    def commit_time(self, commit_hash: str) -> int:
+        if commit_hash not in self.commits:
+            raise _not_found("show", commit_hash)
+        return self.commits[commit_hash].time
+
+    def commit_info(self, commit_hash: str) -> CommitInfo:
+        if commit_hash not in self.commits:
+            raise _not_found("show", commit_hash)
+        commit = self.commits[commit_hash]
+        # The history is newest first, so each commit's parent is the one after it
+        history = list(self.commits)
+        parents = history[history.index(commit_hash) + 1 :][:1]
+        name, _, email = commit.author.partition(" <")
+        # Authored when it was committed, in UTC
+        date = datetime.fromtimestamp(commit.time, timezone.utc).isoformat()
+        return CommitInfo(
+            hash=commit_hash,
+            author_name=name,
+            author_email=email.rstrip(">"),
+            author_date=date,
+            parents=parents,
+            subject=commit.subject,
+        )
+
+    def resolve_commit(self, rev: str) -> str:
+        if rev == "HEAD" and self.commits:
+            return next(iter(self.commits))
+        candidates = [commit_hash for commit_hash in self.commits if commit_hash.startswith(rev)]
+        if len(rev) < 4 or len(candidates) != 1:
+            raise _not_found("rev-parse", "--verify", f"{rev}^{{commit}}")
+        return candidates[0]
+
+    def head_commit(self, repository: Union[str, Path]) -> str:
+        return self.resolve_commit("HEAD")
+
+    def ref_targets(self, repository: Union[str, Path]) -> dict[str, str]:
+        # Fake refs have no tip, so each is identified by the commits reachable from it
+        return {name: " ".join(sorted(commits)) for name, commits in self.branches.items()}
+
+    def config_values(self, key: str, repository: Union[str, Path]) -> list[str]:
+        return list(self.config.get(key, []))
+
+    def object_contents(self, name: str, repository: Union[str, Path]) -> bytes:
+        if name in self.files:
+            return self.files[name]
+        if name.endswith("^{tree}") and name[: -len("^{tree}")] in ("HEAD", *self.commits):
+            return b""
+        raise _not_found("show", name)
+
+    def file_history(
+        self, file_path: Path, max_count: Optional[int] = None
+    ) -> list[tuple[str, str, str, str]]:
+        history = [
+            (
+                commit_hash,
+                commit.author,
+                datetime.fromtimestamp(commit.time, timezone.utc).date().isoformat(),
+                commit.subject,
+            )
+            for commit_hash, commit in self.commits.items()
+            if any(file_path.as_posix().endswith(f"/{path}") for path in commit.paths)
+        ]
+        return history[:max_count]
+
+    def disambiguate(self, prefix: str) -> list[str]:
+        return sorted(name for name in [*self.commits, *self.objects] if name.startswith(prefix))
+
+    def replaced_objects(self) -> dict[str, str]:
+        return dict(self.replacements)
+
+    def log_subjects(
+        self, author: Optional[str] = None, history_range: HistoryRange = HistoryRange()
+    ) -> dict[str, str]:
+        if history_range.since is not None or history_range.until is not None:
+            raise NotImplementedError("Fake commits have no dates")
+        history = list(self.commits.items())[: history_range.max_count]
+        return {
+            commit_hash: commit.subject
+            for commit_hash, commit in history
+            if author is None or re.search(author, commit.author)
+        }
+
+    def trailer_commits(
+        self, trailer: str, history_range: HistoryRange = HistoryRange()
+    ) -> dict[str, str]:
+        key, _, value = trailer.partition(":")
+        subjects: dict[str, str] = {}
+        for commit_hash, subject in self.log_subjects(history_range=history_range).items():
+            for found in self.commits[commit_hash].trailers:
+                found_key, _, found_value = found.partition(":")
+                if found_key.strip().casefold() == key.strip().casefold() and (
+                    not value.strip()
+                    or found_value.strip().casefold() == value.strip().casefold()
+                ):
+                    subjects[commit_hash] = subject
+                    break
+        return subjects
+
+    def log_messages(
+        self, history_range: HistoryRange = HistoryRange()
+    ) -> dict[str, tuple[str, str]]:
+        return {
+            commit_hash: (subject, self.commits[commit_hash].body)
+            for commit_hash, subject in self.log_subjects(history_range=history_range).items()
+        }
+
+    def log_changed_lines(
+        self, ignore_whitespace: bool = False, history_range: HistoryRange = HistoryRange()
+    ) -> dict[str, tuple[int, str]]:
+        counts: dict[str, tuple[int, str]] = {}
+        for commit_hash, subject in self.log_subjects(history_range=history_range).items():
+            commit = self.commits[commit_hash]
+            changed = sum(
+                line.startswith(("+", "-")) and not line.startswith(("+++", "---"))
+                for line in commit.diff.splitlines()
+            )
+            if ignore_whitespace:
+                changed = round(changed * (1 - commit.whitespace_ratio))
+            counts[commit_hash] = (changed, subject)
+        return counts
+
+    def find_reverts(self, commit_hash: str) -> list[str]:
+        return [
+            revert
+            for revert, commit in self.commits.items()
+            if f"This reverts commit {commit_hash}" in commit.body
+        ]
+
+    def first_parent_diff(self, commit_hash: str) -> str:
+        if commit_hash not in self.commits:
+            raise _not_found("show", commit_hash)
+        return self.commits[commit_hash].diff
+
+    def diffstat(self, commit_hash: str) -> str:
+        paths = self.commit_paths(commit_hash)
+        return "\n".join([*(f" {path}" for path in paths), f" {self.shortstat(commit_hash)}"])
+
+    def shortstat(self, commit_hash: str) -> str:
+        lines = self.first_parent_diff(commit_hash).splitlines()
+        files = len(self.commits[commit_hash].paths)
+        added = sum(line.startswith("+") and not line.startswith("+++") for line in lines)
+        deleted = sum(line.startswith("-") and not line.startswith("---") for line in lines)
+        return (
+            f"{files} file{'s' if files != 1 else ''} changed, "
+            f"{added} insertion{'s' if added != 1 else ''}(+), "
+            f"{deleted} deletion{'s' if deleted != 1 else ''}(-)"
+        )
+
+    def patch_id(self, commit_hash: str) -> Optional[str]:
+        diff = self.first_parent_diff(commit_hash)
+        return hashlib.sha1(diff.encode("utf-8")).hexdigest() if diff else None
+
+    def changed_paths(self, commit_hash: str) -> list[str]:
+        if commit_hash not in self.commits:
+            raise _not_found("show", commit_hash)
+        return list(self.commits[commit_hash].paths)
+
+    def commit_paths(self, commit_hash: str) -> list[str]:
+        return self.changed_paths(commit_hash)
+
+    def rename_counts(self, commit_hash: str) -> tuple[int, int]:
+        if commit_hash not in self.commits:
+            raise _not_found("show", commit_hash)
+        commit = self.commits[commit_hash]
+        return commit.renames, len(commit.paths)
+
+    def whitespace_ratio(self, commit_hash: str) -> float:
+        if commit_hash not in self.commits:
+            raise _not_found("show", commit_hash)
+        return self.commits[commit_hash].whitespace_ratio
+
+    def blame_attribution(
+        self,
+        path: str,
+        ignore_revs: Sequence[str] = (),
+        ignore_revs_file: Optional[Union[str, Path]] = None,
+    ) -> list[str]:
+        if path not in self.blame:
+            raise _not_found("blame", "HEAD", "--", path)
+        ignored = set(ignore_revs)
+        if ignore_revs_file is not None:
+            lines = Path(ignore_revs_file).read_text(encoding="utf-8").splitlines()
+            ignored.update(line.strip() for line in lines if _hash_regex.match(line.strip()))
+        history = list(self.commits)
+        attribution: list[str] = []
+        for commit_hash in self.blame[path]:
+            if commit_hash in ignored and commit_hash in history:
+                # The commit before it in history, which isn't ignored itself
+                earlier = history[history.index(commit_hash) + 1 :]
+                commit_hash = next((c for c in earlier if c not in ignored), commit_hash)
+            attribution.append(commit_hash)
+        return attribution
+
+    def blame_contents(self, path: str, contents: str) -> list[str]:
+        # Attributed the way the file is at HEAD, whatever `contents` is
+        return self.blame_attribution(path)
+
+    def remote_url(self, remote: str) -> str:
+        if remote not in self.remotes:
+            raise CalledProcessError(2, ["git", "remote", "get-url", remote])
+        return self.remotes[remote]
+
+    def remote_head(self, remote: str) -> Optional[str]:
+        return self.remote_heads.get(remote)
+
+    def is_ancestor(self, commit_hash: str, rev: str) -> bool:
+        if commit_hash not in self.commits or rev not in (*self.branches, "HEAD"):
+            raise _not_found("merge-base", "--is-ancestor", commit_hash, rev)
+        if rev not in self.branches:
+            # Every commit is in the history of HEAD
+            return True
+        return commit_hash in self.branches[rev]
+
+    def unreferenced_commits(self, commit_hashes: list[str]) -> dict[str, bool]:
+        return {
+            commit_hash: self.unreferenced[commit_hash]
+            for commit_hash in commit_hashes
+            if commit_hash in self.unreferenced
+        }
+
+    def is_shallow(self) -> bool:
+        return self.shallow
+
+    def matching_refs(self, pattern: str) -> list[str]:
+        return [name for name in self.branches if fnmatch.fnmatchcase(name, pattern)]
+
+    def is_tracked(self, path: str) -> bool:
+        return path in self.committed_files or path in self.staged_files
+
+    def is_committed(self, path: str) -> bool:
+        return path in self.committed_files
+
+    def ignore_rule(self, path: str) -> Optional[str]:
+        return self.ignore_rules.get(path)
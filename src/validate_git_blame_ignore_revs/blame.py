@@ -0,0 +1,128 @@
+import errno
+import os
+from pathlib import Path
+from subprocess import CalledProcessError
+from typing import Optional, TypedDict, Union
+
+from validate_git_blame_ignore_revs.backends import GitBackend, SubprocessGit
+from validate_git_blame_ignore_revs.lib import validate_git_blame_ignore_revs
+
+__all__ = (
+    "BlameComparison",
+    "BlameImpact",
+    "compare_blame",
+    "compute_blame_impact",
+    "default_ignore_revs_file",
+)
+
+# Line number -> number of lines at HEAD whose attribution changes
+BlameImpact = dict[int, int]
+
+
+class BlameComparison(TypedDict):
+    ignore_revs_file: Path
+    total_lines: int
+    changes: dict[int, tuple[str, str]]  # Line number -> (commit without ignoring, commit with ignoring)
+
+
+def compute_blame_impact(
+    file_path: Union[str, Path], git: Optional[GitBackend] = None
+) -> BlameImpact:
+    """
+    Computes how much each entry of a `.git-blame-ignore-revs` file changes `git blame`.
+
+    For every valid commit in the file, the paths it touched that still exist
+    at HEAD are blamed with and without ignoring that commit, and the lines
+    whose attribution differs are counted.
+
+    Args:
+        file_path (Union[str, Path]): Path to the `.git-blame-ignore-revs` file.
+        git (Optional[GitBackend]): How the commits are looked up and blamed. Defaults to running `git`.
+
+    Returns:
+        BlameImpact: A dictionary mapping the line number of each entry to the number of lines that change attribution. Entries whose commit cannot be found are omitted.
+    """
+    file_path = Path(file_path).resolve()
+    valid_hashes = validate_git_blame_ignore_revs(file_path)["valid_hashes"]
+
+    if git is None:
+        git = SubprocessGit()
+    git.enter(file_path.parent)
+    git.enter(Path(git.toplevel()))
+
+    # Blame without any ignored revisions is shared between entries.
+    baseline: dict[str, list[str]] = {}
+    impact: BlameImpact = {}
+
+    for line_number, commit_hash in valid_hashes.items():
+        try:
+            paths = git.changed_paths(commit_hash)
+        except CalledProcessError:
+            continue
+        changed_lines = 0
+        for path in paths:
+            if path not in baseline:
+                baseline[path] = git.blame_attribution(path)
+            ignored = git.blame_attribution(path, ignore_revs=[commit_hash])
+            changed_lines += sum(before != after for before, after in zip(baseline[path], ignored))
+        impact[line_number] = changed_lines
+
+    return impact
+
+
+def default_ignore_revs_file(git: Optional[GitBackend] = None) -> Path:
+    """Return the ignore file Git uses for the repository `git` entered, or the working directory.
+
+    This is `blame.ignoreRevsFile` if configured, otherwise `.git-blame-ignore-revs`
+    at the top level of the repository.
+    """
+    if git is None:
+        git = SubprocessGit()
+    top = Path(git.toplevel())
+    # The last value is the one Git uses
+    configured = git.config_values("blame.ignoreRevsFile", top)[-1:]
+    if configured and configured[0]:
+        return top / os.path.expanduser(configured[0])
+    return top / ".git-blame-ignore-revs"
+
+
+def compare_blame(
+    source_path: Union[str, Path],
+    ignore_revs_file: Optional[Union[str, Path]] = None,
+    git: Optional[GitBackend] = None,
+) -> BlameComparison:
+    """
+    Compares `git blame` of a source file with and without an ignore file.
+
+    Args:
+        source_path (Union[str, Path]): Path to the file to blame.
+        ignore_revs_file (Optional[Union[str, Path]]): Path to the `.git-blame-ignore-revs` file. Defaults to the one Git would use for the repository containing `source_path`.
+        git (Optional[GitBackend]): How the file is blamed. Defaults to running `git`.
+
+    Returns:
+        BlameComparison: A dictionary containing the ignore file used, the number of lines in the source file, and the lines whose attribution changes.
+    """
+    source_path = Path(source_path).resolve()
+    if ignore_revs_file is not None:
+        ignore_revs_file = Path(ignore_revs_file).resolve()
+
+    if git is None:
+        git = SubprocessGit()
+    git.enter(source_path.parent)
+    if ignore_revs_file is None:
+        ignore_revs_file = default_ignore_revs_file(git)
+    if not ignore_revs_file.exists():
+        raise FileNotFoundError(errno.ENOENT, os.strerror(errno.ENOENT), str(ignore_revs_file))
+
+    before = git.blame_attribution(source_path.name)
+    after = git.blame_attribution(source_path.name, ignore_revs_file=ignore_revs_file)
+
+    return BlameComparison(
+        ignore_revs_file=ignore_revs_file,
+        total_lines=len(before),
+        changes={
+            line_number: (commit_before, commit_after)
+            for line_number, (commit_before, commit_after) in enumerate(zip(before, after), start=1)
+            if commit_before != commit_after
+        },
+    )
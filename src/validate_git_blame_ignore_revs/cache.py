@@ -0,0 +1,195 @@
+import hashlib
+import json
+import os
+import time
+from dataclasses import fields, replace
+from pathlib import Path
+from subprocess import CalledProcessError
+from typing import Any, Optional, Union, cast
+
+from validate_git_blame_ignore_revs.backends import GitBackend, SubprocessGit
+from validate_git_blame_ignore_revs.lib import (
+    MissingReason,
+    ValidationOptions,
+    ValidationResult,
+    validate_git_blame_ignore_revs,
+)
+from validate_git_blame_ignore_revs.progress import Progress
+from validate_git_blame_ignore_revs.sources import ContentSource, WorkingTree
+
+__all__ = (
+    "CacheStats",
+    "cache_dir",
+    "cache_stats",
+    "cached_validate_git_blame_ignore_revs",
+)
+
+# Bump when the layout of cached results changes
+CACHE_VERSION = 24
+
+# Options of the checks whose results depend on where refs point rather than only on HEAD
+_REF_OPTIONS = ("reachable_from", "check_default_branch", "check_unreferenced")
+
+# Fields of ValidationResult that are keyed by commit hash rather than line number
+_HASH_KEYED_FIELDS = {"missing_pre_commit_ci_commits", "missing_trailer_commits"}
+
+
+class CacheStats:
+    """How many lookups of cached results found one that could be reused, and how many didn't."""
+
+    def __init__(self) -> None:
+        self.hits = 0
+        self.misses = 0
+
+
+# The lookups made by this process, such as for the metrics of `serve`
+cache_stats = CacheStats()
+
+
+def cache_dir() -> Path:
+    """Return the directory where validation results are cached."""
+    base = os.environ.get("XDG_CACHE_HOME") or Path.home() / ".cache"
+    return Path(base) / "validate-git-blame-ignore-revs"
+
+
+def _blob_hash(content: bytes) -> str:
+    """Return the object ID Git would give `content` as a blob."""
+    return hashlib.sha1(b"blob %d\0" % len(content) + content).hexdigest()
+
+
+def _head(directory: Path, git: GitBackend) -> str:
+    try:
+        return git.head_commit(directory)
+    except CalledProcessError:
+        return ""
+
+
+def _refs(directory: Path, git: GitBackend) -> dict[str, str]:
+    # Symbolic refs, such as `refs/remotes/origin/HEAD`, are given the ref they point to
+    try:
+        return git.ref_targets(directory)
+    except CalledProcessError:
+        return {}
+
+
+def _decode(data: dict[str, Any]) -> ValidationResult:
+    result: dict[str, Any] = {}
+    for field, entries in data.items():
+        if field in _HASH_KEYED_FIELDS:
+            result[field] = entries
+        elif field == "missing_reasons":
+            result[field] = {
+                int(line_number): (MissingReason(reason), explanation)
+                for line_number, (reason, explanation) in entries.items()
+            }
+        else:
+            result[field] = {
+                int(line_number): tuple(value) if isinstance(value, list) else value
+                for line_number, value in entries.items()
+            }
+    return cast(ValidationResult, result)
+
+
+def _older_than(result: ValidationResult, older_than: Optional[float]) -> ValidationResult:
+    """Keep the old entries of a result validated with `older_than=0` that are old enough now."""
+    if older_than is None:
+        return result
+    cutoff = time.time() - older_than
+    old_entries = {
+        line_number: committed
+        for line_number, committed in result["old_entries"].items()
+        if committed < cutoff
+    }
+    return cast(ValidationResult, {**result, "old_entries": old_entries})
+
+
+def cached_validate_git_blame_ignore_revs(
+    file_path: Union[str, Path],
+    options: Optional[ValidationOptions] = None,
+    source: Optional[ContentSource] = None,
+    progress: Optional[Progress] = None,
+    git: Optional[GitBackend] = None,
+) -> ValidationResult:
+    """
+    Validates a `.git-blame-ignore-revs` file, reusing the previous result when nothing relevant changed.
+
+    The result is keyed on the Git blob hash of the file as read from its
+    source, the commit checked out at HEAD (when any Git-based check is
+    enabled), where every ref points (when a check depends on refs, such as
+    `reachable_from`), the options, and the source. Only the most recent
+    result is kept for each file.
+
+    With `older_than`, the time each entry's commit was committed is cached,
+    and the cutoff, which moves with the clock, is applied afresh each time.
+    With `verify_pr_links`, the file is always validated again, since pull
+    requests can change on the forge without anything in the key changing.
+
+    Args:
+        file_path (Union[str, Path]): Path to the `.git-blame-ignore-revs` file.
+        options (Optional[ValidationOptions]): Which checks to run, and how. Defaults to only checking the syntax.
+        source (Optional[ContentSource]): Where to read the contents of the file from. Defaults to the working tree.
+        progress (Optional[Progress]): If given, told how the checks progress when the result isn't cached.
+        git (Optional[GitBackend]): How the checks that need Git look things up. Defaults to running `git`.
+
+    Returns:
+        ValidationResult: The same result `validate_git_blame_ignore_revs` would return.
+    """
+    if options is None:
+        options = ValidationOptions()
+    if source is None:
+        source = WorkingTree()
+    if git is None:
+        git = SubprocessGit()
+    if options.verify_pr_links:
+        return validate_git_blame_ignore_revs(
+            file_path, options, source=source, progress=progress, git=git
+        )
+    older_than = options.older_than
+    if older_than is not None:
+        # Every commit is older than no time at all, so the result holds the time of each
+        options = replace(options, older_than=0)
+    file_path = Path(file_path).resolve()
+    content = source.read_bytes(file_path)
+
+    uses_git = options.call_git or options.strict_comments_git
+    uses_refs = options.call_git and any(getattr(options, name) for name in _REF_OPTIONS)
+    key = json.dumps(
+        {
+            "version": CACHE_VERSION,
+            "blob": _blob_hash(content),
+            "head": _head(file_path.parent, git) if uses_git else None,
+            "refs": _refs(file_path.parent, git) if uses_refs else None,
+            # Git looks up different objects when told to ignore replacements
+            "no_replace_objects": bool(os.environ.get("GIT_NO_REPLACE_OBJECTS")),
+            "options": {field.name: getattr(options, field.name) for field in fields(options)},
+            "source": source,
+            "git": git,
+        },
+        sort_keys=True,
+        # Content sources and other objects among the options are identified by their representation
+        default=repr,
+    )
+
+    cache_file = cache_dir() / f"{hashlib.sha256(os.fsencode(file_path)).hexdigest()}.json"
+    try:
+        cached = json.loads(cache_file.read_text(encoding="utf-8"))
+        if cached["key"] == key:
+            result = _decode(cached["result"])
+            cache_stats.hits += 1
+            return _older_than(result, older_than)
+    except (OSError, ValueError, KeyError):
+        pass
+    cache_stats.misses += 1
+
+    result = validate_git_blame_ignore_revs(
+        file_path, options, source=source, progress=progress, git=git
+    )
+
+    try:
+        cache_file.parent.mkdir(parents=True, exist_ok=True)
+        cache_file.write_text(json.dumps({"key": key, "result": result}), encoding="utf-8")
+    except OSError:
+        # Caching is best effort
+        pass
+
+    return _older_than(result, older_than)
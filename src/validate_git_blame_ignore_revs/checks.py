@@ -0,0 +1,343 @@
+import re
+from typing import NamedTuple
+
+from validate_git_blame_ignore_revs.report import Severity
+
+__all__ = (
+    "CHECKS",
+    "CheckInfo",
+    "rule_id",
+)
+
+# Example hashes, which look real enough for the examples without naming any actual commit
+_HASH = "1f3a7c9e5b2d4f6081a3c5e7092b4d6f8a1c3e5b"
+_OTHER_HASH = "9e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b3a2f1e0d"
+
+
+def rule_id(title: str) -> str:
+    """Return the stable identifier of a check from its title, such as `missing-commits`."""
+    return re.sub(r"[^a-z0-9]+", "-", title.lower()).strip("-")
+
+
+class CheckInfo(NamedTuple):
+    title: str  # The heading of its findings in the report
+    severity: Severity
+    options: tuple[str, ...]  # The options that enable or configure it
+    description: str
+    rationale: str
+    failing: str  # Lines of a file that the check reports
+    passing: str  # The same lines, fixed
+
+
+_CHECK_LIST = [
+    CheckInfo(
+        "Errors",
+        Severity.Error,
+        (),
+        "Every line must be a full 40-character commit hash, a comment starting with #, or blank. "
+        "Lines that are almost a hash, such as one character short, are diagnosed, and with "
+        "--call-git the commit they most likely meant is suggested.",
+        "Git refuses to read a file with a line it can't parse, so one typo breaks `git blame` "
+        "for everybody who uses the file.",
+        f"# Apply black\n{_HASH[:7]}",
+        f"# Apply black\n{_HASH}",
+    ),
+    CheckInfo(
+        "Denied commits",
+        Severity.Error,
+        ("--config",),
+        "No entry may list a commit that the [denylist] section of the configuration file "
+        "forbids, by its hash, or with --call-git, by its subject or author.",
+        "Some commits must never be ignored, such as formatting commits that are known to also "
+        "change behavior.",
+        f"# Apply black (also fixes a bug)\n{_OTHER_HASH}",
+        "(the entry is removed)",
+    ),
+    CheckInfo(
+        "Missing commits",
+        Severity.Error,
+        ("--call-git",),
+        "Every hash must name a commit in the repository. Each that doesn't is reported with a "
+        "reason code, such as object-not-found or shallow-boundary.",
+        "A hash that names no commit does nothing, and usually means the commit was rebased or "
+        "squashed away after its hash was added.",
+        f"# Apply black (before the branch was rebased)\n{_OTHER_HASH}",
+        f"# Apply black\n{_HASH}",
+    ),
+    CheckInfo(
+        "Objects that aren't commits",
+        Severity.Error,
+        ("--call-git",),
+        "Every hash must name a commit rather than a tag, tree, or blob.",
+        "Git only ignores commits, so the hash of a tag or tree ignores nothing.",
+        f"# v1.0 (the annotated tag, not the commit)\n{_OTHER_HASH}",
+        f"# Apply black\n{_HASH}",
+    ),
+    CheckInfo(
+        "Replaced commits",
+        Severity.Warning,
+        ("--call-git", "--no-replace-objects"),
+        "Reports commits that have been replaced with `git replace`.",
+        "Git shows and blames the replacement instead, so ignoring the listed commit may not "
+        "have the effect you expect. --no-replace-objects looks up the commits themselves.",
+        f"# Apply black (replaced by a fixed-up commit)\n{_OTHER_HASH}",
+        f"# Apply black\n{_HASH}",
+    ),
+    CheckInfo(
+        "Tracking problems",
+        Severity.Warning,
+        ("--check-tracked",),
+        "The file must be committed and not matched by a .gitignore rule.",
+        "An untracked or only staged file ignores nothing for anybody else, and an ignored one is "
+        "easily lost.",
+        "(the file is untracked)",
+        "(the file is committed)",
+    ),
+    CheckInfo(
+        "Ordering problems",
+        Severity.Warning,
+        ("--append-only",),
+        "Compares the file with the file as it is in a revision, such as the base branch of a "
+        "pull request. Existing entries must keep their order and comments, and new entries must "
+        "come after the existing entries of their section, a block of lines separated by blank "
+        "lines.",
+        "A change that only appends entries is easy to review, while one that reorders or "
+        "rewrites existing entries hides what it really changes.",
+        f"# Apply black\n{_OTHER_HASH}\n# Apply black\n{_HASH}  ({_OTHER_HASH} is new)",
+        f"# Apply black\n{_HASH}\n# Apply black\n{_OTHER_HASH}",
+    ),
+    CheckInfo(
+        "Differences from the default branch",
+        Severity.Warning,
+        ("--compare-remote", "--remote"),
+        "Compares the entries with those of the file on the default branch of the remote, "
+        "fetched for the check, and reports the entries that are only in one of them.",
+        "Blame is run with the local file, so a stale copy makes blame ignore different commits "
+        "than it does for everybody else.",
+        f"# Apply black\n{_HASH}  (the default branch also lists {_OTHER_HASH})",
+        f"# Apply black\n{_HASH}\n# Apply ruff\n{_OTHER_HASH}",
+    ),
+    CheckInfo(
+        "Strict comment errors",
+        Severity.Warning,
+        ("--strict-comments",),
+        "Every hash must have one or more comments above it, and they can't all be empty or "
+        "placeholders such as TODO, fix, or formatting.",
+        "Without a comment, reviewers can't tell why a commit is ignored without looking it up.",
+        _HASH,
+        f"# Apply black\n{_HASH}",
+    ),
+    CheckInfo(
+        "Comment diffs",
+        Severity.Warning,
+        ("--strict-comments-git", "--comment-match", "--similarity-threshold", "--normalize"),
+        "The comment above each hash must match the commit message, by default its start.",
+        "A comment that matches the message shows that the hash was pasted with the right comment.",
+        f"# Apply black\n{_HASH}  (the commit is 'Update the changelog')",
+        f"# Update the changelog\n{_HASH}",
+    ),
+    CheckInfo(
+        "Missing pre-commit-ci commits",
+        Severity.Warning,
+        ("--pre-commit-ci", "--since", "--until", "--max-count"),
+        "Every commit authored by pre-commit-ci[bot] must be listed.",
+        "pre-commit-ci commits only apply automatic fixes, such as from formatters, so they are "
+        "exactly the commits that should be ignored.",
+        "(the file doesn't list the commit '[pre-commit.ci] auto fixes')",
+        f"# [pre-commit.ci] auto fixes\n{_HASH}",
+    ),
+    CheckInfo(
+        "Missing trailer commits",
+        Severity.Warning,
+        ("--trailer", "--config", "--since", "--until", "--max-count"),
+        "Every commit whose message carries the trailer, such as `Blame-Ignore: true`, must be "
+        "listed.",
+        "The trailer lets authors mark formatting commits as they make them, so that listing "
+        "them afterwards can't be forgotten.",
+        "(the file doesn't list the commit 'Reformat with black', which has the trailer)",
+        f"# Reformat with black\n{_HASH}",
+    ),
+    CheckInfo(
+        "Unused entries",
+        Severity.Warning,
+        ("--report-unused-entries",),
+        "Reports commits that no line at HEAD is attributed to anymore.",
+        "Once every line a commit changed has been changed again, ignoring it changes nothing, "
+        "and the entry only slows down `git blame`.",
+        f"# Apply black (every line it touched has been reformatted since)\n{_HASH}",
+        "(the entry is removed)",
+    ),
+    CheckInfo(
+        "Duplicate patches",
+        Severity.Warning,
+        ("--check-duplicate-patches",),
+        "Reports commits that make the same change as a commit listed earlier.",
+        "Cherry-picks and backports are often listed twice, once from each branch, where one "
+        "entry would usually do.",
+        f"# Apply black\n{_HASH}\n# Apply black (backport)\n{_OTHER_HASH}",
+        f"# Apply black\n{_HASH}",
+    ),
+    CheckInfo(
+        "Commits not on the default branch",
+        Severity.Warning,
+        ("--check-default-branch", "--remote"),
+        "Every commit must be reachable from the default branch of the remote.",
+        "Commits of a feature branch that was squashed or rebased when it was merged don't exist "
+        "in the history everybody else blames.",
+        f"# Apply black (on the branch before it was squashed)\n{_OTHER_HASH}",
+        f"# Apply black\n{_HASH}",
+    ),
+    CheckInfo(
+        "Commits not reachable from required refs",
+        Severity.Warning,
+        ("--reachable-from", "--config"),
+        "Every commit must be reachable from each of the refs given, such as `main` and every "
+        "branch matching `release/*`. Each ref a commit isn't on is reported, as is each pattern "
+        "that matches no ref.",
+        "Teams that maintain long-lived release branches blame them too, so a formatting commit "
+        "that was never merged into a release branch is still attributed blame there.",
+        f"# Apply black (only on main, not release/1.0)\n{_OTHER_HASH}",
+        f"# Apply black (merged into every release branch)\n{_HASH}",
+    ),
+    CheckInfo(
+        "Unreferenced commits",
+        Severity.Warning,
+        ("--check-unreferenced",),
+        "Every commit must be reachable from a branch, tag, or other ref. Commits that only the "
+        "reflog reaches are told apart from dangling ones that nothing reaches.",
+        "Amending, rebasing, or squashing a commit leaves the original behind in the local "
+        "repository, where it still looks valid, but `git gc` deletes it, and nobody else ever "
+        "had it.",
+        f"# Apply black (before the commit was amended)\n{_OTHER_HASH}",
+        f"# Apply black\n{_HASH}",
+    ),
+    CheckInfo(
+        "Likely semantic changes",
+        Severity.Warning,
+        ("--check-semantic-changes", "--semantic-threshold"),
+        "Reports commits whose diffs change identifiers or literals rather than only formatting.",
+        "Ignoring a commit that changes behavior hides who made that change from `git blame`.",
+        f"# Apply black\n{_OTHER_HASH}  (the commit also renames a function)",
+        f"# Apply black\n{_HASH}",
+    ),
+    CheckInfo(
+        "Entries in the wrong section",
+        Severity.Warning,
+        ("--check-sections",),
+        "Entries below a section header comment that names a formatter, such as `## black` or "
+        "`# --- prettier ---`, must list commits of that formatter. A commit is attributed to the "
+        "formatter its subject names, or otherwise to the one that formats every file it changes.",
+        "Files grouped by tool are only easy to audit when each entry is in the right group.",
+        f"## prettier\n# Apply black\n{_OTHER_HASH}",
+        f"## black\n# Apply black\n{_OTHER_HASH}",
+    ),
+    CheckInfo(
+        "Rename-heavy commits",
+        Severity.Warning,
+        ("--check-renames",),
+        "Reports commits where most of the changed files were renamed.",
+        "`git blame` already follows a file across renames, and --ignore-rev doesn't change how "
+        "it does, so ignoring a commit that mostly renames files usually achieves nothing.",
+        f"# Move the sources into src/\n{_OTHER_HASH}",
+        "(the entry is removed)",
+    ),
+    CheckInfo(
+        "Unlikely formatting commits",
+        Severity.Warning,
+        ("--score-formatting", "--formatting-threshold"),
+        "Scores how likely each commit is to only reformat code, from the fraction of its changed "
+        "lines that only change whitespace, how many files it touches, whether its subject "
+        "mentions formatting or a formatter, and whether a bot authored it, and reports commits "
+        "scoring below the threshold.",
+        "The score is a quick way for reviewers to spot entries that probably shouldn't be "
+        "ignored, such as a feature pasted in place of the formatting commit before it.",
+        f"# Apply black\n{_OTHER_HASH}  (the commit is 'Add g', changing one line of one file)",
+        f"# Apply black\n{_HASH}",
+    ),
+    CheckInfo(
+        "Entries added before their commits",
+        Severity.Warning,
+        ("--check-entry-dates",),
+        "Every entry must have been added to the file in a commit made after the commit it "
+        "lists, going by commit dates.",
+        "An entry that is older than its commit was probably listed before the commit was "
+        "rewritten by a force push, such as when a branch was rebased, so the hash it lists may "
+        "be stale.",
+        f"# Apply black\n{_OTHER_HASH}  (listed before the commit was rebased)",
+        f"# Apply black\n{_HASH}",
+    ),
+    CheckInfo(
+        "Old entries",
+        Severity.Info,
+        ("--warn-older-than", "--call-git"),
+        "Reports entries whose commits were committed longer ago than the given duration.",
+        "Once every line a formatting commit touched has been changed again, ignoring it no "
+        "longer makes a difference, so old entries are worth reviewing periodically, such as "
+        "with --report-unused-entries.",
+        f"# Apply black (committed in 2015)\n{_HASH}",
+        f"# Apply black (committed last month)\n{_HASH}",
+    ),
+    CheckInfo(
+        "Metadata errors",
+        Severity.Warning,
+        ("--check-metadata", "--config"),
+        "The comments above each hash must have the `Key: value` pairs the [metadata] section of "
+        "the configuration file requires, in the formats it gives.",
+        "Consistent metadata, such as the tool and pull request, makes the file easy to audit.",
+        f"# Apply black\n{_HASH}",
+        f"# Apply black\n# Tool: black\n# PR: #123\n{_HASH}",
+    ),
+    CheckInfo(
+        "Pull request reference errors",
+        Severity.Warning,
+        ("--require-pr-link", "--verify-pr-links", "--remote"),
+        "The comments above each hash must reference a pull request. With --verify-pr-links, the "
+        "pull request must exist on GitHub or GitLab and contain the commit.",
+        "The pull request records where the change was reviewed and why it was made.",
+        f"# Apply black\n{_HASH}",
+        f"# Apply black (#123)\n{_HASH}",
+    ),
+    CheckInfo(
+        "Missing ticket references",
+        Severity.Warning,
+        ("--ticket-pattern",),
+        "The comments above each hash must contain a match for the ticket pattern.",
+        "Projects that track all work in tickets can link each ignored commit to its ticket.",
+        f"# Apply black\n{_HASH}",
+        f"# Apply black for JIRA-42\n{_HASH}",
+    ),
+    CheckInfo(
+        "Commented-out entries",
+        Severity.Warning,
+        ("--check-commented-out",),
+        "Reports comments that start with a commit hash.",
+        "These are usually entries that were disabled while debugging and then forgotten.",
+        f"# Apply black\n# {_HASH}",
+        f"# Apply black\n{_HASH}",
+    ),
+    CheckInfo(
+        "Blank line problems",
+        Severity.Warning,
+        ("--check-blank-lines",),
+        "Groups of entries, each a block of comments and the entries below it, must be separated "
+        "by exactly one blank line. There must be no blank line between a comment and its entry, "
+        "or at the start or end of the file.",
+        "A consistent layout keeps large files easy to scan and changes to them easy to review.",
+        f"# Apply black\n\n{_HASH}\n# Apply ruff\n{_OTHER_HASH}",
+        f"# Apply black\n{_HASH}\n\n# Apply ruff\n{_OTHER_HASH}",
+    ),
+    CheckInfo(
+        "Possible misspellings",
+        Severity.Info,
+        ("--check-spelling", "--spelling-dictionary"),
+        "Words in comments must be in the dictionary or in the words of the project listed in "
+        "the [spelling] section of the configuration file. Acronyms, words in camel case or with "
+        "digits, URLs, and code in backticks are skipped.",
+        "Comments end up in documentation exported from the file, such as by export.",
+        f"# Aply black\n{_HASH}",
+        f"# Apply black\n{_HASH}",
+    ),
+]
+
+# Rule ID -> Check, in the order the checks are reported
+CHECKS = {rule_id(check.title): check for check in _CHECK_LIST}
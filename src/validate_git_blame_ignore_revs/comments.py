@@ -0,0 +1,169 @@
+import re
+import unicodedata
+from collections.abc import Iterable
+from difflib import SequenceMatcher
+from enum import Enum
+from typing import Literal, Optional
+
+__all__ = (
+    "DEFAULT_SIMILARITY_THRESHOLD",
+    "MatchMode",
+    "Normalization",
+    "comment_matches",
+    "comment_similarity",
+    "is_placeholder_comment",
+    "match_directive",
+    "normalize_comment",
+    "split_match_directives",
+)
+
+# A comment of the form `match: <mode>` that overrides the mode for one entry
+_match_directive_regex = re.compile(r"^match:\s*(?P<mode>[\w-]+)$", re.IGNORECASE)
+
+# The default score at or above which a comment is similar enough to a subject
+DEFAULT_SIMILARITY_THRESHOLD = 0.8
+
+Normalization = Literal["NFC", "NFKC"]
+
+# Comments that say nothing about why a commit is ignored, compared ignoring case and punctuation
+PLACEHOLDER_COMMENTS = frozenset(
+    {
+        "",
+        "todo",
+        "fixme",
+        "fix",
+        "tbd",
+        "xxx",
+        "wip",
+        "placeholder",
+        "comment",
+        "format",
+        "formatting",
+        "reformat",
+        "style",
+        "lint",
+    }
+)
+
+# Typographic punctuation that NFKC leaves alone but editors often substitute
+_typographic_punctuation = str.maketrans(
+    {
+        "\u2018": "'",
+        "\u2019": "'",
+        "\u201c": '"',
+        "\u201d": '"',
+        "\u2013": "-",
+        "\u2014": "-",
+    }
+)
+
+
+class MatchMode(str, Enum):
+    """How the comment above an entry is compared with the commit subject."""
+
+    Exact = "exact"  # The comment is the whole subject
+    Prefix = "prefix"  # The subject starts with the comment
+    Substring = "substring"  # The comment appears anywhere in the subject
+    Regex = "regex"  # The comment is a regular expression found in the subject
+    IgnoreCase = "ignore-case"  # The subject starts with the comment, ignoring case
+    Similar = "similar"  # The comment resembles the subject, see `comment_similarity`
+
+
+def normalize_comment(
+    text: str, normalization: Optional[Normalization] = None, casefold: bool = False
+) -> str:
+    """
+    Normalizes a comment or commit subject before they are compared.
+
+    NFC composes combining characters, so that text typed differently
+    compares equal. NFKC also replaces compatibility characters such as
+    ligatures and full-width letters, and in addition, typographic quotes and
+    dashes are replaced with their ASCII equivalents.
+    """
+    if normalization is not None:
+        text = unicodedata.normalize(normalization, text)
+        if normalization == "NFKC":
+            text = text.translate(_typographic_punctuation)
+    if casefold:
+        text = text.casefold()
+    return text
+
+
+def comment_similarity(comment: str, commit_message: str) -> float:
+    """
+    Scores how closely a comment resembles a commit subject, from 0 to 1.
+
+    The score is the larger of the edit similarity of the two strings and the
+    fraction of the comment's words that appear in the subject, both ignoring
+    case and punctuation, so that a comment may paraphrase or shorten the
+    subject.
+    """
+    comment_words = re.findall(r"\w+", comment.casefold())
+    message_words = re.findall(r"\w+", commit_message.casefold())
+    if not comment_words:
+        return 1.0 if not message_words else 0.0
+    edit = SequenceMatcher(None, " ".join(comment_words), " ".join(message_words)).ratio()
+    overlap = sum(word in message_words for word in comment_words) / len(comment_words)
+    return max(edit, overlap)
+
+
+def comment_matches(
+    comment: str,
+    commit_message: str,
+    mode: MatchMode,
+    similarity_threshold: float = DEFAULT_SIMILARITY_THRESHOLD,
+) -> bool:
+    """Return whether a comment matches a commit subject according to `mode`."""
+    if mode is MatchMode.Exact:
+        return comment == commit_message
+    if mode is MatchMode.Prefix:
+        return commit_message.startswith(comment)
+    if mode is MatchMode.Substring:
+        return comment in commit_message
+    if mode is MatchMode.Regex:
+        try:
+            return re.search(comment, commit_message) is not None
+        except re.error:
+            return False
+    if mode is MatchMode.Similar:
+        return comment_similarity(comment, commit_message) >= similarity_threshold
+    return commit_message.casefold().startswith(comment.casefold())
+
+
+def is_placeholder_comment(comment: str) -> bool:
+    """Return whether a comment is empty or a placeholder such as `TODO` or `formatting`."""
+    return re.sub(r"[\W_]+", " ", comment).strip().casefold() in PLACEHOLDER_COMMENTS
+
+
+def match_directive(comment: str) -> Optional[MatchMode]:
+    """Return the mode named by a `match: <mode>` comment, or None for other comments.
+
+    Raises:
+        ValueError: If the comment is a directive naming an unknown mode.
+    """
+    match = _match_directive_regex.match(comment)
+    if not match:
+        return None
+    return MatchMode(match.group("mode").lower())
+
+
+def split_match_directives(
+    comments: Iterable[str],
+) -> tuple[list[str], Optional[MatchMode]]:
+    """Separate `match: <mode>` directives from the other comments above an entry.
+
+    Returns the remaining comments and the mode named by the last directive,
+    if any. Directives naming an unknown mode are kept as ordinary comments.
+    """
+    remaining: list[str] = []
+    mode: Optional[MatchMode] = None
+    for comment in comments:
+        try:
+            directive = match_directive(comment)
+        except ValueError:
+            directive = None
+        if directive is None:
+            remaining.append(comment)
+        else:
+            mode = directive
+    return remaining, mode
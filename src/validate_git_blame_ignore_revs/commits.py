@@ -0,0 +1,143 @@
+import re
+from collections import Counter
+from subprocess import CalledProcessError
+from typing import Optional
+
+from validate_git_blame_ignore_revs.backends import GitBackend, SubprocessGit
+from validate_git_blame_ignore_revs.git import CommitInfo
+
+__all__ = (
+    "CommitInfo",
+    "diff_semantic_change_score",
+    "diffstat",
+    "find_reverts",
+    "first_parent_diff",
+    "formatting_likelihood",
+    "get_commit_info",
+    "is_reachable",
+    "semantic_change_score",
+    "shortstat",
+    "whitespace_ratio",
+)
+
+# Identifiers and numbers, string literals, or any other single character
+_token_regex = re.compile(r"\w+|\"(?:[^\"\\]|\\.)*\"|'(?:[^'\\]|\\.)*'|\S")
+
+# Words in a commit subject that suggest it only reformats code, including common formatters
+_formatting_keyword_regex = re.compile(
+    r"\b(?:(?:re)?format\w*|lint\w*|whitespace|indent\w*|style|black|prettier|ruff|isort|yapf"
+    r"|autopep8|clang-format|rustfmt|gofmt|pre-commit)\b",
+    re.IGNORECASE,
+)
+
+# Bot accounts, such as `pre-commit-ci[bot]`, usually only apply automatic fixes
+_bot_author_regex = re.compile(r"\[bot\]|\bbot\b", re.IGNORECASE)
+
+# A commit touching this many files gets the full weight for the number of files it touches
+_MANY_FILES = 10
+
+
+def _backend(git: Optional[GitBackend]) -> GitBackend:
+    return git if git is not None else SubprocessGit()
+
+
+def get_commit_info(rev: str, git: Optional[GitBackend] = None) -> CommitInfo:
+    """Resolve a revision and return the metadata of the commit it names."""
+    return _backend(git).commit_info(rev)
+
+
+def diffstat(rev: str, git: Optional[GitBackend] = None) -> str:
+    """Return the diffstat of a commit against its first parent."""
+    return _backend(git).diffstat(rev)
+
+
+def shortstat(rev: str, git: Optional[GitBackend] = None) -> str:
+    """Return the summary line of the diffstat of a commit, such as "1 file changed, 2 insertions(+)"."""
+    return _backend(git).shortstat(rev)
+
+
+def whitespace_ratio(rev: str, git: Optional[GitBackend] = None) -> float:
+    """Return the fraction of changed lines in a commit that only change whitespace.
+
+    A commit that changes no lines at all is reported as 0.0.
+    """
+    return _backend(git).whitespace_ratio(rev)
+
+
+def formatting_likelihood(
+    whitespace_ratio: float, files_changed: int, subject: str, author: str
+) -> float:
+    """Estimate how likely a commit is to only reformat code, between 0.0 and 1.0.
+
+    The score combines the fraction of changed lines that only change
+    whitespace, how many files the commit touches (formatters usually touch
+    many), whether its subject mentions formatting or a formatter, and whether
+    its author, as `Name <email>`, is a bot.
+    """
+    score = 0.4 * whitespace_ratio + 0.2 * min(files_changed / _MANY_FILES, 1.0)
+    if _formatting_keyword_regex.search(subject):
+        score += 0.25
+    if _bot_author_regex.search(author):
+        score += 0.15
+    return score
+
+
+def is_reachable(rev: str, ref: str = "HEAD", git: Optional[GitBackend] = None) -> bool:
+    """Return whether a commit is in the history of `ref`."""
+    try:
+        return _backend(git).is_ancestor(rev, ref)
+    except CalledProcessError:
+        return False
+
+
+def find_reverts(commit_hash: str, git: Optional[GitBackend] = None) -> list[str]:
+    """Return the commits reachable from HEAD whose message says they revert `commit_hash`."""
+    return _backend(git).find_reverts(commit_hash)
+
+
+def _significant_tokens(line: str) -> list[str]:
+    """Return the identifiers and literals in a line of code, with string quoting normalized."""
+    tokens = []
+    for token in _token_regex.findall(line):
+        if token[0] in "\"'":
+            # Formatters commonly change the quoting style of strings
+            tokens.append(token[1:-1])
+        elif token[0].isalnum() or token[0] == "_":
+            tokens.append(token)
+    return tokens
+
+
+def semantic_change_score(rev: str, git: Optional[GitBackend] = None) -> float:
+    """Estimate how likely a commit is to change behavior rather than only formatting.
+
+    The identifiers and literals on removed and added lines are compared as
+    multisets, so reindenting, rewrapping, reordering, and punctuation changes
+    such as added trailing commas are ignored. The score is the fraction of
+    those tokens that appear on only one side of the diff, between 0.0 (pure
+    formatting) and 1.0.
+    """
+    return diff_semantic_change_score(first_parent_diff(rev, git))
+
+
+def first_parent_diff(rev: str, git: Optional[GitBackend] = None) -> str:
+    """Return the changed lines of a commit against its first parent, without context lines."""
+    return _backend(git).first_parent_diff(rev)
+
+
+def diff_semantic_change_score(diff: str) -> float:
+    """Score a diff from `first_parent_diff` the way `semantic_change_score` scores a commit."""
+    removed: Counter[str] = Counter()
+    added: Counter[str] = Counter()
+    for line in diff.splitlines():
+        if line.startswith(("+++", "---")):
+            continue
+        if line.startswith("-"):
+            removed.update(_significant_tokens(line[1:]))
+        elif line.startswith("+"):
+            added.update(_significant_tokens(line[1:]))
+
+    total = sum(removed.values()) + sum(added.values())
+    if not total:
+        return 0.0
+    differing = sum((removed - added).values()) + sum((added - removed).values())
+    return differing / total
@@ -0,0 +1,264 @@
+import configparser
+import re
+from pathlib import Path
+from typing import Literal, Optional, TypedDict, Union, cast
+
+from validate_git_blame_ignore_revs.comments import (
+    DEFAULT_SIMILARITY_THRESHOLD,
+    MatchMode,
+    Normalization,
+)
+from validate_git_blame_ignore_revs.denylist import Denylist
+from validate_git_blame_ignore_revs.git import is_valid_trailer
+from validate_git_blame_ignore_revs.metadata import MetadataPolicy
+from validate_git_blame_ignore_revs.template import check_template
+
+__all__ = (
+    "CONFIG_FILE_NAME",
+    "DEFAULT_FILE_NAME",
+    "Config",
+    "EmptyFilePolicy",
+    "default_config",
+    "find_config",
+    "load_config",
+)
+
+# Looked for next to the `.git-blame-ignore-revs` file when no configuration is given
+CONFIG_FILE_NAME = ".validate-git-blame-ignore-revs.cfg"
+
+# The name Git hosts and editors look for, which the file is expected to have
+DEFAULT_FILE_NAME = ".git-blame-ignore-revs"
+
+
+# Whether a file without any entries passes, passes with a warning, or fails
+EmptyFilePolicy = Literal["allow", "warn", "error"]
+
+
+class Config(TypedDict):
+    metadata: Optional[MetadataPolicy]  # None if the file has no [metadata] section
+    ticket_pattern: Optional[str]  # Regular expression every entry's comments must contain
+    comment_match: MatchMode
+    similarity_threshold: float  # Score at or above which comments match in the similar mode
+    comment_template: Optional[str]  # How comments describing commits are written and checked
+    normalization: Optional[Normalization]  # Unicode normalization applied before comparing
+    casefold: bool
+    empty_file: EmptyFilePolicy
+    file_name: str  # The name the file is expected to have
+    trailer: Optional[str]  # Trailer, such as "Blame-Ignore: true", of commits that must be listed
+    reachable_from: list[str]  # Refs or glob patterns, such as "release/*", every commit must be on
+    denylist: Optional[Denylist]  # None if the file has no [denylist] section
+    spelling_words: list[str]  # Words of the project that comments may use, such as tool names
+
+
+def default_config() -> Config:
+    """Return the settings used when there is no configuration file."""
+    return Config(
+        metadata=None,
+        ticket_pattern=None,
+        comment_match=MatchMode.Prefix,
+        similarity_threshold=DEFAULT_SIMILARITY_THRESHOLD,
+        comment_template=None,
+        normalization=None,
+        casefold=False,
+        empty_file="warn",
+        file_name=DEFAULT_FILE_NAME,
+        trailer=None,
+        reachable_from=[],
+        denylist=None,
+        spelling_words=[],
+    )
+
+
+def _split_list(value: str) -> list[str]:
+    return [item.strip() for item in value.replace("\n", ",").split(",") if item.strip()]
+
+
+def _split_lines(value: str) -> list[str]:
+    # For regular expressions, which can contain commas
+    return [line.strip() for line in value.splitlines() if line.strip()]
+
+
+def _check_pattern(pattern: str, where: str) -> None:
+    try:
+        re.compile(pattern)
+    except re.error as e:
+        raise ValueError(f"Invalid regular expression for {where}: {e}") from e
+
+
+def load_config(path: Union[str, Path], defaults: Optional[Config] = None) -> Config:
+    """
+    Reads a configuration file.
+
+    The file uses INI syntax. The `[metadata]` section lists the keys every
+    entry's comments must contain, and `[metadata.formats]` maps keys to the
+    regular expressions their values must match. In the `[comments]` section,
+    `ticket_pattern` is a regular expression that every entry's comments must
+    contain a match for, `match` is how `--strict-comments-git` compares
+    comments with commit messages, and `similarity_threshold` is the score
+    from 0 to 1 comments need in the `similar` mode. `template` is how the
+    comments describing commits are written, such as by `import`, with
+    fields such as `{subject}`, `{date}`, and `{pr}`, and with it,
+    `--strict-comments-git` compares comments with the template written for
+    each commit rather than with its subject. Before comparing,
+    `normalize` applies a Unicode normalization form (NFC or NFKC) and
+    `casefold` ignores case. In the `[file]` section, `empty` is whether a
+    file without any entries is allowed, a warning, or an error, and `name`
+    is the name the file is expected to have. In the `[commits]` section,
+    `trailer` is a trailer, such as `Blame-Ignore: true` or only its key,
+    that authors add to commits that must be listed, and `reachable_from`
+    lists the refs, or glob patterns matching them, from which every listed
+    commit must be reachable. The `[denylist]` section
+    lists commits that must never be listed, as full or abbreviated hashes in
+    `commits`, and as regular expressions, one per line, searched for in their
+    subjects in `subjects` and in their authors in `authors`. In the
+    `[spelling]` section, `words` lists the words that `--check-spelling`
+    accepts in comments besides those of the dictionary, such as the names
+    of tools and people:
+
+        [metadata]
+        required_keys = Tool, PR
+
+        [metadata.formats]
+        Tool = black|isort|ruff
+        PR = #[0-9]+
+
+        [comments]
+        ticket_pattern = JIRA-[0-9]+
+        match = similar
+        similarity_threshold = 0.7
+        template = {subject} ({date}, {pr})
+        normalize = NFKC
+        casefold = true
+
+        [file]
+        empty = error
+        name = .git-blame-ignore-revs
+
+        [commits]
+        trailer = Blame-Ignore: true
+        reachable_from = main, release/*
+
+        [denylist]
+        commits = 0123456789abcdef
+        subjects = ^Add\\b
+        authors = @contractor\\.example$
+
+        [spelling]
+        words = isort, prettier, rustfmt
+
+    Args:
+        path (Union[str, Path]): Path to the configuration file.
+        defaults (Optional[Config]): The settings used for those the file doesn't have, such as from a profile. Defaults to `default_config()`.
+
+    Returns:
+        Config: The settings found in the file.
+
+    Raises:
+        ValueError: If a format or pattern is not a valid regular expression, the comment template uses an unknown field, the match mode, normalization form, or empty file policy is unknown, the trailer has no valid key, a denied hash isn't hexadecimal, or a value has the wrong type.
+    """
+    if defaults is None:
+        defaults = default_config()
+    parser = configparser.ConfigParser(interpolation=None)
+    # Keep keys case-sensitive, since they're compared with the comments
+    parser.optionxform = str  # type: ignore[method-assign]
+    with Path(path).open(encoding="utf-8") as f:
+        parser.read_file(f)
+
+    metadata = defaults["metadata"]
+    if parser.has_section("metadata") or parser.has_section("metadata.formats"):
+        formats: dict[str, str] = {}
+        if parser.has_section("metadata.formats"):
+            formats = dict(parser.items("metadata.formats"))
+        for key, pattern in formats.items():
+            _check_pattern(pattern, f"{key} in [metadata.formats]")
+        metadata = MetadataPolicy(
+            required_keys=_split_list(parser.get("metadata", "required_keys", fallback="")),
+            formats=formats,
+        )
+
+    ticket_pattern = parser.get("comments", "ticket_pattern", fallback=defaults["ticket_pattern"])
+    if ticket_pattern is not None:
+        _check_pattern(ticket_pattern, "ticket_pattern in [comments]")
+
+    comment_match = parser.get("comments", "match", fallback=defaults["comment_match"].value)
+    try:
+        match_mode = MatchMode(comment_match)
+    except ValueError:
+        modes = ", ".join(mode.value for mode in MatchMode)
+        raise ValueError(
+            f"Unknown match in [comments]: {comment_match!r} (expected {modes})"
+        ) from None
+
+    similarity_threshold = parser.getfloat(
+        "comments", "similarity_threshold", fallback=defaults["similarity_threshold"]
+    )
+    if not 0 <= similarity_threshold <= 1:
+        raise ValueError(
+            f"similarity_threshold in [comments] must be from 0 to 1, not {similarity_threshold}"
+        )
+
+    comment_template = parser.get("comments", "template", fallback=defaults["comment_template"])
+    if comment_template is not None:
+        try:
+            check_template(comment_template)
+        except ValueError as e:
+            raise ValueError(f"Invalid template in [comments]: {e}") from None
+
+    normalization = parser.get("comments", "normalize", fallback=defaults["normalization"])
+    if normalization not in (None, "NFC", "NFKC"):
+        raise ValueError(f"Unknown normalize in [comments]: {normalization!r} (expected NFC, NFKC)")
+
+    empty_file = parser.get("file", "empty", fallback=defaults["empty_file"])
+    if empty_file not in ("allow", "warn", "error"):
+        raise ValueError(f"Unknown empty in [file]: {empty_file!r} (expected allow, warn, error)")
+
+    trailer = parser.get("commits", "trailer", fallback=defaults["trailer"])
+    if trailer is not None and not is_valid_trailer(trailer):
+        raise ValueError(f"Invalid trailer in [commits]: {trailer!r} (expected Key or Key: value)")
+
+    reachable_from = defaults["reachable_from"]
+    if parser.has_option("commits", "reachable_from"):
+        reachable_from = _split_list(parser.get("commits", "reachable_from"))
+
+    denylist = defaults["denylist"]
+    if parser.has_section("denylist"):
+        commits = _split_list(parser.get("denylist", "commits", fallback=""))
+        for commit in commits:
+            if not re.fullmatch(r"[0-9a-f]{4,40}", commit):
+                raise ValueError(
+                    f"Invalid commit in [denylist]: {commit!r} "
+                    "(expected 4 to 40 lowercase hexadecimal characters)"
+                )
+        subjects = _split_lines(parser.get("denylist", "subjects", fallback=""))
+        authors = _split_lines(parser.get("denylist", "authors", fallback=""))
+        for pattern in subjects:
+            _check_pattern(pattern, "subjects in [denylist]")
+        for pattern in authors:
+            _check_pattern(pattern, "authors in [denylist]")
+        denylist = Denylist(commits=commits, subjects=subjects, authors=authors)
+
+    spelling_words = defaults["spelling_words"]
+    if parser.has_option("spelling", "words"):
+        spelling_words = _split_list(parser.get("spelling", "words"))
+
+    return Config(
+        metadata=metadata,
+        ticket_pattern=ticket_pattern,
+        comment_match=match_mode,
+        similarity_threshold=similarity_threshold,
+        comment_template=comment_template,
+        normalization=cast(Optional[Normalization], normalization),
+        casefold=parser.getboolean("comments", "casefold", fallback=defaults["casefold"]),
+        empty_file=cast(EmptyFilePolicy, empty_file),
+        file_name=parser.get("file", "name", fallback=defaults["file_name"]),
+        trailer=trailer,
+        reachable_from=reachable_from,
+        denylist=denylist,
+        spelling_words=spelling_words,
+    )
+
+
+def find_config(file_path: Union[str, Path]) -> Optional[Path]:
+    """Return the configuration file next to a `.git-blame-ignore-revs` file, if there is one."""
+    candidate = Path(file_path).resolve().parent / CONFIG_FILE_NAME
+    return candidate if candidate.is_file() else None
@@ -0,0 +1,33 @@
+import re
+from typing import Optional, TypedDict
+
+__all__ = (
+    "Denylist",
+    "denied_commit",
+    "denied_hash",
+)
+
+
+class Denylist(TypedDict):
+    commits: list[str]  # Full or abbreviated hashes
+    subjects: list[str]  # Regular expressions searched for in commit subjects
+    authors: list[str]  # Regular expressions searched for in authors, as `Name <email>`
+
+
+def denied_hash(commit_hash: str, denylist: Denylist) -> Optional[str]:
+    """Return why the denylist forbids listing a commit by its hash, or None if it doesn't."""
+    for denied in denylist["commits"]:
+        if commit_hash.startswith(denied):
+            return f"denied commit {denied}"
+    return None
+
+
+def denied_commit(subject: str, author: str, denylist: Denylist) -> Optional[str]:
+    """Return why the denylist forbids listing a commit by its subject or author, if it does."""
+    for pattern in denylist["subjects"]:
+        if re.search(pattern, subject):
+            return f"subject matches {pattern!r}"
+    for pattern in denylist["authors"]:
+        if re.search(pattern, author):
+            return f"author matches {pattern!r}"
+    return None
@@ -0,0 +1,181 @@
+from collections.abc import Iterable
+from difflib import SequenceMatcher
+from pathlib import Path
+from subprocess import CalledProcessError
+from typing import NamedTuple, Optional, TypedDict, Union
+
+from validate_git_blame_ignore_revs.backends import GitBackend, SubprocessGit
+from validate_git_blame_ignore_revs.lib import (
+    HashEntries,
+    LineKind,
+    ValidationOptions,
+    parse_lines,
+    validate_git_blame_ignore_revs,
+)
+
+__all__ = (
+    "FileDiff",
+    "check_added_entries",
+    "compare_entries",
+    "ordering_problems",
+    "read_at_revision",
+)
+
+
+class _Entry(NamedTuple):
+    line_number: int
+    comments: tuple[str, ...]
+
+
+class FileDiff(TypedDict):
+    added: HashEntries  # Line number in the new file -> Hash
+    removed: HashEntries  # Line number in the old file -> Hash
+    recommented: dict[int, tuple[tuple[str, ...], tuple[str, ...]]]  # Old and new comments
+    reordered: HashEntries  # Line number in the new file -> Hash that moved relative to the others
+
+
+def _entries(lines: Iterable[str]) -> dict[str, _Entry]:
+    """Return the line and comments of each hash, keeping the first if one is listed twice."""
+    entries: dict[str, _Entry] = {}
+    for parsed_line in parse_lines(lines):
+        if parsed_line.kind is LineKind.Hash and parsed_line.text not in entries:
+            entries[parsed_line.text] = _Entry(parsed_line.line_number, parsed_line.comments)
+    return entries
+
+
+def compare_entries(old_lines: Iterable[str], new_lines: Iterable[str]) -> FileDiff:
+    """
+    Compares the entries of two versions of a `.git-blame-ignore-revs` file.
+
+    Entries are matched by hash, so moving an entry isn't reported as removing
+    and adding it. Only entries whose order differs from that of the other
+    entries are reported as reordered.
+
+    Args:
+        old_lines (Iterable[str]): The lines of the old version.
+        new_lines (Iterable[str]): The lines of the new version.
+
+    Returns:
+        FileDiff: A dictionary containing the entries added, removed, with changed comments, and moved.
+    """
+    old = _entries(old_lines)
+    new = _entries(new_lines)
+
+    added = {entry.line_number: commit for commit, entry in new.items() if commit not in old}
+    removed = {entry.line_number: commit for commit, entry in old.items() if commit not in new}
+    recommented = {
+        entry.line_number: (old[commit].comments, entry.comments)
+        for commit, entry in new.items()
+        if commit in old and old[commit].comments != entry.comments
+    }
+
+    # Entries outside the longest common ordering are the ones that moved
+    old_order = [commit for commit in old if commit in new]
+    new_order = [commit for commit in new if commit in old]
+    in_order: set[str] = set()
+    for block in SequenceMatcher(None, old_order, new_order, autojunk=False).get_matching_blocks():
+        in_order.update(new_order[block.b : block.b + block.size])
+    reordered = {new[commit].line_number: commit for commit in new_order if commit not in in_order}
+
+    return FileDiff(added=added, removed=removed, recommented=recommented, reordered=reordered)
+
+
+def ordering_problems(
+    old_lines: Iterable[str], new_lines: Iterable[str]
+) -> dict[int, tuple[str, str]]:
+    """
+    Finds the changes to a `.git-blame-ignore-revs` file other than appending entries.
+
+    Existing entries must keep their order and comments. New entries must come
+    after the existing entries of their section, a block of lines separated by
+    blank lines such as the entries for one tool, which includes appending them
+    to the end of the file. Removing entries is allowed.
+
+    Args:
+        old_lines (Iterable[str]): The lines of the old version.
+        new_lines (Iterable[str]): The lines of the new version.
+
+    Returns:
+        dict[int, tuple[str, str]]: A dictionary mapping the line number of each entry in the new version that breaks the policy to its hash and the problem.
+    """
+    new_lines = list(new_lines)
+    changes = compare_entries(old_lines, new_lines)
+
+    hashes: HashEntries = {}
+    # Line number of each entry -> Its section
+    sections: dict[int, int] = {}
+    # Section -> Line number of its last existing entry
+    last_existing: dict[int, int] = {}
+    section = 0
+    for parsed_line in parse_lines(new_lines):
+        if parsed_line.kind is LineKind.Blank:
+            section += 1
+        elif parsed_line.kind is LineKind.Hash:
+            hashes[parsed_line.line_number] = parsed_line.text
+            sections[parsed_line.line_number] = section
+            if parsed_line.line_number not in changes["added"]:
+                last_existing[section] = parsed_line.line_number
+
+    problems: dict[int, tuple[str, str]] = {}
+    for line_number, commit in changes["added"].items():
+        if line_number < last_existing.get(sections[line_number], 0):
+            problems[line_number] = (commit, "inserted before existing entries of its section")
+    for line_number in changes["recommented"]:
+        problems[line_number] = (hashes[line_number], "comments changed")
+    for line_number, commit in changes["reordered"].items():
+        problems[line_number] = (commit, "moved relative to the other existing entries")
+    return dict(sorted(problems.items()))
+
+
+def read_at_revision(
+    file_path: Union[str, Path], rev: str, git: Optional[GitBackend] = None
+) -> list[str]:
+    """
+    Returns the lines of a file as of a revision, such as the base branch of a pull request.
+
+    A file that doesn't exist at the revision yet has no lines.
+
+    Raises:
+        CalledProcessError: If `rev` doesn't name a commit.
+    """
+    file_path = Path(file_path).resolve()
+    if git is None:
+        git = SubprocessGit()
+    git.enter(file_path.parent)
+    git.resolve_commit(rev)
+    try:
+        content = git.object_contents(f"{rev}:./{file_path.name}", file_path.parent)
+    except CalledProcessError:
+        return []
+    # Decoded the way Git's output is everywhere else
+    return content.decode("utf-8", errors="surrogateescape").strip().splitlines()
+
+
+def check_added_entries(
+    file_path: Union[str, Path], added: HashEntries, git: Optional[GitBackend] = None
+) -> dict[int, dict[str, bool]]:
+    """
+    Validates only the given entries of a `.git-blame-ignore-revs` file.
+
+    Args:
+        file_path (Union[str, Path]): Path to the new version of the file.
+        added (HashEntries): The entries to check, such as `FileDiff["added"]`.
+        git (Optional[GitBackend]): How the commits are looked up. Defaults to running `git`.
+
+    Returns:
+        dict[int, dict[str, bool]]: A dictionary mapping the line number of each entry to whether it passes each check, keyed by command-line flag.
+    """
+    result = validate_git_blame_ignore_revs(
+        file_path,
+        ValidationOptions(call_git=True, strict_comments=True, strict_comments_git=True),
+        git=git,
+    )
+    return {
+        line_number: {
+            "--call-git": line_number not in result["missing_commits"]
+            and line_number not in result["non_commit_objects"],
+            "--strict-comments": line_number not in result["strict_comment_errors"],
+            "--strict-comments-git": line_number not in result["comment_diffs"],
+        }
+        for line_number in added
+    }
@@ -0,0 +1,118 @@
+import re
+from pathlib import Path
+from typing import Optional, TypedDict, Union
+
+from validate_git_blame_ignore_revs.backends import GitBackend, SubprocessGit
+from validate_git_blame_ignore_revs.commits import is_reachable
+from validate_git_blame_ignore_revs.git import CommitInfo
+from validate_git_blame_ignore_revs.lib import (
+    ValidationOptions,
+    parse_lines,
+    validate_git_blame_ignore_revs,
+)
+
+__all__ = (
+    "AmbiguousRevisionError",
+    "Explanation",
+    "explain_entry",
+)
+
+
+# Abbreviated hashes shorter than this aren't looked up by Git
+_abbreviated_hash_regex = re.compile(r"^[0-9a-f]{4,39}$")
+
+
+class AmbiguousRevisionError(ValueError):
+    """Raised when an abbreviated hash matches more than one object."""
+
+    def __init__(self, rev: str, candidates: dict[str, str]) -> None:
+        super().__init__(f"{rev} is ambiguous")
+        self.rev = rev
+        self.candidates = candidates  # Full hash -> Description of the object
+
+
+class Explanation(TypedDict):
+    line_number: Optional[int]  # None if the commit isn't listed in the file
+    comment: Optional[str]
+    commit: CommitInfo
+    diffstat: str
+    whitespace_ratio: float
+    is_merge: bool
+    reachable: bool
+    reverted_by: list[str]
+    checks: dict[str, bool]  # Command-line flag -> whether the entry passes it
+
+
+def explain_entry(
+    file_path: Union[str, Path], rev: str, git: Optional[GitBackend] = None
+) -> Explanation:
+    """
+    Collects everything known about one commit listed in a `.git-blame-ignore-revs` file.
+
+    Args:
+        file_path (Union[str, Path]): Path to the `.git-blame-ignore-revs` file.
+        rev (str): Any revision that Git can resolve to the commit, such as a full or abbreviated hash.
+        git (Optional[GitBackend]): How the commit is looked up. Defaults to running `git`.
+
+    Returns:
+        Explanation: A dictionary containing the entry's location and comment, the commit metadata, and the results of each check for that entry.
+
+    Raises:
+        AmbiguousRevisionError: If `rev` is an abbreviated hash that matches more than one object.
+    """
+    file_path = Path(file_path).resolve()
+
+    if git is None:
+        git = SubprocessGit()
+    git.enter(file_path.parent)
+    if _abbreviated_hash_regex.match(rev):
+        candidates = git.disambiguate(rev)
+        if len(candidates) > 1:
+            raise AmbiguousRevisionError(
+                rev, {candidate: git.describe_object(candidate) for candidate in candidates}
+            )
+    commit = git.commit_info(git.resolve_commit(rev))
+
+    result = validate_git_blame_ignore_revs(
+        file_path,
+        ValidationOptions(
+            call_git=True,
+            strict_comments=True,
+            strict_comments_git=True,
+            report_unused_entries=True,
+        ),
+        git=git,
+    )
+
+    line_number = None
+    comment = None
+    checks: dict[str, bool] = {}
+    for candidate, commit_hash in result["valid_hashes"].items():
+        if commit_hash == commit["hash"]:
+            line_number = candidate
+            break
+
+    if line_number is not None:
+        with file_path.open(encoding="utf-8") as lines:
+            for parsed_line in parse_lines(lines):
+                if parsed_line.line_number == line_number:
+                    comment = parsed_line.comment
+                    break
+        checks = {
+            "--call-git": line_number not in result["missing_commits"],
+            "--strict-comments": line_number not in result["strict_comment_errors"],
+            "--strict-comments-git": line_number not in result["comment_diffs"],
+            "--report-unused-entries": line_number not in result["unused_entries"],
+        }
+
+    return Explanation(
+        line_number=line_number,
+        comment=comment,
+        commit=commit,
+        diffstat=git.diffstat(commit["hash"]),
+        whitespace_ratio=git.whitespace_ratio(commit["hash"]),
+        is_merge=len(commit["parents"]) > 1,
+        reachable=is_reachable(commit["hash"], git=git),
+        reverted_by=git.find_reverts(commit["hash"]),
+        checks=checks,
+    )
@@ -0,0 +1,118 @@
+import json
+from pathlib import Path
+from subprocess import CalledProcessError
+from typing import Optional, TypedDict, Union
+
+from validate_git_blame_ignore_revs.backends import GitBackend, SubprocessGit
+from validate_git_blame_ignore_revs.git import CommitInfo
+from validate_git_blame_ignore_revs.lib import LineKind, parse_lines
+
+__all__ = (
+    "EXPORT_FORMATS",
+    "ExportedEntry",
+    "export_entries",
+    "render_export_json",
+    "render_export_markdown",
+)
+
+# Formats the entries can be exported in, the first being the default
+EXPORT_FORMATS = ("markdown", "json")
+
+
+class ExportedEntry(TypedDict):
+    line: int
+    hash: str
+    commit: Optional[CommitInfo]  # None if the hash doesn't name a commit in the repository
+    comment: Optional[str]  # The comment directly above the entry, which describes it
+
+
+def export_entries(
+    file_path: Union[str, Path], git: Optional[GitBackend] = None
+) -> list[ExportedEntry]:
+    """
+    Looks up the commit listed on each line of a `.git-blame-ignore-revs` file, with its comment.
+
+    Args:
+        file_path (Union[str, Path]): Path to the `.git-blame-ignore-revs` file.
+        git (Optional[GitBackend]): How the commits are looked up. Defaults to running `git`.
+
+    Returns:
+        list[ExportedEntry]: The entries in the order the file lists them.
+    """
+    file_path = Path(file_path).resolve()
+    with file_path.open(encoding="utf-8") as lines:
+        hashes = [
+            parsed_line for parsed_line in parse_lines(lines) if parsed_line.kind is LineKind.Hash
+        ]
+
+    if git is None:
+        git = SubprocessGit()
+    git.enter(file_path.parent)
+
+    entries: list[ExportedEntry] = []
+    for parsed_line in hashes:
+        commit: Optional[CommitInfo] = None
+        try:
+            if git.object_type(parsed_line.text) == "commit":
+                commit = git.commit_info(parsed_line.text)
+        except CalledProcessError:
+            pass
+        entries.append(
+            ExportedEntry(
+                line=parsed_line.line_number,
+                hash=parsed_line.text,
+                commit=commit,
+                comment=parsed_line.comment.strip() if parsed_line.comment else None,
+            )
+        )
+    return entries
+
+
+def _markdown_cell(text: str) -> str:
+    # A pipe would end the cell
+    return text.replace("|", "\\|")
+
+
+def render_export_markdown(entries: list[ExportedEntry]) -> str:
+    """
+    Lays out the entries as a Markdown table, such as for contributor documentation.
+
+    Each row gives the hash, the date (without the time) and author of the
+    commit, its subject, and the comment describing the entry. The cells about
+    the commit are empty for hashes that don't name a commit.
+    """
+    lines = ["| Commit | Date | Author | Subject | Comment |", "| --- | --- | --- | --- | --- |"]
+    for entry in entries:
+        commit = entry["commit"]
+        cells = [
+            f"`{entry['hash']}`",
+            commit["author_date"][:10] if commit else "",
+            commit["author_name"] if commit else "",
+            commit["subject"] if commit else "",
+            entry["comment"] or "",
+        ]
+        lines.append(f"| {' | '.join(_markdown_cell(cell) for cell in cells)} |")
+    return "\n".join(lines) + "\n"
+
+
+def render_export_json(entries: list[ExportedEntry]) -> str:
+    """
+    Lays out the entries as a JSON list, such as for a dashboard.
+
+    Each entry is an object with the line number, hash, and comment, and the
+    author, author email, date, and subject of the commit, which are null for
+    hashes that don't name a commit.
+    """
+    data = [
+        {
+            "line": entry["line"],
+            "hash": entry["hash"],
+            "date": entry["commit"]["author_date"] if entry["commit"] else None,
+            "author": entry["commit"]["author_name"] if entry["commit"] else None,
+            "author_email": entry["commit"]["author_email"] if entry["commit"] else None,
+            "subject": entry["commit"]["subject"] if entry["commit"] else None,
+            "comment": entry["comment"],
+        }
+        for entry in entries
+    ]
+    return json.dumps(data, indent=2) + "\n"
@@ -0,0 +1,189 @@
+import io
+import re
+from collections.abc import Iterable
+from pathlib import Path
+from subprocess import CalledProcessError
+from typing import Any, NamedTuple, Optional, Union
+
+from validate_git_blame_ignore_revs.backends import GitBackend, SubprocessGit
+from validate_git_blame_ignore_revs.lib import LineKind, parse_lines
+from validate_git_blame_ignore_revs.suggestions import near_miss, resolve_near_miss
+
+__all__ = (
+    "EDIT_FORMATS",
+    "Fix",
+    "Position",
+    "Range",
+    "TextEdit",
+    "apply_edits",
+    "find_fixes",
+)
+
+# Formats the edits of fixes can be printed in instead of being applied
+EDIT_FORMATS = ("json",)
+
+# A hash in any case, which Git only reads in lowercase
+_mixed_case_hash_regex = re.compile(r"^[0-9a-fA-F]{40}$")
+
+# An abbreviated hash, long enough for Git to look up
+_abbreviated_hash_regex = re.compile(r"^[0-9a-f]{4,39}$")
+
+
+class Position(NamedTuple):
+    line: int  # Zero-based, as in the Language Server Protocol
+    character: int  # Zero-based, in UTF-16 code units as in the Language Server Protocol
+
+
+class Range(NamedTuple):
+    start: Position
+    end: Position  # Exclusive
+
+
+class TextEdit(NamedTuple):
+    range: Range
+    new_text: str
+
+    def to_lsp(self) -> dict[str, Any]:
+        """Return the edit as a Language Server Protocol `TextEdit` object."""
+        return {
+            "range": {
+                "start": self.range.start._asdict(),
+                "end": self.range.end._asdict(),
+            },
+            "newText": self.new_text,
+        }
+
+
+class Fix(NamedTuple):
+    title: str  # Such as "Lowercase hash", shown by editors as a code action
+    line_number: int  # One-based, as in the report
+    edits: tuple[TextEdit, ...]
+
+
+def _utf16_length(text: str) -> int:
+    return len(text.encode("utf-16-le")) // 2
+
+
+def _utf16_index(line: str, character: int) -> int:
+    # The index in the string of a position counted in UTF-16 code units
+    units = 0
+    for index, char in enumerate(line):
+        if units >= character:
+            return index
+        units += 2 if ord(char) > 0xFFFF else 1
+    return len(line)
+
+
+def _split_lines(contents: str) -> list[str]:
+    # Split lines as editors do, rather than at every character `str.splitlines` breaks at
+    return io.StringIO(contents, newline=None).read().split("\n")
+
+
+def _replace_line(index: int, line: str, new_text: str) -> TextEdit:
+    return TextEdit(Range(Position(index, 0), Position(index, _utf16_length(line))), new_text)
+
+
+def find_fixes(
+    file_path: Union[str, Path],
+    contents: Optional[str] = None,
+    call_git: bool = False,
+    git: Optional[GitBackend] = None,
+) -> list[Fix]:
+    """
+    Finds fixes for problems in a `.git-blame-ignore-revs` file that need no judgement.
+
+    - "Lowercase hash": a hash with uppercase letters, which Git can't read.
+    - "Expand abbreviated hash": with `call_git`, an abbreviated hash of
+      exactly one commit.
+    - "Replace with suggested hash": with `call_git`, a near miss such as a
+      hash one character too long, when it names exactly one commit.
+    - "Remove invalid line": any other line that is neither a hash, a
+      comment, nor blank. Lines that look like a hash are left for the
+      report to explain instead.
+    - "Insert comment from commit subject": with `call_git`, an entry without
+      a comment above it.
+
+    Each fix is a list of text edits whose ranges refer to the contents as
+    given, so that editors can apply them as they would the edits of a
+    language server, and the fixes don't overlap.
+
+    Args:
+        file_path (Union[str, Path]): Path to the `.git-blame-ignore-revs` file.
+        contents (Optional[str]): The contents to fix, such as unsaved changes in an editor. Defaults to reading the file.
+        call_git (bool): If True, also makes the fixes that need to look up commits.
+        git (Optional[GitBackend]): How commits are looked up. Defaults to running `git`.
+
+    Returns:
+        list[Fix]: The fixes, in line order.
+    """
+    file_path = Path(file_path).resolve()
+    if contents is None:
+        contents = file_path.read_text(encoding="utf-8")
+    lines = _split_lines(contents)
+    if git is None:
+        git = SubprocessGit()
+    if call_git:
+        git.enter(file_path.parent)
+
+    fixes: list[Fix] = []
+    for parsed_line in parse_lines(lines):
+        index = parsed_line.line_number - 1
+        line = lines[index]
+        if parsed_line.kind is LineKind.Invalid:
+            text = parsed_line.text
+            if _mixed_case_hash_regex.match(text):
+                edit = _replace_line(index, line, text.lower())
+                fixes.append(Fix("Lowercase hash", parsed_line.line_number, (edit,)))
+                continue
+            miss = near_miss(text)
+            if _abbreviated_hash_regex.match(text) or miss is not None:
+                # Likely a mistyped entry, so it's only ever replaced with the commit it names
+                commit = None
+                if call_git:
+                    commit = resolve_near_miss(miss.candidates if miss else (text,), git)
+                if commit is not None:
+                    title = "Replace with suggested hash" if miss else "Expand abbreviated hash"
+                    edit = _replace_line(index, line, commit)
+                    fixes.append(Fix(title, parsed_line.line_number, (edit,)))
+                continue
+            # The line break goes too, so that no blank line is left in its place
+            edit = TextEdit(Range(Position(index, 0), Position(index + 1, 0)), "")
+            fixes.append(Fix("Remove invalid line", parsed_line.line_number, (edit,)))
+        elif parsed_line.kind is LineKind.Hash and call_git and parsed_line.comment is None:
+            try:
+                subject = git.commit_subject(parsed_line.text)
+            except CalledProcessError:
+                subject = None
+            if subject:
+                indent = line[: len(line) - len(line.lstrip())]
+                edit = TextEdit(
+                    Range(Position(index, 0), Position(index, 0)), f"{indent}# {subject}\n"
+                )
+                fixes.append(
+                    Fix("Insert comment from commit subject", parsed_line.line_number, (edit,))
+                )
+    return fixes
+
+
+def apply_edits(contents: str, edits: Iterable[TextEdit]) -> str:
+    """
+    Applies text edits that don't overlap, such as those of `find_fixes`, to contents.
+
+    The lines of the result end with a line feed, whatever line breaks the contents used.
+    """
+    lines = _split_lines(contents)
+    text = "\n".join(lines)
+    # Where each line starts in the text
+    starts = [0]
+    for line in lines:
+        starts.append(starts[-1] + len(line) + 1)
+
+    def offset(position: Position) -> int:
+        if position.line >= len(lines):
+            return len(text)
+        return starts[position.line] + _utf16_index(lines[position.line], position.character)
+
+    # From the end, so that the ranges of the remaining edits still refer to the same text
+    for edit in sorted(edits, key=lambda edit: edit.range.start, reverse=True):
+        text = text[: offset(edit.range.start)] + edit.new_text + text[offset(edit.range.end) :]
+    return text
@@ -0,0 +1,307 @@
+import base64
+import json
+import os
+import re
+from collections.abc import Iterable
+from typing import Any, NamedTuple, Optional
+from urllib.error import HTTPError
+from urllib.parse import quote
+from urllib.request import ProxyHandler, Request, build_opener
+
+from validate_git_blame_ignore_revs.checks import rule_id
+from validate_git_blame_ignore_revs.forge_api import ForgeClient
+from validate_git_blame_ignore_revs.report import FileReport, Severity
+
+__all__ = (
+    "BITBUCKET_HOST",
+    "ForgeRepository",
+    "code_insights",
+    "commit_exists",
+    "commit_url",
+    "file_contents",
+    "find_pull_request",
+    "parse_remote_url",
+    "publish_code_insights",
+    "pull_request_contains",
+)
+
+BITBUCKET_HOST = "bitbucket.org"
+
+# Bitbucket Pipelines authenticates requests to the API made through this proxy, over plain HTTP
+_BITBUCKET_PIPELINES_PROXY = "http://localhost:29418"
+
+# The most annotations Bitbucket accepts in one request
+_ANNOTATIONS_PER_REQUEST = 100
+
+# scp-like (git@host:owner/name.git) and URL (https://host/owner/name.git) remotes
+_remote_url_regex = re.compile(
+    r"^(?:[\w.+-]+://(?:[^@/]+@)?|[^@/]+@)?(?P<host>[^/:]+)(?::\d+)?[:/]"
+    r"(?P<owner>[^/]+)/(?P<name>[^/]+?)(?:\.git)?/?$"
+)
+
+# Links to pull requests on GitHub, merge requests on GitLab, and pull requests on Bitbucket
+_pull_request_url_regex = re.compile(
+    r"https?://\S+?/(?:pull|-/merge_requests|pull-requests)/(?P<number>\d+)"
+)
+
+# Short references such as #123 (GitHub) or !123 (GitLab)
+_pull_request_reference_regex = re.compile(r"(?<![\w&])[#!](?P<number>\d+)\b")
+
+
+class ForgeRepository(NamedTuple):
+    host: str
+    owner: str
+    name: str
+
+
+def parse_remote_url(url: str) -> Optional[ForgeRepository]:
+    """Return the host, owner, and name of the repository a remote URL points to."""
+    match = _remote_url_regex.match(url.strip())
+    if not match:
+        return None
+    return ForgeRepository(match.group("host"), match.group("owner"), match.group("name"))
+
+
+def commit_url(repository: ForgeRepository, commit_hash: str) -> str:
+    """Return the URL of the page showing a commit on the forge that hosts the repository.
+
+    Self-hosted forges are recognized by their host name, such as `gitlab.example.com`.
+    Other hosts are assumed to lay out URLs like GitHub, as GitHub Enterprise,
+    Gitea, and Forgejo do.
+    """
+    base = f"https://{repository.host}/{repository.owner}/{repository.name}"
+    if "gitlab" in repository.host:
+        return f"{base}/-/commit/{commit_hash}"
+    if "bitbucket" in repository.host:
+        return f"{base}/commits/{commit_hash}"
+    return f"{base}/commit/{commit_hash}"
+
+
+def find_pull_request(comments: Iterable[str]) -> Optional[int]:
+    """Return the number of the first pull request linked or referenced in the comments."""
+    for comment in comments:
+        match = _pull_request_url_regex.search(comment) or _pull_request_reference_regex.search(
+            comment
+        )
+        if match:
+            return int(match.group("number"))
+    return None
+
+
+def file_contents(
+    repository: ForgeRepository,
+    path: str,
+    ref: Optional[str] = None,
+    client: Optional[ForgeClient] = None,
+) -> Optional[bytes]:
+    """
+    Fetches a file from a repository on GitHub or GitLab with its API, without cloning it.
+
+    Args:
+        repository (ForgeRepository): The repository the file is in.
+        path (str): The path of the file from the top level of the repository.
+        ref (Optional[str]): The branch, tag, or commit to read the file from. Defaults to the default branch.
+        client (Optional[ForgeClient]): The client for the forge, shared between requests. Defaults to a new one for the host of the repository.
+
+    Returns:
+        Optional[bytes]: The contents of the file, or None if the repository or the file doesn't exist.
+
+    Raises:
+        OSError: If the API cannot be reached.
+        UnknownForgeError: If no client is given and the host isn't a known forge.
+    """
+    if client is None:
+        client = ForgeClient(repository.host)
+    project = client.repository_path(repository.owner, repository.name)
+    try:
+        if client.kind == "gitlab":
+            # GitLab has no default for the ref of a file
+            if ref is None:
+                ref = client.get(project)["default_branch"]
+            endpoint = f"{project}/repository/files/{quote(path, safe='')}?ref={quote(ref)}"
+        else:
+            endpoint = f"{project}/contents/{quote(path)}"
+            if ref is not None:
+                endpoint += f"?ref={quote(ref)}"
+        data = client.get(endpoint)
+    except HTTPError as e:
+        if e.code == 404:
+            return None
+        raise
+    # A list for directories
+    if not isinstance(data, dict) or data.get("encoding") != "base64":
+        return None
+    return base64.b64decode(data["content"])
+
+
+def commit_exists(
+    repository: ForgeRepository, commit_hash: str, client: Optional[ForgeClient] = None
+) -> bool:
+    """
+    Checks with the API of GitHub or GitLab whether a repository has a commit, without cloning it.
+
+    Raises:
+        OSError: If the API cannot be reached.
+        UnknownForgeError: If no client is given and the host isn't a known forge.
+    """
+    if client is None:
+        client = ForgeClient(repository.host)
+    project = client.repository_path(repository.owner, repository.name)
+    endpoint = "repository/commits" if client.kind == "gitlab" else "commits"
+    try:
+        client.get(f"{project}/{endpoint}/{commit_hash}")
+    except HTTPError as e:
+        # GitHub answers 422 for hashes that aren't commits
+        if e.code in (404, 422):
+            return False
+        raise
+    return True
+
+
+def pull_request_contains(
+    repository: ForgeRepository,
+    number: int,
+    commit_hash: str,
+    client: Optional[ForgeClient] = None,
+) -> Optional[bool]:
+    """
+    Checks with the API of GitHub or GitLab whether a pull or merge request contains a commit.
+
+    A commit is contained in a pull request if it is one of its commits or the
+    commit it was merged, squashed, or rebased as.
+
+    Args:
+        repository (ForgeRepository): The repository the pull request is in.
+        number (int): The number of the pull request.
+        commit_hash (str): The commit to look for.
+        client (Optional[ForgeClient]): The client for the forge, shared between requests. Defaults to a new one for the host of the repository.
+
+    Returns:
+        Optional[bool]: Whether the pull request contains the commit, or None if the pull request doesn't exist.
+
+    Raises:
+        OSError: If the API cannot be reached.
+        UnknownForgeError: If no client is given and the host isn't a known forge.
+    """
+    if client is None:
+        client = ForgeClient(repository.host)
+    path = client.repository_path(repository.owner, repository.name)
+    if client.kind == "gitlab":
+        path = f"{path}/merge_requests/{number}"
+        merged_as = ("merge_commit_sha", "squash_commit_sha")
+        commit_key = "id"
+    else:
+        path = f"{path}/pulls/{number}"
+        merged_as = ("merge_commit_sha",)
+        commit_key = "sha"
+    try:
+        pull_request = client.get(path)
+    except HTTPError as e:
+        if e.code == 404:
+            return None
+        raise
+    if any(pull_request.get(key) == commit_hash for key in merged_as):
+        return True
+    commits = client.get_all(f"{path}/commits")
+    return any(commit.get(commit_key) == commit_hash for commit in commits)
+
+
+def code_insights(file: FileReport, path: str) -> tuple[dict[str, Any], list[dict[str, Any]]]:
+    """
+    Lays out the findings on a file as a Bitbucket Code Insights report and its annotations.
+
+    Args:
+        file (FileReport): The findings on the file.
+        path (str): The path of the file relative to the top level of the repository.
+
+    Returns:
+        tuple[dict[str, Any], list[dict[str, Any]]]: The report, and an annotation for each finding.
+    """
+    severities = [section.severity for section in file.sections for _ in section.findings]
+    errors = severities.count(Severity.Error)
+    warnings = severities.count(Severity.Warning)
+    report = {
+        "title": "validate-git-blame-ignore-revs",
+        "details": f"Found {errors} error(s) and {warnings} warning(s) in {path}.",
+        "report_type": "TEST",
+        "reporter": "validate-git-blame-ignore-revs",
+        "result": "FAILED" if file.exit_code else "PASSED",
+        "data": [
+            {"title": "Errors", "type": "NUMBER", "value": errors},
+            {"title": "Warnings", "type": "NUMBER", "value": warnings},
+        ],
+    }
+    annotations: list[dict[str, Any]] = []
+    for section in file.sections:
+        is_error = section.severity is Severity.Error
+        is_info = section.severity is Severity.Info
+        for finding in section.findings:
+            heading = f"{finding.label}: {finding.text}" if finding.text else finding.label
+            annotation: dict[str, Any] = {
+                "external_id": f"{rule_id(section.title)}-{len(annotations) + 1}",
+                "annotation_type": "BUG" if is_error else "CODE_SMELL",
+                "severity": "HIGH" if is_error else "LOW" if is_info else "MEDIUM",
+                "summary": f"{section.title}: {heading}",
+                "path": path,
+            }
+            if finding.details:
+                annotation["details"] = "\n".join(finding.details)
+            if finding.line_number is not None:
+                annotation["line"] = finding.line_number
+            if finding.url is not None:
+                annotation["link"] = finding.url
+            annotations.append(annotation)
+    return report, annotations
+
+
+def _send_bitbucket_json(method: str, url: str, body: Any) -> None:
+    """
+    Send a request to the Bitbucket API, authenticating with `BITBUCKET_TOKEN` if it is set.
+
+    Without a token, requests in Bitbucket Pipelines go through the proxy that
+    authenticates them.
+    """
+    headers = {"Accept": "application/json", "Content-Type": "application/json"}
+    token = os.environ.get("BITBUCKET_TOKEN")
+    opener = build_opener()
+    if token:
+        headers["Authorization"] = f"Bearer {token}"
+    elif "BITBUCKET_BUILD_NUMBER" in os.environ:
+        url = url.replace("https://", "http://", 1)
+        opener = build_opener(ProxyHandler({"http": _BITBUCKET_PIPELINES_PROXY}))
+    data = json.dumps(body).encode("utf-8")
+    with opener.open(Request(url, data=data, headers=headers, method=method), timeout=30):
+        pass
+
+
+def publish_code_insights(
+    repository: ForgeRepository,
+    commit_hash: str,
+    report_id: str,
+    report: dict[str, Any],
+    annotations: list[dict[str, Any]],
+) -> None:
+    """
+    Creates or replaces a Code Insights report on a commit in a Bitbucket Cloud repository.
+
+    Replacing a report also deletes its annotations, so a report never mixes
+    the findings of two runs.
+
+    Args:
+        repository (ForgeRepository): The repository on Bitbucket Cloud.
+        commit_hash (str): The commit the report is about.
+        report_id (str): Identifies the report among the reports on the commit.
+        report (dict[str, Any]): The report, as returned by `code_insights`.
+        annotations (list[dict[str, Any]]): Its annotations, as returned by `code_insights`.
+
+    Raises:
+        OSError: If the API cannot be reached or rejects the report.
+    """
+    url = (
+        f"https://api.{BITBUCKET_HOST}/2.0/repositories/{repository.owner}/{repository.name}"
+        f"/commit/{commit_hash}/reports/{report_id}"
+    )
+    _send_bitbucket_json("PUT", url, report)
+    for start in range(0, len(annotations), _ANNOTATIONS_PER_REQUEST):
+        chunk = annotations[start : start + _ANNOTATIONS_PER_REQUEST]
+        _send_bitbucket_json("POST", f"{url}/annotations", chunk)
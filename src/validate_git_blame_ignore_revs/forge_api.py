@@ -0,0 +1,412 @@
+import hashlib
+import json
+import os
+import re
+import sys
+import time
+from email.message import Message
+from pathlib import Path
+from typing import Any, Optional
+from urllib.error import HTTPError, URLError
+from urllib.parse import quote, urlsplit
+from urllib.request import Request, urlopen
+
+from validate_git_blame_ignore_revs.retry import RetryError, RetryPolicy, default_policy
+
+__all__ = (
+    "ForgeClient",
+    "ResponseCache",
+    "UnknownForgeError",
+    "discover_token",
+    "forge_kind",
+)
+
+# A link to the next page of results in the Link header of GitHub and GitLab responses
+_next_link_regex = re.compile(r'<(?P<url>[^>]+)>\s*;\s*rel="?next"?')
+
+# The public instances of each forge, which need no configuration
+_PUBLIC_FORGES = {"github.com": "github", "gitlab.com": "gitlab"}
+
+
+class UnknownForgeError(ValueError):
+    """A host isn't known to serve the GitHub or GitLab API, so no requests are made to it."""
+
+    def __init__(self, host: str) -> None:
+        super().__init__(host)
+        self.host = host
+
+    def __str__(self) -> str:
+        return (
+            f"{self.host} isn't a known GitHub or GitLab host; log in to it with gh or glab, "
+            "or name it in GH_HOST or GITLAB_HOST"
+        )
+
+
+def _config_dir(variable: str, name: str) -> Path:
+    # Where gh and glab keep their configuration, which these variables move
+    if variable in os.environ:
+        return Path(os.environ[variable])
+    if sys.platform == "win32" and "APPDATA" in os.environ:
+        return Path(os.environ["APPDATA"]) / name
+    config_home = os.environ.get("XDG_CONFIG_HOME") or Path.home() / ".config"
+    return Path(config_home) / name
+
+
+def _gh_config_dir() -> Path:
+    return _config_dir("GH_CONFIG_DIR", "gh")
+
+
+def _glab_config_dir() -> Path:
+    return _config_dir("GLAB_CONFIG_DIR", "glab-cli")
+
+
+def _read_config(path: Path) -> str:
+    try:
+        return path.read_text(encoding="utf-8")
+    except (OSError, UnicodeDecodeError):
+        return ""
+
+
+def _is_host_key(stripped: str, host: str) -> bool:
+    # The key a host's settings are under, such as `github.example.com:`
+    return stripped.endswith(":") and stripped.rstrip(":").strip("'\"") == host
+
+
+def _has_host(text: str, host: str) -> bool:
+    """Return whether a gh or glab configuration file, which is YAML, has settings for a host."""
+    return any(_is_host_key(line.strip(), host) for line in text.splitlines())
+
+
+def _host_setting(text: str, host: str, key: str) -> Optional[str]:
+    """Return a setting of a host in a gh or glab configuration file, which is YAML."""
+    # The indentation of the host's settings, once the host has been found
+    host_indent: Optional[int] = None
+    for line in text.splitlines():
+        stripped = line.strip()
+        if not stripped or stripped.startswith("#"):
+            continue
+        indent = len(line) - len(line.lstrip())
+        if host_indent is None:
+            if _is_host_key(stripped, host):
+                host_indent = indent
+        elif indent <= host_indent:
+            return None
+        else:
+            name, _, value = stripped.partition(":")
+            if name == key and value.strip():
+                return value.strip().strip("'\"")
+    return None
+
+
+def _environment_host(variable: str) -> Optional[str]:
+    # GH_HOST is a host name, while GITLAB_HOST and GITHUB_SERVER_URL may be URLs
+    value = os.environ.get(variable)
+    if not value:
+        return None
+    return urlsplit(value).hostname if "://" in value else value
+
+
+def forge_kind(host: str) -> Optional[str]:
+    """Return whether a host serves the GitHub or the GitLab API, or None if it isn't known to.
+
+    Besides github.com and gitlab.com, these are the self-hosted instances the
+    user configured: those gh or glab are logged in to, the ones `GH_HOST` and
+    `GITLAB_HOST` name, and the server a GitHub Actions workflow runs on,
+    `GITHUB_SERVER_URL`. Other hosts aren't guessed at from their name, so that
+    a token is never sent to a host that only looks like a forge.
+    """
+    if host in _PUBLIC_FORGES:
+        return _PUBLIC_FORGES[host]
+    github_hosts = {_environment_host("GH_HOST"), _environment_host("GITHUB_SERVER_URL")}
+    if host in github_hosts or _has_host(_read_config(_gh_config_dir() / "hosts.yml"), host):
+        return "github"
+    gitlab_config = _read_config(_glab_config_dir() / "config.yml")
+    if host == _environment_host("GITLAB_HOST") or _has_host(gitlab_config, host):
+        return "gitlab"
+    return None
+
+
+def discover_token(host: str) -> Optional[str]:
+    """
+    Finds a token for the API of a forge, as its command-line tool would.
+
+    For GitHub, the token is `GH_TOKEN` or `GITHUB_TOKEN` on github.com, and
+    `GH_ENTERPRISE_TOKEN`, `GITHUB_ENTERPRISE_TOKEN`, or `GITHUB_TOKEN` on other
+    hosts, or otherwise the one gh saved for the host in its `hosts.yml`. For
+    GitLab, it is `GITLAB_TOKEN`, `GITLAB_ACCESS_TOKEN`, or `OAUTH_TOKEN`, or
+    otherwise the one glab saved for the host in its `config.yml`. Tokens that
+    gh keeps in the system keyring aren't found. Hosts `forge_kind` doesn't
+    know get no token at all.
+
+    Args:
+        host (str): The host of the forge, such as `github.com`.
+
+    Returns:
+        Optional[str]: The token, or None to make unauthenticated requests.
+    """
+    kind = forge_kind(host)
+    if kind is None:
+        return None
+    if kind == "gitlab":
+        variables = ["GITLAB_TOKEN", "GITLAB_ACCESS_TOKEN", "OAUTH_TOKEN"]
+        config_path = _glab_config_dir() / "config.yml"
+        key = "token"
+    else:
+        if host == "github.com":
+            variables = ["GH_TOKEN", "GITHUB_TOKEN"]
+        else:
+            variables = ["GH_ENTERPRISE_TOKEN", "GITHUB_ENTERPRISE_TOKEN", "GITHUB_TOKEN"]
+        config_path = _gh_config_dir() / "hosts.yml"
+        key = "oauth_token"
+    for variable in variables:
+        if os.environ.get(variable):
+            return os.environ[variable]
+    return _host_setting(_read_config(config_path), host, key)
+
+
+def _open(request: Request) -> tuple[Any, Message]:
+    """Send a request, returning the decoded JSON body and the headers of the response."""
+    with urlopen(request, timeout=30) as response:
+        return json.load(response), response.headers
+
+
+def _rate_limit_wait(error: HTTPError) -> Optional[float]:
+    """Return how long to wait before retrying a request the API refused for the rate limit."""
+    headers = error.headers
+    if headers is None or error.code not in (403, 429):
+        return None
+    retry_after = headers.get("Retry-After")
+    if retry_after is not None and retry_after.isdigit():
+        return float(retry_after)
+    # GitHub and GitLab give when the limit resets, in seconds since the epoch
+    reset = headers.get("X-RateLimit-Reset") or headers.get("RateLimit-Reset")
+    remaining = headers.get("X-RateLimit-Remaining") or headers.get("RateLimit-Remaining")
+    if reset is not None and reset.isdigit() and (remaining == "0" or error.code == 429):
+        return max(float(reset) - time.time(), 0.0) + 1
+    # A 403 for any other reason, such as a missing permission
+    return None if error.code == 403 else 60.0
+
+
+def _token_fingerprint(token: Optional[str]) -> str:
+    """Return what identifies a token in the keys of cached responses, without revealing it."""
+    if not token:
+        return ""
+    return hashlib.sha256(f"validate-git-blame-ignore-revs\0{token}".encode("utf-8")).hexdigest()
+
+
+class ResponseCache:
+    """
+    Keeps the responses of forge APIs on disk, so later runs don't make the same requests.
+
+    Responses are reused for `ttl` seconds, and a 404 for `not_found_ttl`
+    seconds at most, since what didn't exist, such as a pull request, may be
+    created soon after. Each response is kept in its own file, keyed by URL
+    and by a fingerprint of the token it was requested with, so what one
+    token may see is never served to a request with another token or none.
+    The token itself isn't kept.
+    """
+
+    def __init__(self, directory: Path, ttl: float = 86400, not_found_ttl: float = 600) -> None:
+        self.directory = directory
+        self.ttl = ttl
+        self.not_found_ttl = min(not_found_ttl, ttl)
+
+    def _path(self, url: str, fingerprint: str) -> Path:
+        key = f"{fingerprint}\0{url}"
+        return self.directory / f"{hashlib.sha256(key.encode('utf-8')).hexdigest()}.json"
+
+    def get(
+        self, url: str, token: Optional[str] = None
+    ) -> Optional[tuple[int, Any, Optional[str]]]:
+        """Return the status, body, and Link header of the response to a URL, if still fresh."""
+        fingerprint = _token_fingerprint(token)
+        try:
+            cached = json.loads(self._path(url, fingerprint).read_text(encoding="utf-8"))
+            if cached["url"] != url or cached["token"] != fingerprint:
+                return None
+            ttl = self.not_found_ttl if cached["status"] == 404 else self.ttl
+            if not 0 <= time.time() - cached["time"] < ttl:
+                return None
+            return cached["status"], cached["data"], cached["link"]
+        except (OSError, ValueError, KeyError, TypeError):
+            return None
+
+    def put(
+        self,
+        url: str,
+        status: int,
+        data: Any,
+        link: Optional[str],
+        token: Optional[str] = None,
+    ) -> None:
+        """Keep the response to a URL, requested with `token` if any."""
+        fingerprint = _token_fingerprint(token)
+        entry = {
+            "url": url,
+            "token": fingerprint,
+            "time": time.time(),
+            "status": status,
+            "data": data,
+            "link": link,
+        }
+        try:
+            self.directory.mkdir(parents=True, exist_ok=True)
+            self._path(url, fingerprint).write_text(json.dumps(entry), encoding="utf-8")
+        except OSError:
+            # Caching is best effort
+            pass
+
+    def __repr__(self) -> str:
+        # Also identifies the cache in the key of cached results
+        return f"ResponseCache({str(self.directory)!r}, {self.ttl!r}, {self.not_found_ttl!r})"
+
+
+class ForgeClient:
+    """
+    Makes requests to the REST API of a GitHub or GitLab instance.
+
+    Requests are authenticated with the token found by `discover_token`
+    unless one is given, and only those to the API itself, not to other
+    hosts that the links to further pages may point to. Requests refused for
+    the rate limit are retried once it resets, unless that is more than
+    `max_wait` seconds away, and requests that fail for server or network
+    errors are retried as `retry_policy` says, which defaults to the policy
+    of the run. A request that fails after being retried raises
+    `RetryError`. With a `cache`, responses it still has are reused instead
+    of being requested again. A host `forge_kind` doesn't know raises
+    `UnknownForgeError` rather than being guessed at.
+    """
+
+    def __init__(
+        self,
+        host: str,
+        token: Optional[str] = None,
+        *,
+        retry_policy: Optional[RetryPolicy] = None,
+        max_wait: float = 60.0,
+        cache: Optional[ResponseCache] = None,
+    ) -> None:
+        kind = forge_kind(host)
+        if kind is None:
+            raise UnknownForgeError(host)
+        self.host = host
+        self.kind = kind
+        self.token = token if token is not None else discover_token(host)
+        self.retry_policy = retry_policy if retry_policy is not None else default_policy()
+        self.max_wait = max_wait
+        self.cache = cache
+        if self.kind == "gitlab":
+            self.api_url = f"https://{host}/api/v4"
+        elif host == "github.com":
+            self.api_url = "https://api.github.com"
+        else:
+            # GitHub Enterprise Server
+            self.api_url = f"https://{host}/api/v3"
+
+    def repository_path(self, owner: str, name: str) -> str:
+        """Return the path of the API endpoint of a repository, such as `repos/owner/name`."""
+        if self.kind == "gitlab":
+            # GitLab identifies projects by their full path, encoded as one path segment
+            return f"projects/{quote(f'{owner}/{name}', safe='')}"
+        return f"repos/{owner}/{name}"
+
+    def _token(self, url: str) -> Optional[str]:
+        """Return the token to send with a request to a URL, if any."""
+        # Only the API gets the token, whichever host a link to the next page points to
+        return self.token if urlsplit(url)[:2] == urlsplit(self.api_url)[:2] else None
+
+    def _headers(self, url: str) -> dict[str, str]:
+        token = self._token(url)
+        if self.kind == "gitlab":
+            headers = {"Accept": "application/json"}
+            if token:
+                headers["PRIVATE-TOKEN"] = token
+        else:
+            headers = {"Accept": "application/vnd.github+json"}
+            if token:
+                headers["Authorization"] = f"Bearer {token}"
+        return headers
+
+    def _request(self, url: str) -> tuple[Any, Message]:
+        token = self._token(url)
+        if self.cache is not None:
+            cached = self.cache.get(url, token)
+            if cached is not None:
+                status, data, link = cached
+                if status == 404:
+                    raise HTTPError(url, 404, "Not Found", Message(), None)
+                headers = Message()
+                if link is not None:
+                    headers["Link"] = link
+                return data, headers
+        try:
+            data, headers = self._fetch(url)
+        except HTTPError as e:
+            if e.code == 404 and self.cache is not None:
+                self.cache.put(url, 404, None, None, token)
+            raise
+        if self.cache is not None:
+            self.cache.put(url, 200, data, headers.get("Link"), token)
+        return data, headers
+
+    def _retry_wait(self, error: URLError, attempt: int) -> Optional[float]:
+        """Return how long to wait before retrying a request that failed, or None to give up."""
+        if attempt >= self.retry_policy.retries:
+            return None
+        if not isinstance(error, HTTPError):
+            # The API couldn't be reached
+            return self.retry_policy.delay(attempt)
+        wait = _rate_limit_wait(error)
+        if wait is None and error.code >= 500:
+            wait = self.retry_policy.delay(attempt)
+        return wait if wait is not None and wait <= self.max_wait else None
+
+    def _fetch(self, url: str) -> tuple[Any, Message]:
+        start = time.monotonic()
+        attempt = 0
+        while True:
+            try:
+                return _open(Request(url, headers=self._headers(url)))
+            except URLError as e:
+                error = e
+            wait = self._retry_wait(error, attempt)
+            if wait is None:
+                if attempt:
+                    raise RetryError(
+                        f"GET {url}", attempt + 1, time.monotonic() - start, error
+                    ) from error
+                raise error
+            time.sleep(wait)
+            attempt += 1
+
+    def get(self, path: str) -> Any:
+        """
+        Fetches an API endpoint, such as `repos/owner/name/pulls/1`.
+
+        Raises:
+            HTTPError: If the API refuses the request, such as with 404 for what doesn't exist.
+            OSError: If the API cannot be reached.
+        """
+        data, _ = self._request(f"{self.api_url}/{path}")
+        return data
+
+    def get_all(self, path: str) -> list[Any]:
+        """
+        Fetches every page of an API endpoint that lists items, such as `repos/owner/name/pulls/1/commits`.
+
+        Raises:
+            HTTPError: If the API refuses the request, such as with 404 for what doesn't exist.
+            OSError: If the API cannot be reached.
+        """
+        separator = "&" if "?" in path else "?"
+        url: Optional[str] = f"{self.api_url}/{path}{separator}per_page=100"
+        items: list[Any] = []
+        while url is not None:
+            data, headers = self._request(url)
+            items.extend(data)
+            match = _next_link_regex.search(headers.get("Link") or "")
+            url = match.group("url") if match else None
+        return items
+
+    def __repr__(self) -> str:
+        return f"ForgeClient({self.host!r})"
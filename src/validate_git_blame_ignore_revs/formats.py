@@ -0,0 +1,445 @@
+import json
+import xml.etree.ElementTree as ET
+from collections.abc import Callable
+from pathlib import PurePath
+from typing import Any
+
+from validate_git_blame_ignore_revs.checks import rule_id
+from validate_git_blame_ignore_revs.report import FileReport, Finding, Section, Severity
+
+__all__ = (
+    "FORMATS",
+    "PORCELAIN_VERSION",
+    "RENDERERS",
+    "render_azure",
+    "render_compact",
+    "render_json",
+    "render_junit",
+    "render_markdown",
+    "render_porcelain",
+    "render_sarif",
+)
+
+_TOOL_NAME = "validate-git-blame-ignore-revs"
+
+# The version of the porcelain format, which only changes when a change would break parsers
+PORCELAIN_VERSION = 1
+
+# The SARIF level of the results of each severity
+_SARIF_LEVELS = {Severity.Error: "error", Severity.Warning: "warning", Severity.Info: "note"}
+
+
+def _rule_id(section: Section) -> str:
+    """Return a stable identifier for a check, such as `missing-commits`."""
+    return rule_id(section.title)
+
+
+def _message(finding: Finding) -> str:
+    heading = f"{finding.label}: {finding.text}" if finding.text else finding.label
+    return "\n".join([heading, *finding.details])
+
+
+def _uri(file_path: str) -> str:
+    path = PurePath(file_path)
+    return path.as_uri() if path.is_absolute() else path.as_posix()
+
+
+def _exit_code(files: list[FileReport]) -> int:
+    code = 0
+    for file in files:
+        code |= file.exit_code
+    return code
+
+
+def render_json(files: list[FileReport]) -> str:
+    """
+    Lays out the findings of each check on each file as a JSON document.
+
+    Args:
+        files (list[FileReport]): The findings on each file, in the order the files were given.
+
+    Returns:
+        str: The JSON document, ending with a newline.
+    """
+    severities = [
+        section.severity for file in files for section in file.sections for _ in section.findings
+    ]
+    document = {
+        "exit_code": _exit_code(files),
+        "summary": {
+            "files": len(files),
+            "files_with_problems": sum(bool(file.exit_code) for file in files),
+            "errors": severities.count(Severity.Error),
+            "warnings": severities.count(Severity.Warning),
+            "info": severities.count(Severity.Info),
+        },
+        "files": [
+            {
+                "file": file.path,
+                "exit_code": file.exit_code,
+                "checks": [
+                    {
+                        "id": _rule_id(section),
+                        "title": section.title,
+                        "severity": section.severity.value,
+                        "findings": [
+                            {
+                                "line": finding.line_number,
+                                "commit": finding.commit,
+                                "label": finding.label,
+                                "text": finding.text,
+                                "details": list(finding.details),
+                                "url": finding.url,
+                                "reason": finding.reason,
+                            }
+                            for finding in section.findings
+                        ],
+                    }
+                    for section in file.sections
+                ],
+                "notes": file.notes,
+            }
+            for file in files
+        ],
+    }
+    return json.dumps(document, indent=2) + "\n"
+
+
+def render_sarif(files: list[FileReport]) -> str:
+    """
+    Lays out the findings of each check on each file as a SARIF 2.1.0 log, for code scanning tools.
+
+    Each check is a rule, and each finding a result located at the line of the
+    file it is about. Findings about commits that aren't in the file are
+    located at the file as a whole.
+
+    Args:
+        files (list[FileReport]): The findings on each file, in the order the files were given.
+
+    Returns:
+        str: The SARIF log, ending with a newline.
+    """
+    # Checks with the same title are the same rule, whichever file they were run on
+    rules: dict[str, str] = {}
+    results: list[dict[str, Any]] = []
+    notifications: list[dict[str, Any]] = []
+    for index, file in enumerate(files):
+        artifact = {"uri": _uri(file.path), "index": index}
+        for section in file.sections:
+            rules.setdefault(_rule_id(section), section.title)
+            for finding in section.findings:
+                location: dict[str, Any] = {"artifactLocation": artifact}
+                if finding.line_number is not None:
+                    location["region"] = {"startLine": finding.line_number}
+                result: dict[str, Any] = {
+                    "ruleId": _rule_id(section),
+                    "level": _SARIF_LEVELS[section.severity],
+                    "message": {"text": _message(finding)},
+                    "locations": [{"physicalLocation": location}],
+                }
+                if finding.reason is not None:
+                    result["properties"] = {"reason": finding.reason}
+                results.append(result)
+        notifications.extend(
+            {
+                "message": {"text": note},
+                "locations": [{"physicalLocation": {"artifactLocation": artifact}}],
+            }
+            for note in file.notes
+        )
+
+    log = {
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [
+            {
+                "tool": {
+                    "driver": {
+                        "name": _TOOL_NAME,
+                        "rules": [
+                            {"id": rule_id, "shortDescription": {"text": title}}
+                            for rule_id, title in rules.items()
+                        ],
+                    }
+                },
+                "invocations": [
+                    {
+                        # A file has no checks when it couldn't be read at all
+                        "executionSuccessful": all(file.sections for file in files),
+                        "exitCode": _exit_code(files),
+                        "toolExecutionNotifications": notifications,
+                    }
+                ],
+                "artifacts": [{"location": {"uri": _uri(file.path)}} for file in files],
+                "results": results,
+            }
+        ],
+    }
+    return json.dumps(log, indent=2) + "\n"
+
+
+def render_junit(files: list[FileReport]) -> str:
+    """
+    Lays out the findings of each check on each file as a JUnit XML report, for CI test dashboards.
+
+    Each file is a test suite, and each check a test case that fails when it
+    has findings. The findings of info-level checks are printed as output of
+    their test case instead, since they don't fail the run.
+
+    Args:
+        files (list[FileReport]): The findings on each file, in the order the files were given.
+
+    Returns:
+        str: The XML document, ending with a newline.
+    """
+    testsuites = ET.Element("testsuites", name=_TOOL_NAME)
+    totals = {"tests": 0, "failures": 0, "errors": 0}
+    for file in files:
+        # A file that couldn't be checked at all is an error rather than a pass
+        counts = {
+            "tests": len(file.sections) or 1,
+            "failures": sum(
+                bool(section.findings) and section.severity is not Severity.Info
+                for section in file.sections
+            ),
+            "errors": int(not file.sections),
+        }
+        testsuite = ET.SubElement(testsuites, "testsuite", name=file.path)
+        for key, count in counts.items():
+            testsuite.set(key, str(count))
+            totals[key] += count
+
+        if not file.sections:
+            testcase = ET.SubElement(testsuite, "testcase", classname=file.path, name="Validation")
+            error = ET.SubElement(testcase, "error", message=f"Exited with code {file.exit_code}")
+            error.text = "\n".join(file.notes)
+        for section in file.sections:
+            testcase = ET.SubElement(testsuite, "testcase", classname=file.path, name=section.title)
+            if section.findings and section.severity is Severity.Info:
+                output = ET.SubElement(testcase, "system-out")
+                output.text = "\n".join(_message(finding) for finding in section.findings)
+            elif section.findings:
+                failure = ET.SubElement(
+                    testcase,
+                    "failure",
+                    message=f"{section.title} ({len(section.findings)})",
+                    type=section.severity.value,
+                )
+                failure.text = "\n".join(_message(finding) for finding in section.findings)
+        if file.notes:
+            ET.SubElement(testsuite, "system-out").text = "\n".join(file.notes)
+
+    for key, total in totals.items():
+        testsuites.set(key, str(total))
+    return ET.tostring(testsuites, encoding="unicode", xml_declaration=True) + "\n"
+
+
+def _azure_escape(text: str, is_property: bool = False) -> str:
+    # The escapes Azure Pipelines decodes in logging commands
+    text = text.replace("%", "%AZP25").replace("\r", "%0D").replace("\n", "%0A")
+    if is_property:
+        text = text.replace(";", "%3B").replace("]", "%5D")
+    return text
+
+
+def render_azure(files: list[FileReport]) -> str:
+    """
+    Lays out the findings of each check on each file as Azure Pipelines logging commands.
+
+    Each finding is a `##vso[task.logissue]` command, which Azure Pipelines
+    shows as an error or warning of the build, located at the line of the
+    file it is about. Info-level findings, which Azure Pipelines has no issue
+    type for, and notes about the files are printed as they are.
+
+    Args:
+        files (list[FileReport]): The findings on each file, in the order the files were given.
+
+    Returns:
+        str: The logging commands, each ending with a newline.
+    """
+    lines: list[str] = []
+    for file in files:
+        for section in file.sections:
+            for finding in section.findings:
+                if section.severity is Severity.Info:
+                    location = file.path
+                    if finding.line_number is not None:
+                        location += f":{finding.line_number}"
+                    lines.append(f"{location}: {_message(finding)}")
+                    continue
+                properties = {
+                    "type": section.severity.value,
+                    "sourcepath": file.path,
+                    "code": _rule_id(section),
+                }
+                if finding.line_number is not None:
+                    properties["linenumber"] = str(finding.line_number)
+                    properties["columnnumber"] = "1"
+                command = ";".join(
+                    f"{key}={_azure_escape(value, is_property=True)}"
+                    for key, value in properties.items()
+                )
+                lines.append(f"##vso[task.logissue {command}]{_azure_escape(_message(finding))}")
+        lines.extend(file.notes)
+    return "".join(f"{line}\n" for line in lines)
+
+
+def _markdown_cell(text: str) -> str:
+    # A pipe would end the cell, and a newline the row
+    return text.replace("|", "\\|").replace("\n", "<br>")
+
+
+def render_markdown(files: list[FileReport]) -> str:
+    """
+    Lays out the findings of each check on each file as Markdown, such as for a CI job summary.
+
+    Each check with findings is a table of them, with links to the commits
+    they are about when the repository is on a forge. Checks without
+    findings are only listed.
+
+    Args:
+        files (list[FileReport]): The findings on each file, in the order the files were given.
+
+    Returns:
+        str: The Markdown document, ending with a newline.
+    """
+    lines: list[str] = []
+    for file in files:
+        lines += [f"### `{file.path}`", ""]
+        if not file.sections:
+            lines += [f"Could not be checked (exit code {file.exit_code}).", ""]
+        for section in file.sections:
+            if not section.findings:
+                continue
+            count = len(section.findings)
+            # "info" is the same in the plural
+            plural = "" if count == 1 or section.severity is Severity.Info else "s"
+            lines += [
+                f"#### {section.title} ({count} {section.severity.value}{plural})",
+                "",
+                "| Entry | Problem |",
+                "| --- | --- |",
+            ]
+            for finding in section.findings:
+                entry = _markdown_cell(finding.label)
+                if finding.url is not None:
+                    entry = f"[{entry}]({finding.url})"
+                problem = _markdown_cell("\n".join([finding.text, *finding.details]).strip())
+                lines.append(f"| {entry} | {problem} |")
+            lines.append("")
+        passed = [section.title for section in file.sections if not section.findings]
+        if passed:
+            lines += [f"Passed: {', '.join(passed)}.", ""]
+        lines += [f"> {note}" for note in file.notes]
+        if file.notes:
+            lines.append("")
+    return "\n".join(lines).rstrip("\n") + "\n"
+
+
+def _porcelain_field(value: object) -> str:
+    # Backslashes, tabs, and newlines are escaped so that every record is one line of fields
+    text = str(value)
+    return text.replace("\\", "\\\\").replace("\t", "\\t").replace("\n", "\\n")
+
+
+def _porcelain_record(*fields: object) -> str:
+    return "\t".join(_porcelain_field(field) for field in fields) + "\n"
+
+
+def render_porcelain(files: list[FileReport]) -> str:
+    """
+    Lays out the findings of each check on each file as tab-separated records, one per line.
+
+    The format is stable across releases, like the porcelain formats of Git:
+    records are only ever added, and fields only appended to the end of a
+    record, so scripts should ignore records they don't know and fields past
+    those they use. The first record gives the version of the format::
+
+        version 1
+        file    <path>  <exit code>
+        finding <severity>  <check id>  <line or ->  <commit or line>  <label>  <text>
+        detail  <text>
+        note    <text>
+        exit    <exit code>
+
+    Each `file` record is followed by the findings on it, each with its
+    `detail` records, and then its notes. Backslashes, tabs, and newlines in
+    fields are escaped as `\\\\`, `\\t`, and `\\n`.
+
+    Args:
+        files (list[FileReport]): The findings on each file, in the order the files were given.
+
+    Returns:
+        str: The records, each ending with a newline.
+    """
+    records = [_porcelain_record("version", PORCELAIN_VERSION)]
+    for file in files:
+        records.append(_porcelain_record("file", file.path, file.exit_code))
+        for section in file.sections:
+            for finding in section.findings:
+                records.append(
+                    _porcelain_record(
+                        "finding",
+                        section.severity.value,
+                        _rule_id(section),
+                        "-" if finding.line_number is None else finding.line_number,
+                        finding.commit,
+                        finding.label,
+                        finding.text,
+                    )
+                )
+                records.extend(_porcelain_record("detail", detail) for detail in finding.details)
+        records.extend(_porcelain_record("note", note) for note in file.notes)
+    records.append(_porcelain_record("exit", _exit_code(files)))
+    return "".join(records)
+
+
+def render_compact(files: list[FileReport]) -> str:
+    """
+    Lays out the findings of each check on each file as diagnostics like those of compilers.
+
+    Each finding is a line `path:line:col: severity[check-id]: message`, which
+    editors such as Vim (with its quickfix list) and Emacs (with
+    compilation-mode) and many CI log parsers pick up. Its details follow as
+    `note` diagnostics at the same location. Findings about commits that
+    aren't in the file, and notes about the file, have no line and column.
+
+    Args:
+        files (list[FileReport]): The findings on each file, in the order the files were given.
+
+    Returns:
+        str: The diagnostics, each ending with a newline.
+    """
+    lines: list[str] = []
+    for file in files:
+        for section in file.sections:
+            for finding in section.findings:
+                if finding.line_number is None:
+                    location = file.path
+                    message = f"{finding.label}: {finding.text}" if finding.text else finding.label
+                else:
+                    # Entries always start at the start of their line
+                    location = f"{file.path}:{finding.line_number}:1"
+                    message = finding.text or finding.label
+                diagnostic = f"{section.severity.value}[{_rule_id(section)}]"
+                lines.append(f"{location}: {diagnostic}: {message}")
+                lines.extend(f"{location}: note: {detail}" for detail in finding.details)
+        lines.extend(f"{file.path}: note: {note}" for note in file.notes)
+    return "".join(f"{line}\n" for line in lines)
+
+
+Renderer = Callable[[list[FileReport]], str]
+
+# The machine-readable formats. The human-readable report is laid out by `render_report`
+RENDERERS: dict[str, Renderer] = {
+    "json": render_json,
+    "sarif": render_sarif,
+    "junit": render_junit,
+    "compact": render_compact,
+    "azure": render_azure,
+    "markdown": render_markdown,
+    "porcelain": render_porcelain,
+}
+
+# Report formats, the first being the human-readable report printed by default
+FORMATS = ("human", *RENDERERS)
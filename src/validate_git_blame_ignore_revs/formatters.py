@@ -0,0 +1,78 @@
+import re
+from collections.abc import Iterable
+from typing import Optional
+
+__all__ = (
+    "FORMATTERS",
+    "detect_formatter",
+    "mentions_tool",
+    "section_formatter",
+    "section_header",
+)
+
+# Formatter name -> File extensions it formats
+FORMATTERS: dict[str, tuple[str, ...]] = {
+    "black": (".py", ".pyi"),
+    "prettier": (
+        ".css",
+        ".html",
+        ".js",
+        ".json",
+        ".jsx",
+        ".md",
+        ".scss",
+        ".ts",
+        ".tsx",
+        ".yaml",
+        ".yml",
+    ),
+    "rustfmt": (".rs",),
+    "clang-format": (".c", ".cc", ".cpp", ".cxx", ".h", ".hh", ".hpp"),
+    "gofmt": (".go",),
+}
+
+# A section header, such as `## black` or `# --- prettier ---`
+_section_header_regexes = (
+    re.compile(r"^#{2,}\s*(?P<name>[^#\s].*?)\s*#*$"),
+    re.compile(r"^#\s*[-=*~]{3,}\s*(?P<name>.*?[^-=*~\s])\s*[-=*~]*$"),
+)
+
+
+def mentions_tool(tool: str, text: str) -> bool:
+    """Return whether text names a tool as a word of its own, ignoring case."""
+    return re.search(rf"(?<![\w-]){re.escape(tool)}(?![\w-])", text, re.IGNORECASE) is not None
+
+
+def detect_formatter(
+    subject: str, paths: Iterable[str], tools: Iterable[str] = tuple(FORMATTERS)
+) -> Optional[str]:
+    """
+    Guess which formatter made a commit, or return None if it doesn't look like any of them.
+
+    A commit is attributed to the first tool its subject names, or otherwise
+    to the first whose extensions every changed path has.
+    """
+    tools = list(tools)
+    for tool in tools:
+        if mentions_tool(tool, subject):
+            return tool
+    paths = list(paths)
+    for tool in tools:
+        extensions = FORMATTERS.get(tool, ())
+        if paths and extensions and all(path.endswith(extensions) for path in paths):
+            return tool
+    return None
+
+
+def section_header(comment: str) -> Optional[str]:
+    """Return the name of the section a comment line starts, such as `## black`, if it does."""
+    for regex in _section_header_regexes:
+        match = regex.match(comment)
+        if match:
+            return match.group("name")
+    return None
+
+
+def section_formatter(name: str, tools: Iterable[str] = tuple(FORMATTERS)) -> Optional[str]:
+    """Return the formatter a section is for, going by the tool its name mentions, if any."""
+    return next((tool for tool in tools if mentions_tool(tool, name)), None)
@@ -0,0 +1,885 @@
+import fnmatch
+import logging
+import os
+import re
+import shlex
+import time
+from collections.abc import Iterable, Iterator
+from contextlib import contextmanager
+from pathlib import Path
+from subprocess import CalledProcessError, CompletedProcess, run
+from typing import Any, NamedTuple, Optional, TextIO, TypedDict, Union
+
+from validate_git_blame_ignore_revs.paths import WINDOWS, find_executable, strip_long_path_prefix
+from validate_git_blame_ignore_revs.retry import attempts_summary, default_policy
+
+__all__ = (
+    "CommitInfo",
+    "GitNotFoundError",
+    "HistoryRange",
+    "blame_attribution",
+    "blame_contents",
+    "changed_paths",
+    "commit_info",
+    "commit_paths",
+    "commit_time",
+    "config_values",
+    "describe_object",
+    "diffstat",
+    "disambiguate",
+    "fetch_remote_head",
+    "file_history",
+    "find_reverts",
+    "first_parent_diff",
+    "git_executable",
+    "head_commit",
+    "ignore_rule",
+    "is_ancestor",
+    "is_committed",
+    "is_shallow",
+    "is_tracked",
+    "is_valid_trailer",
+    "log_changed_lines",
+    "log_messages",
+    "log_subjects",
+    "matching_refs",
+    "object_contents",
+    "object_type",
+    "patch_id",
+    "pin_repository_environment",
+    "prefetch_diffs",
+    "promisor_remote",
+    "ref_targets",
+    "remote_head",
+    "remote_url",
+    "rename_counts",
+    "replaced_objects",
+    "repository_path",
+    "resolve_commit",
+    "run_command",
+    "shortstat",
+    "tag_name",
+    "toplevel",
+    "trace_git",
+    "trailer_commits",
+    "unreferenced_commits",
+    "whitespace_ratio",
+)
+
+_logger = logging.getLogger(__name__)
+
+# The number of characters of output shown for each command by `trace_git`
+_TRACE_OUTPUT_LENGTH = 200
+
+# A porcelain header line: the commit hash, the original line number, the
+# final line number, and optionally the number of lines in the group.
+_porcelain_header_regex = re.compile(r"^([0-9a-f]{40}) \d+ \d+(?: \d+)?$")
+
+# A rule printed by `git check-ignore --verbose`, as `source:line:pattern`. On Windows, the source
+# can start with a drive letter, such as for a global excludes file in `C:/Users/name/.config`.
+_ignore_rule_regex = re.compile(r"^(?P<source>(?:[A-Za-z]:)?[^:]*):(?P<line>\d*):(?P<pattern>.*)$")
+
+# The extensions Windows tries for a program when `PATHEXT` isn't set
+_DEFAULT_PATHEXT = os.pathsep.join([".COM", ".EXE", ".BAT", ".CMD"])
+
+# Errors of Git that a later attempt may not run into: those of the network, of network file
+# systems, and of another Git process holding a lock
+_transient_error_regex = re.compile(
+    r"Could not resolve host|Temporary failure in name resolution|"
+    r"Connection (?:timed out|reset|refused)|Operation timed out|"
+    r"The remote end hung up unexpectedly|early EOF|RPC failed|"
+    r"The requested URL returned error: 5\d\d|"
+    r"Resource temporarily unavailable|Stale file handle|Input/output error|"
+    r"Interrupted system call|Unable to create '[^']*\.lock': File exists",
+    re.IGNORECASE,
+)
+
+# A trailer key, such as `Blame-Ignore`, optionally followed by a colon and the value
+_trailer_regex = re.compile(r"^[A-Za-z0-9][A-Za-z0-9-]*(?:\s*:.*)?$")
+
+
+class GitNotFoundError(RuntimeError):
+    """Raised when the `git` executable cannot be run."""
+
+
+class HistoryRange(NamedTuple):
+    """Limits on the commits scanned by `git log`, such as to skip the era before the ignore file was adopted."""
+
+    since: Optional[str] = None  # Any date `git log --since` accepts
+    until: Optional[str] = None
+    max_count: Optional[int] = None
+
+    def log_args(self) -> list[str]:
+        """Return the `git log` arguments that apply the limits."""
+        args: list[str] = []
+        if self.since is not None:
+            args.append(f"--since={self.since}")
+        if self.until is not None:
+            args.append(f"--until={self.until}")
+        if self.max_count is not None:
+            args.append(f"--max-count={self.max_count}")
+        return args
+
+
+@contextmanager
+def trace_git(stream: TextIO) -> Iterator[None]:
+    """Log each Git command run in the block to `stream`, such as to debug missing commits.
+
+    Each line shows the command, its exit status, how long it took, and the
+    start of its output, or of its error output if it failed.
+    """
+    handler = logging.StreamHandler(stream)
+    handler.setFormatter(logging.Formatter("git trace: %(message)s"))
+    level = _logger.level
+    _logger.addHandler(handler)
+    _logger.setLevel(logging.DEBUG)
+    try:
+        yield
+    finally:
+        _logger.removeHandler(handler)
+        _logger.setLevel(level)
+
+
+def _git_not_found(command: list[str], e: OSError) -> GitNotFoundError:
+    return GitNotFoundError(
+        f"Could not run {command[0]}: {e.strerror}. Install Git and make sure it is on "
+        "the PATH, or pass --no-git to run only the checks that don't need it."
+    )
+
+
+def git_executable() -> str:
+    """
+    Return the program to run for `git`.
+
+    On Windows, it is looked up with `find_executable` in `PATH` and `PATHEXT`,
+    so that a `git.exe` in the repository being checked is never run instead,
+    and a `git.cmd` wrapper is found. Elsewhere, it is `git`, which is looked up
+    in `PATH` when run.
+    """
+    if not WINDOWS:
+        return "git"
+    search_path = os.environ.get("PATH", "")
+    extensions = os.environ.get("PATHEXT") or _DEFAULT_PATHEXT
+    # Left for running it to fail with the error Windows gives
+    return find_executable("git", search_path, extensions) or "git"
+
+
+def _directory(repository: Union[str, Path]) -> str:
+    """Return a directory as an argument of `git -C`, which doesn't accept long-path prefixes."""
+    return strip_long_path_prefix(repository)
+
+
+def _log(command: list[str], result: CompletedProcess[Any], seconds: float) -> None:
+    output = result.stdout if result.returncode == 0 else result.stderr
+    if isinstance(output, bytes):
+        output = output.decode("utf-8", errors="replace")
+    output = output.strip().replace("\n", "\\n")
+    if len(output) > _TRACE_OUTPUT_LENGTH:
+        output = f"{output[:_TRACE_OUTPUT_LENGTH]}... ({len(output)} characters)"
+    _logger.debug(
+        "%s -> exit %d in %.3fs: %s", shlex.join(command), result.returncode, seconds, output
+    )
+
+
+def _is_transient(result: CompletedProcess[Any]) -> bool:
+    """Return whether a command failed for what is likely a passing problem, going by its errors."""
+    error = result.stderr
+    if isinstance(error, bytes):
+        error = error.decode("utf-8", errors="replace")
+    return bool(error and _transient_error_regex.search(error))
+
+
+def _run(command: list[str], **kwargs: Any) -> CompletedProcess[Any]:
+    """Run a command like `subprocess.run` with `check=True`, logging it when Git is traced.
+
+    A command that fails for what is likely a passing problem, such as a
+    network error or a file a network file system couldn't read for a moment,
+    is run again as the retry policy of the run says. If it still fails, how
+    often it was run is added to the end of its error output.
+    """
+    program = [git_executable(), *command[1:]] if command[0] == "git" else command
+    policy = default_policy()
+    first_start = time.monotonic()
+    attempt = 0
+    while True:
+        start = time.monotonic()
+        try:
+            result = run(program, capture_output=True, **kwargs)
+        except OSError as e:
+            raise _git_not_found(command, e) from e
+        if _logger.isEnabledFor(logging.DEBUG):
+            _log(command, result, time.monotonic() - start)
+        if result.returncode == 0 or attempt >= policy.retries or not _is_transient(result):
+            break
+        time.sleep(policy.delay(attempt))
+        attempt += 1
+    if result.returncode != 0 and attempt:
+        elapsed = time.monotonic() - first_start
+        summary = f" (gave up after {attempts_summary(attempt + 1, elapsed)})"
+        stderr = result.stderr.rstrip()
+        result.stderr = stderr + (summary.encode() if isinstance(stderr, bytes) else summary)
+    result.check_returncode()
+    return result
+
+
+def run_command(command: list[str], input: Optional[str] = None) -> str:
+    """Run a Git command, optionally writing `input` to it, and return its output.
+
+    Bytes that aren't valid UTF-8, such as in file names, are decoded the same
+    way Python decodes file names, so paths in the output can be passed back to
+    Git or the file system unchanged.
+    """
+    result = _run(command, input=input, encoding="utf-8", errors="surrogateescape")
+    return str(result.stdout).strip()
+
+
+def object_contents(name: str, repository: Union[str, Path]) -> bytes:
+    """Return the contents of an object, such as `HEAD:./file` or `:./file` for the index, unchanged.
+
+    Raises:
+        CalledProcessError: If there is no such object.
+        GitNotFoundError: If the `git` executable cannot be run.
+    """
+    content: bytes = _run(["git", "-C", _directory(repository), "show", name]).stdout
+    return content
+
+
+def log_subjects(*extra_args: str) -> dict[str, str]:
+    """Return the subject of each commit selected by `git log` with the given arguments.
+
+    Commits are keyed by their full hash, newest first.
+    """
+    subjects: dict[str, str] = {}
+    log = run_command(["git", "log", "--pretty=format:%H %s", *extra_args])
+    for commit_entry in log.splitlines():
+        # Skip empty or malformed lines
+        if not commit_entry.strip():
+            continue
+        parts = commit_entry.split(" ", 1)
+        if len(parts) != 2:
+            continue
+        commit_hash, commit_message = parts
+        subjects[commit_hash] = commit_message
+    return subjects
+
+
+def is_valid_trailer(trailer: str) -> bool:
+    """Return whether `trailer` is a key, such as `Blame-Ignore`, optionally with a value."""
+    return bool(_trailer_regex.match(trailer.strip()))
+
+
+def trailer_commits(trailer: str, *extra_args: str) -> dict[str, str]:
+    """Return the subject of each commit selected by `git log` that carries a trailer.
+
+    `trailer` is a key, such as `Blame-Ignore`, which matches whatever the
+    value is, or a key and a value, such as `Blame-Ignore: true`. Both are
+    compared ignoring case. Commits are keyed by their full hash, newest first.
+    """
+    key, _, value = trailer.partition(":")
+    value = value.strip().casefold()
+    log = run_command(
+        [
+            "git",
+            "log",
+            "-z",
+            f"--pretty=format:%H %s%x1f%(trailers:key={key.strip()},valueonly)",
+            *extra_args,
+        ]
+    )
+    subjects: dict[str, str] = {}
+    for record in log.split("\0"):
+        entry, _, values = record.partition("\x1f")
+        commit_hash, _, subject = entry.partition(" ")
+        values = [found.strip().casefold() for found in values.splitlines() if found.strip()]
+        if values and (not value or value in values):
+            subjects[commit_hash] = subject
+    return subjects
+
+
+def object_type(rev: str) -> str:
+    """Return the type of the object `rev` names: commit, tag, tree, or blob."""
+    return run_command(["git", "cat-file", "-t", rev])
+
+
+def remote_url(remote: str = "origin", repository: Union[str, Path] = ".") -> str:
+    """Return the URL of a remote of the repository that contains `repository`."""
+    return run_command(["git", "-C", _directory(repository), "remote", "get-url", remote])
+
+
+def matching_refs(pattern: str) -> list[str]:
+    """Return the short names of the branches, remote-tracking branches, and tags matching a glob.
+
+    A pattern without wildcards, such as `main`, matches the ref of that name.
+    """
+    refs = ["refs/heads", "refs/remotes", "refs/tags"]
+    names = run_command(["git", "for-each-ref", "--format=%(refname:short)", *refs]).splitlines()
+    return [name for name in names if fnmatch.fnmatchcase(name, pattern)]
+
+
+def head_commit(repository: Union[str, Path] = ".") -> str:
+    """Return the hash of the commit checked out in the repository that contains `repository`."""
+    return run_command(["git", "-C", _directory(repository), "rev-parse", "--verify", "HEAD"])
+
+
+def repository_path(path: Path) -> str:
+    """Return the path of a file relative to the top level of its repository, with slashes."""
+    # Unlike a path, the prefix is empty at the top level and otherwise ends with a slash
+    prefix = run_command(["git", "-C", _directory(path.parent), "rev-parse", "--show-prefix"])
+    return f"{prefix}{path.name}"
+
+
+def is_shallow() -> bool:
+    """Return whether the repository is a shallow clone, missing the history before some commits."""
+    return run_command(["git", "rev-parse", "--is-shallow-repository"]) == "true"
+
+
+def remote_head(remote: str, repository: Union[str, Path] = ".") -> Optional[str]:
+    """Return the default branch of a remote, such as `origin/main`, or None if it isn't known.
+
+    The default branch is recorded as `refs/remotes/<remote>/HEAD` when cloning,
+    or by `git remote set-head`.
+    """
+    try:
+        return run_command(
+            [
+                "git",
+                "-C",
+                _directory(repository),
+                "symbolic-ref",
+                "--quiet",
+                "--short",
+                f"refs/remotes/{remote}/HEAD",
+            ]
+        )
+    except CalledProcessError:
+        return None
+
+
+def fetch_remote_head(remote: str, repository: Union[str, Path] = ".") -> str:
+    """Fetch the commit the default branch of a remote points to now, returning its hash.
+
+    Only that branch is fetched, and no remote-tracking branch is updated.
+
+    Raises:
+        CalledProcessError: If the remote can't be reached.
+    """
+    directory = _directory(repository)
+    run_command(["git", "-C", directory, "fetch", "--quiet", "--no-tags", remote, "HEAD"])
+    return run_command(["git", "-C", directory, "rev-parse", "--verify", "FETCH_HEAD"])
+
+
+def is_ancestor(commit_hash: str, rev: str) -> bool:
+    """Return whether a commit is reachable from `rev`."""
+    try:
+        run_command(["git", "merge-base", "--is-ancestor", commit_hash, rev])
+    except CalledProcessError as e:
+        # Exits with 1 when the commit isn't an ancestor, and otherwise for errors
+        if e.returncode == 1:
+            return False
+        raise
+    return True
+
+
+def unreferenced_commits(commit_hashes: Iterable[str]) -> dict[str, bool]:
+    """Return the commits no branch, tag, or other ref reaches, each with whether the reflog does.
+
+    Amending, rebasing, or squashing a commit leaves the original behind like
+    this. `git gc` deletes such commits once nothing reaches them, including
+    the reflog, whose entries expire. Commits that don't exist are skipped.
+    """
+    listed = list(dict.fromkeys(commit_hashes))
+    if not listed:
+        return {}
+    # The listed commits and their ancestors, without those reachable from any ref or HEAD
+    output = run_command(
+        ["git", "rev-list", "--ignore-missing", "--stdin", "--not", "--all"],
+        input="".join(f"{commit_hash}\n" for commit_hash in listed),
+    )
+    unreferenced = set(output.splitlines())
+    found = [commit_hash for commit_hash in listed if commit_hash in unreferenced]
+    if not found:
+        return {}
+    in_reflog = set(run_command(["git", "rev-list", "--reflog", "--not", "--all"]).splitlines())
+    return {commit_hash: commit_hash in in_reflog for commit_hash in found}
+
+
+def replaced_objects() -> dict[str, str]:
+    """Return the replacement for each object replaced with `git replace`.
+
+    Most Git commands, including `git show` and `git blame`, silently use the
+    replacement instead. When `GIT_NO_REPLACE_OBJECTS` is set, they don't, and
+    nothing is returned.
+    """
+    if os.environ.get("GIT_NO_REPLACE_OBJECTS"):
+        return {}
+    replacements: dict[str, str] = {}
+    refs = run_command(
+        ["git", "for-each-ref", "--format=%(refname) %(objectname)", "refs/replace/"]
+    )
+    for line in refs.splitlines():
+        refname, _, replacement = line.partition(" ")
+        replacements[refname[len("refs/replace/") :]] = replacement
+    return replacements
+
+
+def tag_name(rev: str) -> str:
+    """Return the name recorded in the annotated tag object `rev`."""
+    for line in run_command(["git", "cat-file", "tag", rev]).splitlines():
+        # The header ends at the first blank line, before the tag message
+        if not line:
+            break
+        if line.startswith("tag "):
+            return line[len("tag ") :]
+    return ""
+
+
+def describe_object(rev: str) -> str:
+    """Return the type of the object `rev` names, followed by the subject of commits or the name of tags."""
+    kind = object_type(rev)
+    if kind == "commit":
+        return f"commit {run_command(['git', 'log', '-1', '--pretty=format:%s', rev])}"
+    if kind == "tag":
+        return f"tag {tag_name(rev)}"
+    return kind
+
+
+def patch_id(commit_hash: str) -> Optional[str]:
+    """Return the stable patch ID of a commit's changes, or None if it changes nothing.
+
+    Commits that make the same change, such as cherry-picks, have the same patch ID.
+    """
+    diff = run_command(["git", "diff-tree", "--patch", "--root", commit_hash])
+    output = run_command(["git", "patch-id", "--stable"], input=diff + "\n")
+    return output.split(" ", 1)[0] or None
+
+
+def disambiguate(prefix: str) -> list[str]:
+    """Return the full hashes of every object whose hash starts with `prefix`.
+
+    The prefix must be at least four hexadecimal characters long.
+    """
+    return run_command(["git", "rev-parse", f"--disambiguate={prefix}"]).splitlines()
+
+
+def is_tracked(path: Union[str, Path]) -> bool:
+    """Return whether `path` is in the index."""
+    try:
+        run_command(["git", "ls-files", "--error-unmatch", "--", str(path)])
+    except CalledProcessError:
+        return False
+    return True
+
+
+def is_committed(path: Union[str, Path]) -> bool:
+    """Return whether `path` is in the commit checked out at HEAD."""
+    try:
+        return bool(run_command(["git", "ls-tree", "--name-only", "HEAD", "--", str(path)]))
+    except CalledProcessError:
+        # There are no commits yet
+        return False
+
+
+def ignore_rule(path: Union[str, Path]) -> Optional[str]:
+    """Return the `.gitignore` rule matching `path` as `source:line:pattern`, if any.
+
+    Rules are checked even if the path is tracked.
+    """
+    try:
+        output = run_command(["git", "check-ignore", "--verbose", "--no-index", "--", str(path)])
+    except CalledProcessError:
+        # Exits with 1 when no rule matches
+        return None
+    rule, _, _ = output.partition("\t")
+    match = _ignore_rule_regex.match(rule)
+    # Negated patterns (`!pattern`) match paths that are not ignored
+    return None if match is not None and match.group("pattern").startswith("!") else rule
+
+
+def config_values(key: str, repository: Union[str, Path]) -> list[str]:
+    """Return every value of a configuration variable as seen from `repository`, or [] if it isn't set."""
+    try:
+        output = run_command(["git", "-C", _directory(repository), "config", "--get-all", key])
+    except CalledProcessError:
+        # `git config` fails when the variable isn't set
+        return []
+    return output.splitlines()
+
+
+def promisor_remote(repository: Union[str, Path] = ".") -> Optional[str]:
+    """Return the remote a partial clone fetches missing objects from, or None for a full clone."""
+    try:
+        output = run_command(
+            [
+                "git",
+                "-C",
+                _directory(repository),
+                "config",
+                "--type=bool",
+                "--get-regexp",
+                r"^remote\..*\.promisor$",
+            ]
+        )
+    except CalledProcessError:
+        # `git config` fails when no variable matches
+        return None
+    for line in output.splitlines():
+        key, _, value = line.partition(" ")
+        if value == "true":
+            return key[len("remote.") : -len(".promisor")]
+    return None
+
+
+def prefetch_diffs(commit_hashes: list[str]) -> None:
+    """Make sure the objects needed to diff each commit against its first parent are present.
+
+    In a partial clone, Git fetches the objects a commit's diff needs in one
+    request rather than one at a time. Commits that don't exist are skipped.
+    """
+    run_command(
+        [
+            "git",
+            "log",
+            "--stdin",
+            "--no-walk=unsorted",
+            "--ignore-missing",
+            "--pretty=format:",
+            "--shortstat",
+            "--diff-merges=first-parent",
+        ],
+        input="".join(f"{commit_hash}\n" for commit_hash in commit_hashes),
+    )
+
+
+# Variables naming paths that Git resolves relative to the directory it runs in
+_PATH_VARIABLES = (
+    "GIT_DIR",
+    "GIT_WORK_TREE",
+    "GIT_INDEX_FILE",
+    "GIT_OBJECT_DIRECTORY",
+    "GIT_COMMON_DIR",
+)
+
+
+def pin_repository_environment() -> None:
+    """Make the repository that Git's environment variables select independent of the directory.
+
+    The checks run Git from the directory of each file, so the relative paths
+    that Git exports to hooks, such as `GIT_DIR=.git`, are made absolute. With
+    `GIT_DIR` but not `GIT_WORK_TREE`, Git takes the directory it runs in as the
+    top level of the working tree unless `core.worktree` says otherwise, so the
+    top level as it is now is pinned in `GIT_WORK_TREE`.
+    """
+    for name in _PATH_VARIABLES:
+        value = os.environ.get(name)
+        if value and not os.path.isabs(value):
+            os.environ[name] = os.path.abspath(value)
+    if "GIT_DIR" in os.environ and "GIT_WORK_TREE" not in os.environ:
+        try:
+            os.environ["GIT_WORK_TREE"] = toplevel()
+        except (CalledProcessError, GitNotFoundError):
+            # A bare repository, a working directory outside the working tree, or no Git at all
+            pass
+
+
+def toplevel() -> str:
+    """Return the top level of the repository containing the working directory."""
+    return run_command(["git", "rev-parse", "--show-toplevel"])
+
+
+def changed_paths(commit_hash: str) -> list[str]:
+    """Return the paths touched by a commit that still exist at HEAD.
+
+    Paths are relative to the top level of the repository.
+    """
+    # NUL-terminated output keeps Git from quoting unusual file names
+    paths = run_command(
+        ["git", "show", "-z", "--pretty=format:", "--name-only", "--no-renames", commit_hash]
+    ).split("\0")
+    paths = [path for path in paths if path]
+    if not paths:
+        return []
+    tracked = run_command(["git", "ls-tree", "-r", "-z", "--name-only", "HEAD", "--", *paths])
+    return [path for path in tracked.split("\0") if path]
+
+
+def rename_counts(commit_hash: str) -> tuple[int, int]:
+    """Return how many files a commit renamed and how many it changed in all.
+
+    Merge commits are compared against their first parent.
+    """
+    fields = run_command(
+        [
+            "git",
+            "show",
+            "-z",
+            "--pretty=format:",
+            "--name-status",
+            "--find-renames",
+            "--diff-merges=first-parent",
+            commit_hash,
+        ]
+    ).split("\0")
+    renamed = changed = 0
+    i = 0
+    while i < len(fields) and fields[i]:
+        status = fields[i]
+        changed += 1
+        if status.startswith("R"):
+            renamed += 1
+        # Renames and copies are followed by two paths, everything else by one
+        i += 3 if status.startswith(("R", "C")) else 2
+    return renamed, changed
+
+
+def blame_attribution(
+    path: Union[str, Path],
+    ignore_revs: Iterable[str] = (),
+    ignore_revs_file: Optional[Union[str, Path]] = None,
+) -> list[str]:
+    """Return the commit each line of `path` at HEAD is attributed to.
+
+    Only the given revisions are ignored; any `blame.ignoreRevsFile` from the
+    Git configuration is not applied.
+    """
+    # An empty file name clears the revisions read from the configuration
+    command = ["git", "blame", "--porcelain", "--ignore-revs-file="]
+    if ignore_revs_file is not None:
+        command.extend(["--ignore-revs-file", str(ignore_revs_file)])
+    for rev in ignore_revs:
+        command.extend(["--ignore-rev", rev])
+    command.extend(["HEAD", "--", str(path)])
+    return _porcelain_attribution(command)
+
+
+def blame_contents(path: Union[str, Path], contents: str) -> list[str]:
+    """Return the commit each line of `contents`, a version of `path`, is attributed to.
+
+    Lines that aren't in HEAD are attributed to the null hash of forty zeros. No
+    revisions are ignored.
+    """
+    command = ["git", "blame", "--porcelain", "--ignore-revs-file=", "--contents=-"]
+    return _porcelain_attribution([*command, "--", str(path)], input=contents)
+
+
+def _porcelain_attribution(command: list[str], input: Optional[str] = None) -> list[str]:
+    """Return the commit of each line in the output of a `git blame --porcelain` command."""
+    # Not stripped like `run_command`, which would drop a last line of only whitespace
+    output = _run(command, input=input, encoding="utf-8", errors="surrogateescape").stdout
+    attribution: list[str] = []
+    current_commit = ""
+    for line in str(output).splitlines():
+        if line.startswith("\t"):
+            attribution.append(current_commit)
+            continue
+        match = _porcelain_header_regex.match(line)
+        if match:
+            current_commit = match.group(1)
+    return attribution
+
+
+def commit_time(commit_hash: str) -> int:
+    """Return when a commit was committed, in seconds since the epoch.
+
+    Rewriting a commit, such as by rebasing it, changes when it was committed
+    but not when it was authored.
+    """
+    return int(run_command(["git", "show", "--quiet", "--pretty=format:%ct", commit_hash]))
+
+
+class CommitInfo(TypedDict):
+    hash: str
+    author_name: str
+    author_email: str
+    author_date: str  # ISO 8601
+    parents: list[str]
+    subject: str
+
+
+def commit_info(rev: str) -> CommitInfo:
+    """Resolve a revision and return the metadata of the commit it names."""
+    fields = run_command(
+        ["git", "show", "--quiet", "--pretty=format:%H%x00%an%x00%ae%x00%aI%x00%P%x00%s", rev]
+    ).split("\0")
+    commit_hash, author_name, author_email, author_date, parents, subject = fields
+    return CommitInfo(
+        hash=commit_hash,
+        author_name=author_name,
+        author_email=author_email,
+        author_date=author_date,
+        parents=parents.split(),
+        subject=subject,
+    )
+
+
+def resolve_commit(rev: str) -> str:
+    """Return the full hash of the commit a revision names.
+
+    Raises:
+        CalledProcessError: If `rev` doesn't name a commit.
+    """
+    return run_command(["git", "rev-parse", "--verify", f"{rev}^{{commit}}"])
+
+
+def diffstat(rev: str) -> str:
+    """Return the diffstat of a commit against its first parent."""
+    return run_command(
+        ["git", "show", "--stat", "--pretty=format:", "--diff-merges=first-parent", rev]
+    )
+
+
+def shortstat(rev: str) -> str:
+    """Return the summary line of the diffstat of a commit, such as "1 file changed"."""
+    return run_command(
+        ["git", "show", "--shortstat", "--pretty=format:", "--diff-merges=first-parent", rev]
+    )
+
+
+def _changed_line_count(rev: str, *extra_args: str) -> int:
+    numstat = run_command(
+        [
+            "git",
+            "show",
+            "--numstat",
+            "--pretty=format:",
+            "--diff-merges=first-parent",
+            *extra_args,
+            rev,
+        ]
+    )
+    count = 0
+    for line in numstat.splitlines():
+        added, deleted, _ = line.split("\t", 2)
+        # Binary files are reported with "-" instead of line counts
+        if added != "-":
+            count += int(added) + int(deleted)
+    return count
+
+
+def whitespace_ratio(rev: str) -> float:
+    """Return the fraction of changed lines in a commit that only change whitespace.
+
+    A commit that changes no lines at all is reported as 0.0.
+    """
+    total = _changed_line_count(rev)
+    if not total:
+        return 0.0
+    return (total - _changed_line_count(rev, "--ignore-all-space")) / total
+
+
+def first_parent_diff(rev: str) -> str:
+    """Return the changed lines of a commit against its first parent, without context lines."""
+    return run_command(
+        ["git", "show", "--pretty=format:", "--unified=0", "--diff-merges=first-parent", rev]
+    )
+
+
+def find_reverts(commit_hash: str) -> list[str]:
+    """Return the commits reachable from HEAD whose message says they revert `commit_hash`."""
+    return run_command(
+        [
+            "git",
+            "log",
+            "--pretty=format:%H",
+            "--fixed-strings",
+            f"--grep=This reverts commit {commit_hash}",
+        ]
+    ).splitlines()
+
+
+def log_changed_lines(*extra_args: str) -> dict[str, tuple[int, str]]:
+    """Return the number of changed lines and the subject of every non-merge commit in history.
+
+    Commits are keyed by their full hash, newest first.
+    """
+    output = run_command(
+        ["git", "log", "--no-merges", "--numstat", "--pretty=format:%x00%H %s", *extra_args]
+    )
+    commits: dict[str, tuple[int, str]] = {}
+    commit_hash = ""
+    for line in output.splitlines():
+        if line.startswith("\0"):
+            commit_hash, _, subject = line[1:].partition(" ")
+            commits[commit_hash] = (0, subject)
+        elif line:
+            added, deleted, _ = line.split("\t", 2)
+            # Binary files are reported with "-" instead of line counts
+            if added != "-":
+                count, subject = commits[commit_hash]
+                commits[commit_hash] = (count + int(added) + int(deleted), subject)
+    return commits
+
+
+def log_messages(*extra_args: str) -> dict[str, tuple[str, str]]:
+    """Return the subject and body of every non-merge commit in history, newest first."""
+    log = run_command(
+        ["git", "log", "-z", "--no-merges", "--pretty=format:%H%x1f%s%x1f%b", *extra_args]
+    )
+    messages: dict[str, tuple[str, str]] = {}
+    for record in log.split("\0"):
+        if not record:
+            continue
+        commit_hash, _, message = record.partition("\x1f")
+        subject, _, body = message.partition("\x1f")
+        messages[commit_hash] = (subject, body)
+    return messages
+
+
+def commit_paths(commit_hash: str) -> list[str]:
+    """Return every path a commit touched, including those deleted since.
+
+    Paths are relative to the top level of the repository.
+    """
+    # NUL-terminated output keeps Git from quoting unusual file names
+    show = run_command(["git", "show", "-z", "--pretty=format:", "--name-only", commit_hash])
+    return [path for path in show.split("\0") if path]
+
+
+def file_history(
+    file_path: Path, max_count: Optional[int] = None
+) -> list[tuple[str, str, str, str]]:
+    """Return the commits that changed a file in the history of HEAD, newest first.
+
+    Each is given as its hash, its author as `Name <email>`, the date it was
+    authored as YYYY-MM-DD, and its subject. Commits that deleted the file are
+    included.
+    """
+    command = [
+        "git",
+        "-C",
+        _directory(file_path.parent),
+        "log",
+        "--date=short",
+        "--format=%H%x00%an <%ae>%x00%ad%x00%s",
+    ]
+    if max_count is not None:
+        command.append(f"--max-count={max_count}")
+    output = run_command([*command, "--", file_path.name])
+    history: list[tuple[str, str, str, str]] = []
+    for line in output.splitlines():
+        if line:
+            commit_hash, author, date, subject = line.split("\0", 3)
+            history.append((commit_hash, author, date, subject))
+    return history
+
+
+def ref_targets(repository: Union[str, Path] = ".") -> dict[str, str]:
+    """Return what each ref of the repository that contains `repository` points to.
+
+    Symbolic refs, such as `refs/remotes/origin/HEAD`, are given the ref they
+    point to, and other refs their object.
+    """
+    output = run_command(
+        [
+            "git",
+            "-C",
+            _directory(repository),
+            "for-each-ref",
+            "--format=%(refname) %(objectname) %(symref)",
+        ]
+    )
+    targets: dict[str, str] = {}
+    for line in output.splitlines():
+        name, _, target = line.partition(" ")
+        objectname, _, symref = target.partition(" ")
+        targets[name] = symref or objectname
+    return targets
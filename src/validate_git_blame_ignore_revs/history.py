@@ -0,0 +1,195 @@
+from collections.abc import Iterable
+from pathlib import Path
+from typing import Optional, TypedDict, Union
+
+from validate_git_blame_ignore_revs.backends import GitBackend, SubprocessGit
+from validate_git_blame_ignore_revs.formatters import FORMATTERS, detect_formatter, mentions_tool
+from validate_git_blame_ignore_revs.git import HistoryRange
+from validate_git_blame_ignore_revs.lib import validate_git_blame_ignore_revs
+
+__all__ = (
+    "FORMATTERS",
+    "CoverageReport",
+    "audit_formatter_commits",
+    "compute_coverage",
+    "find_formatter_commits",
+    "find_formatting_commits",
+)
+
+
+class CoverageReport(TypedDict):
+    covered: dict[str, str]  # Commit hash -> Commit message
+    uncovered: dict[str, str]  # Commit hash -> Commit message
+
+
+def find_formatting_commits(
+    min_ratio: float = 0.9,
+    min_lines: int = 10,
+    history_range: HistoryRange = HistoryRange(),
+    git: Optional[GitBackend] = None,
+) -> dict[str, str]:
+    """
+    Finds commits in the history of HEAD that look like formatting sweeps.
+
+    A commit is considered a formatting sweep when it changes at least
+    `min_lines` lines and at least `min_ratio` of them only change whitespace.
+    The history is that of the repository `git` entered, or of the working
+    directory when running `git`, the default.
+
+    Returns:
+        dict[str, str]: A dictionary mapping commit hashes to commit messages, newest first.
+    """
+    if git is None:
+        git = SubprocessGit()
+    all_changes = git.log_changed_lines(history_range=history_range)
+    non_whitespace_changes = git.log_changed_lines(True, history_range)
+
+    candidates: dict[str, str] = {}
+    for commit_hash, (total, subject) in all_changes.items():
+        if total < min_lines:
+            continue
+        remaining, _ = non_whitespace_changes.get(commit_hash, (0, subject))
+        if (total - remaining) / total >= min_ratio:
+            candidates[commit_hash] = subject
+    return candidates
+
+
+def compute_coverage(
+    file_path: Union[str, Path],
+    min_ratio: float = 0.9,
+    min_lines: int = 10,
+    history_range: HistoryRange = HistoryRange(),
+    git: Optional[GitBackend] = None,
+) -> CoverageReport:
+    """
+    Estimates how many of the formatting sweeps in history are listed in a `.git-blame-ignore-revs` file.
+
+    Args:
+        file_path (Union[str, Path]): Path to the `.git-blame-ignore-revs` file.
+        min_ratio (float): Minimum fraction of whitespace-only changed lines for a commit to count as a formatting sweep.
+        min_lines (int): Minimum number of changed lines for a commit to count as a formatting sweep.
+        history_range (HistoryRange): Limits on the commits searched for formatting sweeps.
+        git (Optional[GitBackend]): How the history is looked up. Defaults to running `git`.
+
+    Returns:
+        CoverageReport: A dictionary containing the detected formatting sweeps that are listed in the file and those that are not.
+    """
+    file_path = Path(file_path).resolve()
+    listed = set(validate_git_blame_ignore_revs(file_path)["valid_hashes"].values())
+
+    if git is None:
+        git = SubprocessGit()
+    git.enter(file_path.parent)
+
+    covered: dict[str, str] = {}
+    uncovered: dict[str, str] = {}
+    for commit_hash, subject in find_formatting_commits(
+        min_ratio, min_lines, history_range, git
+    ).items():
+        if commit_hash in listed:
+            covered[commit_hash] = subject
+        else:
+            uncovered[commit_hash] = subject
+
+    return CoverageReport(covered=covered, uncovered=uncovered)
+
+
+def find_formatter_commits(
+    tools: Iterable[str] = tuple(FORMATTERS),
+    min_ratio: float = 0.9,
+    min_lines: int = 10,
+    history_range: HistoryRange = HistoryRange(),
+    trailer: Optional[str] = None,
+    git: Optional[GitBackend] = None,
+) -> dict[str, tuple[str, str]]:
+    """
+    Finds commits in the history of HEAD that look like the output of a formatter.
+
+    A commit is attributed to a tool when its message mentions the tool by
+    name, or when it is a formatting sweep (see `find_formatting_commits`)
+    that only touches files with extensions the tool is known to format.
+    Commits carrying `trailer`, if it is given, are attributed to the trailer,
+    since their authors marked them as formatting commits. The history is that
+    of the repository `git` entered, or of the working directory when running
+    `git`, the default.
+
+    Returns:
+        dict[str, tuple[str, str]]: A dictionary mapping commit hashes to the detected tool and the commit message, newest first.
+    """
+    tools = list(tools)
+    if git is None:
+        git = SubprocessGit()
+
+    # Full messages of every commit, which also gives the order of history
+    messages = git.log_messages(history_range)
+
+    detected: dict[str, str] = {}
+    if trailer is not None:
+        for commit_hash in git.trailer_commits(trailer, history_range):
+            # Merges aren't formatter runs, whatever their trailers say
+            if commit_hash in messages:
+                detected[commit_hash] = trailer
+    for commit_hash, (subject, body) in messages.items():
+        if commit_hash in detected:
+            continue
+        for tool in tools:
+            if mentions_tool(tool, subject) or mentions_tool(tool, body):
+                detected[commit_hash] = tool
+                break
+
+    for commit_hash in find_formatting_commits(min_ratio, min_lines, history_range, git):
+        if commit_hash in detected:
+            continue
+        # The subject was already searched for the tools' names
+        tool = detect_formatter("", git.commit_paths(commit_hash), tools)
+        if tool is not None:
+            detected[commit_hash] = tool
+
+    return {
+        commit_hash: (detected[commit_hash], subject)
+        for commit_hash, (subject, _) in messages.items()
+        if commit_hash in detected
+    }
+
+
+def audit_formatter_commits(
+    file_path: Union[str, Path],
+    tools: Iterable[str] = tuple(FORMATTERS),
+    min_ratio: float = 0.9,
+    min_lines: int = 10,
+    history_range: HistoryRange = HistoryRange(),
+    trailer: Optional[str] = None,
+    git: Optional[GitBackend] = None,
+) -> dict[str, tuple[str, str]]:
+    """
+    Finds formatter runs in history that are missing from a `.git-blame-ignore-revs` file.
+
+    This generalizes the check for commits authored by `pre-commit-ci[bot]`
+    to any commit that looks like it was produced by one of `tools`.
+
+    Args:
+        file_path (Union[str, Path]): Path to the `.git-blame-ignore-revs` file.
+        tools (Iterable[str]): Names of the formatters to look for.
+        min_ratio (float): Minimum fraction of whitespace-only changed lines for a commit to count as a formatting sweep.
+        min_lines (int): Minimum number of changed lines for a commit to count as a formatting sweep.
+        history_range (HistoryRange): Limits on the commits searched for formatter runs.
+        trailer (Optional[str]): If given, a trailer, such as `Blame-Ignore: true`, that marks commits which must be listed whatever they change.
+        git (Optional[GitBackend]): How the history is looked up. Defaults to running `git`.
+
+    Returns:
+        dict[str, tuple[str, str]]: A dictionary mapping the hashes of missing commits to the detected tool, or the trailer, and the commit message.
+    """
+    file_path = Path(file_path).resolve()
+    listed = set(validate_git_blame_ignore_revs(file_path)["valid_hashes"].values())
+
+    if git is None:
+        git = SubprocessGit()
+    git.enter(file_path.parent)
+
+    return {
+        commit_hash: detected
+        for commit_hash, detected in find_formatter_commits(
+            tools, min_ratio, min_lines, history_range, trailer, git
+        ).items()
+        if commit_hash not in listed
+    }
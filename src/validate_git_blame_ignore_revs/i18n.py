@@ -0,0 +1,118 @@
+import ast
+import os
+from collections.abc import Callable
+from functools import lru_cache
+from pathlib import Path
+from typing import Optional
+
+__all__ = (
+    "LANGUAGES",
+    "Translate",
+    "load_catalog",
+    "requested_languages",
+    "translator",
+    "untranslated",
+)
+
+# Where the catalog of each language is kept, as a gettext PO file named after the language
+LOCALE_DIR = Path(__file__).parent / "locale"
+
+# The languages the human-readable report can be printed in besides English
+LANGUAGES = tuple(sorted(path.stem for path in LOCALE_DIR.glob("*.po")))
+
+# Returns the translation of a message, or the message itself if there is none
+Translate = Callable[[str], str]
+
+
+def untranslated(message: str) -> str:
+    """Return a message as it is, in English."""
+    return message
+
+
+def _unquote(text: str) -> str:
+    # PO strings use the same escapes as Python string literals for everything catalogs contain
+    value = ast.literal_eval(text.strip())
+    if not isinstance(value, str):
+        raise ValueError(f"expected a quoted string, got {text!r}")
+    return value
+
+
+@lru_cache(maxsize=None)
+def load_catalog(language: str) -> dict[str, str]:
+    """
+    Reads the catalog of a language, mapping each English message to its translation.
+
+    Only the `msgid` and `msgstr` of each entry are read, and entries
+    without a translation are left out, so their messages stay in English.
+
+    Returns:
+        dict[str, str]: The translations, or none if there is no catalog for the language.
+    """
+    path = LOCALE_DIR / f"{language}.po"
+    if not path.is_file():
+        return {}
+    catalog: dict[str, str] = {}
+    # The keyword of the string being read, and its parts so far
+    keyword: Optional[str] = None
+    parts: dict[str, list[str]] = {}
+
+    def finish() -> None:
+        msgid = "".join(parts.get("msgid", []))
+        msgstr = "".join(parts.get("msgstr", []))
+        if msgid and msgstr:
+            catalog[msgid] = msgstr
+        parts.clear()
+
+    for line in path.read_text(encoding="utf-8").splitlines():
+        line = line.strip()
+        if not line or line.startswith("#"):
+            continue
+        if line.startswith('"'):
+            if keyword is not None:
+                parts[keyword].append(_unquote(line))
+            continue
+        keyword, _, text = line.partition(" ")
+        if keyword == "msgid":
+            finish()
+        parts[keyword] = [_unquote(text)]
+    finish()
+    return catalog
+
+
+def requested_languages(language: Optional[str] = None) -> list[str]:
+    """
+    Return the languages to look for translations in, most preferred first.
+
+    The language is `language` if given, and otherwise the first of the
+    `LANGUAGE`, `LC_ALL`, `LC_MESSAGES`, and `LANG` environment variables
+    that is set, as gettext chooses it. `LANGUAGE` can list several, such as
+    `de:fr`. A locale such as `de_AT.UTF-8` also falls back to `de`.
+    """
+    if language is None:
+        for variable in ("LANGUAGE", "LC_ALL", "LC_MESSAGES", "LANG"):
+            if os.environ.get(variable):
+                language = os.environ[variable]
+                break
+        else:
+            return []
+    languages: list[str] = []
+    for locale in language.split(":"):
+        # Leave out the encoding and modifier, such as in `de_DE.UTF-8@euro`
+        locale = locale.split(".")[0].split("@")[0]
+        if locale in ("C", "POSIX"):
+            break
+        for candidate in (locale, locale.split("_")[0]):
+            if candidate and candidate not in languages:
+                languages.append(candidate)
+    return languages
+
+
+def translator(language: Optional[str] = None) -> Translate:
+    """Return how to translate messages into the first of the requested languages with a catalog."""
+    for candidate in requested_languages(language):
+        if candidate == "en":
+            break
+        catalog = load_catalog(candidate)
+        if catalog:
+            return lambda message: catalog.get(message, message)
+    return untranslated
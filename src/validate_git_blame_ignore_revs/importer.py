@@ -0,0 +1,243 @@
+import json
+import re
+from pathlib import Path
+from subprocess import CalledProcessError
+from typing import Any, NamedTuple, Optional, Union
+
+from validate_git_blame_ignore_revs.backends import GitBackend, SubprocessGit
+from validate_git_blame_ignore_revs.template import DEFAULT_COMMENT_TEMPLATE, render_comment
+
+__all__ = (
+    "IMPORT_FORMATS",
+    "ImportedEntry",
+    "ResolvedEntry",
+    "detect_format",
+    "format_entries",
+    "parse_entries",
+    "resolve_entries",
+)
+
+# Formats that entries can be imported from, the first being the default
+IMPORT_FORMATS = ("auto", "plain", "json", "yaml", "changelog")
+
+# A full or abbreviated hash, not part of a longer word
+_rev_regex = re.compile(r"(?<![0-9A-Za-z])[0-9a-f]{7,40}(?![0-9A-Za-z])")
+
+# A hash linked to a commit on a forge, or in backticks or parentheses, as changelogs give them
+_changelog_rev_regex = re.compile(
+    r"/commits?/([0-9a-f]{7,40})\b|`([0-9a-f]{7,40})`|\(([0-9a-f]{7,40})\)"
+)
+
+# A Markdown link, whose text is kept in comments
+_markdown_link_regex = re.compile(r"\[([^\]]*)\]\([^)]*\)")
+
+# Keys that objects in JSON and YAML lists give the hash and the description under
+_REV_KEYS = ("commit", "sha", "hash", "rev", "id")
+_COMMENT_KEYS = ("comment", "message", "subject", "description", "title", "reason")
+
+
+class ImportedEntry(NamedTuple):
+    location: str  # Where the source lists it, such as "line 3" or "item 2"
+    rev: str  # As the source gives it, a full or abbreviated hash
+    comment: Optional[str]  # The description the source gives, if any
+
+
+class ResolvedEntry(NamedTuple):
+    commit: str  # The full hash
+    comment: str  # The description from the source, or otherwise one written from the template
+
+
+def detect_format(source_name: str, text: str) -> str:
+    """Guess the format of a source from its name, or its contents for standard input."""
+    suffix = Path(source_name).suffix.lower()
+    if suffix == ".json" or text.lstrip().startswith(("[", "{")):
+        return "json"
+    if suffix in (".yaml", ".yml"):
+        return "yaml"
+    if suffix in (".md", ".markdown") or "changelog" in Path(source_name).name.lower():
+        return "changelog"
+    return "plain"
+
+
+def _rev_in(text: str) -> Optional[re.Match[str]]:
+    """Return the hash in a line, preferring one with letters over a number such as a date."""
+    matches = list(_rev_regex.finditer(text))
+    for match in matches:
+        if not match.group().isdigit():
+            return match
+    return matches[0] if matches else None
+
+
+def _plain_entry(location: str, line: str) -> Optional[ImportedEntry]:
+    match = _rev_in(line)
+    if match is None:
+        return None
+    # Whatever follows the hash, such as the subject in `git log --oneline`, describes it
+    comment = line[match.end() :].strip(" \t-:|,;")
+    return ImportedEntry(location, match.group(), comment or None)
+
+
+def _parse_plain(text: str) -> list[ImportedEntry]:
+    entries: list[ImportedEntry] = []
+    for line_number, line in enumerate(text.splitlines(), start=1):
+        if line.lstrip().startswith("#"):
+            continue
+        entry = _plain_entry(f"line {line_number}", line)
+        if entry is not None:
+            entries.append(entry)
+    return entries
+
+
+def _structured_entry(location: str, item: Any) -> Optional[ImportedEntry]:
+    if isinstance(item, str):
+        return _plain_entry(location, item)
+    if not isinstance(item, dict):
+        return None
+    rev = next((str(item[key]) for key in _REV_KEYS if item.get(key)), None)
+    if rev is None:
+        return None
+    comment = next((str(item[key]) for key in _COMMENT_KEYS if item.get(key)), None)
+    return ImportedEntry(location, rev.strip(), comment)
+
+
+def _structured_entries(data: Any) -> list[ImportedEntry]:
+    # A list of entries, or an object holding one, such as {"revs": [...]}
+    if isinstance(data, dict):
+        data = next((value for value in data.values() if isinstance(value, list)), [])
+    if not isinstance(data, list):
+        raise ValueError("expected a list of entries")
+    entries = [_structured_entry(f"item {index}", item) for index, item in enumerate(data, 1)]
+    return [entry for entry in entries if entry is not None]
+
+
+def _yaml_scalar(value: str) -> str:
+    value = value.strip()
+    if len(value) >= 2 and value[0] == value[-1] and value[0] in "'\"":
+        return value[1:-1]
+    # Unquoted, a # after a space starts a comment
+    return value.split(" #", 1)[0].strip()
+
+
+def _parse_yaml(text: str) -> list[Any]:
+    """Parse a list of strings or of flat mappings, the subset of YAML tools export entries as."""
+    items: list[Any] = []
+    # The indentation of the keys of the mapping being read, if any
+    mapping_indent: Optional[int] = None
+    for line in text.splitlines():
+        stripped = line.strip()
+        if not stripped or stripped.startswith("#") or stripped == "---":
+            continue
+        indent = len(line) - len(line.lstrip())
+        if stripped.startswith("- ") or stripped == "-":
+            item = stripped[1:].strip()
+            key, separator, value = item.partition(": ")
+            if separator and re.fullmatch(r"[\w-]+", key):
+                items.append({key: _yaml_scalar(value)})
+                mapping_indent = indent + 2
+            else:
+                items.append(_yaml_scalar(item))
+                mapping_indent = None
+        elif mapping_indent is not None and indent >= mapping_indent and ": " in stripped:
+            key, _, value = stripped.partition(": ")
+            items[-1][key] = _yaml_scalar(value)
+    return items
+
+
+def _changelog_comment(line: str, rev: str) -> str:
+    text = _markdown_link_regex.sub(r"\1", line.strip())
+    # The hash, full or abbreviated, as the link text often gives it
+    text = re.sub(rf"\(?`?{rev[:7]}[0-9a-f]*`?\)?", "", text)
+    text = re.sub(r"^(?:[-*+]|\d+\.)\s+", "", text).replace("**", "").replace("__", "")
+    return " ".join(text.split()).strip(" -:")
+
+
+def _parse_changelog(text: str) -> list[ImportedEntry]:
+    entries: list[ImportedEntry] = []
+    for line_number, line in enumerate(text.splitlines(), start=1):
+        match = _changelog_rev_regex.search(line)
+        if match is None:
+            continue
+        rev = next(group for group in match.groups() if group)
+        comment = _changelog_comment(line, rev)
+        entries.append(ImportedEntry(f"line {line_number}", rev, comment or None))
+    return entries
+
+
+def parse_entries(text: str, source_format: str) -> list[ImportedEntry]:
+    """
+    Finds the commits listed in a source in one of `IMPORT_FORMATS` other than `auto`.
+
+    - `plain`: any text with a hash on each line, after any prefix, such as
+      `- 1a2b3c4` or the output of `git log --oneline`. Lines starting with `#`
+      are skipped, and the text after a hash describes it.
+    - `json` and `yaml`: a list of hashes, or of objects giving the hash under
+      a key such as `sha` or `commit` and a description under a key such as
+      `message` or `comment`. The list can also be the value of a key of an
+      object. Only block-style YAML lists of strings or flat mappings are read.
+    - `changelog`: Markdown, such as a changelog generated by Renovate or
+      release tooling, with a hash linked to its commit on a forge or in
+      backticks or parentheses on each entry. The rest of the line, with
+      links replaced by their text, describes it.
+
+    Raises:
+        ValueError: If a JSON or YAML source isn't a list of entries.
+    """
+    if source_format == "plain":
+        return _parse_plain(text)
+    if source_format == "json":
+        return _structured_entries(json.loads(text))
+    if source_format == "yaml":
+        return _structured_entries(_parse_yaml(text))
+    if source_format == "changelog":
+        return _parse_changelog(text)
+    raise ValueError(f"Unknown format {source_format!r}")
+
+
+def resolve_entries(
+    entries: list[ImportedEntry],
+    repository: Union[str, Path],
+    template: str = DEFAULT_COMMENT_TEMPLATE,
+    git: Optional[GitBackend] = None,
+) -> tuple[list[ResolvedEntry], dict[str, str]]:
+    """
+    Looks up the commit each imported entry names in a repository.
+
+    Commits listed more than once are only resolved the first time. Entries
+    the source gives no description for are described by `template`.
+
+    Args:
+        entries (list[ImportedEntry]): The entries found by `parse_entries`.
+        repository (Union[str, Path]): A directory of the repository the commits are in.
+        template (str): The comment template, as for `render_comment`, such as `{subject} ({date}, {pr})`.
+        git (Optional[GitBackend]): How the commits are looked up. Defaults to running `git`.
+
+    Returns:
+        tuple[list[ResolvedEntry], dict[str, str]]: The entries for commits in the repository, in the order they were listed, and the problem with each other entry by its location.
+    """
+    if git is None:
+        git = SubprocessGit()
+    git.enter(Path(repository))
+
+    resolved: list[ResolvedEntry] = []
+    problems: dict[str, str] = {}
+    seen: set[str] = set()
+    for entry in entries:
+        try:
+            commit = git.resolve_commit(entry.rev)
+        except CalledProcessError:
+            if len(git.disambiguate(entry.rev)) > 1:
+                problems[entry.location] = f"{entry.rev} is ambiguous"
+            else:
+                problems[entry.location] = f"{entry.rev} is not a commit in the repository"
+            continue
+        if commit in seen:
+            continue
+        seen.add(commit)
+        comment = entry.comment or render_comment(template, git.commit_info(commit))
+        resolved.append(ResolvedEntry(commit, " ".join(comment.split())))
+    return resolved, problems
+
+
+def format_entries(entries: list[ResolvedEntry]) -> str:
+    """Return lines for a `.git-blame-ignore-revs` file listing each entry below its comment."""
+    return "".join(f"# {entry.comment}\n{entry.commit}\n" for entry in entries)
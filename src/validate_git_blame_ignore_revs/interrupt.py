@@ -0,0 +1,59 @@
+import signal
+import threading
+from collections.abc import Iterator
+from contextlib import contextmanager
+from types import FrameType
+from typing import Optional
+
+__all__ = (
+    "Interrupted",
+    "interruptible",
+)
+
+# The signals that ask the process to stop: Ctrl+C in a terminal, and CI runners cancelling a job
+_SIGNALS = (signal.SIGINT, signal.SIGTERM)
+
+
+class Interrupted(Exception):
+    """Raised when the process is asked to stop by a signal."""
+
+    def __init__(self, signum: int) -> None:
+        # Keep the signal number as the only argument so the exception survives pickling
+        # on its way back from a worker process
+        super().__init__(signum)
+        self.signum = signum
+
+    @property
+    def exit_code(self) -> int:
+        """The exit code for the signal, following the shell convention of 128 + its number."""
+        return 128 + self.signum
+
+    def __str__(self) -> str:
+        return f"Interrupted by {signal.Signals(self.signum).name}"
+
+
+def _raise_interrupted(signum: int, frame: Optional[FrameType]) -> None:
+    raise Interrupted(signum)
+
+
+@contextmanager
+def interruptible() -> Iterator[None]:
+    """
+    Turns SIGINT and SIGTERM into `Interrupted` while the block runs.
+
+    Raising the exception unwinds the block like any other, so a Git command
+    that is running is killed by `subprocess.run`, and the caller can still
+    report what was done before the signal arrived. The previous handlers are
+    restored afterwards. Signal handlers can only be set from the main thread,
+    so elsewhere this does nothing.
+    """
+    if threading.current_thread() is not threading.main_thread():
+        yield
+        return
+
+    previous = {signum: signal.signal(signum, _raise_interrupted) for signum in _SIGNALS}
+    try:
+        yield
+    finally:
+        for signum, handler in previous.items():
+            signal.signal(signum, handler)
@@ -0,0 +1,95 @@
+from collections.abc import Iterable
+from pathlib import Path
+from typing import NamedTuple, Optional
+
+from validate_git_blame_ignore_revs.backends import GitBackend, SubprocessGit
+from validate_git_blame_ignore_revs.report import Section
+
+__all__ = (
+    "FileVersion",
+    "FindingKey",
+    "Introduction",
+    "file_versions",
+    "find_introductions",
+    "finding_keys",
+)
+
+# The title of the check and the hash or line a finding is about, which identify it in every
+# version of the file, whatever line it is on
+FindingKey = tuple[str, str]
+
+
+class FileVersion(NamedTuple):
+    """A commit that changed a file, and who made it."""
+
+    commit: str
+    author: str  # The name and email address, as `Name <email>`
+    date: str  # The date it was authored, as YYYY-MM-DD
+    subject: str
+
+
+class Introduction(NamedTuple):
+    """Where a finding of the current version of a file first appeared."""
+
+    version: Optional[FileVersion]  # None when it only appears in changes not committed yet
+    # Whether it appears in the oldest version that was checked, so it may be older still
+    at_or_before: bool = False
+
+
+def file_versions(
+    file_path: Path, max_count: Optional[int] = None, git: Optional[GitBackend] = None
+) -> list[FileVersion]:
+    """
+    Return the commits that changed a file in the history of HEAD, newest first.
+
+    Commits that deleted the file are included, since the file may be added back later.
+
+    Raises:
+        CalledProcessError: If the file isn't in a Git repository.
+    """
+    if git is None:
+        git = SubprocessGit()
+    history = git.file_history(Path(file_path).resolve(), max_count)
+    return [FileVersion(*version) for version in history]
+
+
+def finding_keys(sections: Iterable[Section]) -> set[FindingKey]:
+    """Return what identifies each finding of a version of the file in the others."""
+    return {(section.title, finding.commit) for section in sections for finding in section.findings}
+
+
+def find_introductions(
+    current: Iterable[FindingKey],
+    versions: Iterable[tuple[FileVersion, set[FindingKey]]],
+    complete: bool = True,
+) -> dict[FindingKey, Introduction]:
+    """
+    Finds the version of a file that introduced each finding of its current version.
+
+    A finding was introduced by the oldest of the versions that have it
+    without a break since the current one. `versions` are the findings of
+    each version, newest first, and are only consumed until every finding is
+    accounted for, so the checks can be run on each version as it is needed.
+
+    Args:
+        current (Iterable[FindingKey]): The findings of the current version of the file, which may have changes that aren't committed.
+        versions (Iterable[tuple[FileVersion, set[FindingKey]]]): Each committed version of the file and its findings, newest first.
+        complete (bool): Whether `versions` go back to when the file was created, rather than stopping at a limit.
+
+    Returns:
+        dict[FindingKey, Introduction]: Where each finding of the current version was introduced.
+    """
+    introductions: dict[FindingKey, Introduction] = {}
+    # Finding -> The oldest version it was found in so far, without a break since the current one
+    pending: dict[FindingKey, Optional[FileVersion]] = dict.fromkeys(current)
+    for version, keys in versions:
+        for key in list(pending):
+            if key in keys:
+                pending[key] = version
+            else:
+                introductions[key] = Introduction(pending.pop(key))
+        if not pending:
+            break
+    for key, oldest in pending.items():
+        introductions[key] = Introduction(oldest, at_or_before=oldest is not None and not complete)
+    return introductions
@@ -1,162 +1,1167 @@
-import os
 import re
+import time
+import warnings
+from collections.abc import Iterable, Iterator, Sequence, Sized
+from dataclasses import dataclass
+from enum import Enum
 from pathlib import Path
-from subprocess import CalledProcessError, run
-from typing import TypedDict, Union
+from subprocess import CalledProcessError
+from typing import Any, NamedTuple, Optional, TypedDict, TypeVar, Union, cast
+
+from validate_git_blame_ignore_revs.backends import GitBackend, SubprocessGit
+from validate_git_blame_ignore_revs.comments import (
+    DEFAULT_SIMILARITY_THRESHOLD,
+    MatchMode,
+    Normalization,
+    comment_matches,
+    comment_similarity,
+    is_placeholder_comment,
+    normalize_comment,
+    split_match_directives,
+)
+from validate_git_blame_ignore_revs.commits import (
+    diff_semantic_change_score,
+    formatting_likelihood,
+)
+from validate_git_blame_ignore_revs.denylist import Denylist, denied_commit, denied_hash
+from validate_git_blame_ignore_revs.forge import (
+    find_pull_request,
+    parse_remote_url,
+    pull_request_contains,
+)
+from validate_git_blame_ignore_revs.forge_api import ForgeClient, ResponseCache, UnknownForgeError
+from validate_git_blame_ignore_revs.formatters import (
+    detect_formatter,
+    section_formatter,
+    section_header,
+)
+from validate_git_blame_ignore_revs.git import GitNotFoundError, HistoryRange, run_command
+from validate_git_blame_ignore_revs.metadata import MetadataPolicy, check_metadata
+from validate_git_blame_ignore_revs.paths import path_key
+from validate_git_blame_ignore_revs.progress import Progress
+from validate_git_blame_ignore_revs.sources import ContentSource, WorkingTree
+from validate_git_blame_ignore_revs.spelling import load_dictionary, misspelled_words
+from validate_git_blame_ignore_revs.suggestions import near_miss, resolve_near_miss
+from validate_git_blame_ignore_revs.template import render_comment
 
 __all__ = (
+    "GitNotFoundError",
+    "LineKind",
+    "MissingReason",
+    "ParsedLine",
+    "ValidationOptions",
     "ValidationResult",
+    "WorkspaceResult",
+    "add_finding",
+    "add_result",
+    "blank_line_problems",
+    "empty_result",
+    "error_count",
+    "extend_result",
+    "info_count",
+    "is_clean",
+    "merge_result",
+    "merge_results",
+    "result_summary",
+    "parse_lines",
     "run_command",
+    "tracking_problems",
+    "validate_files",
+    "warning_count",
     "validate_git_blame_ignore_revs",
     "HashEntries",
 )
 
 HashEntries = dict[int, str]
 
+_K = TypeVar("_K")
+_V = TypeVar("_V")
+
+# Regular expression for a valid Git commit hash (40 hexadecimal characters)
+commit_hash_regex = re.compile(r"^[0-9a-f]{40}$")
+
+# Regular expression for a comment that starts with a commit hash, such as an entry that was
+# commented out. Hashes later in a comment are usually references to related commits
+commented_out_hash_regex = re.compile(r"^#+\s*([0-9a-f]{40})(?![0-9a-z])")
+
+
+class LineKind(Enum):
+    Blank = "blank"
+    Comment = "comment"
+    Hash = "hash"
+    Invalid = "invalid"
+
+
+class MissingReason(str, Enum):
+    ObjectNotFound = "object-not-found"  # No object in the repository has the hash
+    NotACommit = "not-a-commit"  # The hash names a tag, tree, or blob
+    UnreachableFromRef = "unreachable-from-ref"  # The commit isn't on a ref it must be on
+    ReflogOnly = "reflog-only"  # Only the reflog reaches the commit, until its entry expires
+    Dangling = "dangling"  # Nothing reaches the commit, so `git gc` will delete it
+    ShallowBoundary = "shallow-boundary"  # Not in a shallow clone, so maybe older than its history
+    GitError = "git-error"  # Git failed for another reason, which its error message gives
+
+
+# What Git says when an object doesn't exist, as opposed to when it couldn't be read
+_NOT_FOUND_MESSAGES = ("Not a valid object name", "could not get object info", "bad object")
+
+
+def _missing_reason(error: CalledProcessError, shallow: bool) -> tuple[MissingReason, str]:
+    """Return why looking up a listed commit failed, and an explanation, from Git's error."""
+    stderr = (error.stderr or "").strip()
+    if stderr and not any(message in stderr for message in _NOT_FOUND_MESSAGES):
+        return MissingReason.GitError, stderr.splitlines()[-1]
+    if shallow:
+        return (
+            MissingReason.ShallowBoundary,
+            "not in this shallow clone, which may not go back far enough",
+        )
+    return MissingReason.ObjectNotFound, "no object has this hash"
+
+
+class ParsedLine(NamedTuple):
+    line_number: int
+    kind: LineKind
+    text: str  # The line without surrounding whitespace
+    comment: Optional[str]  # The last comment since the previous hash, without the leading "#"
+    comments: tuple[str, ...]  # Every comment since the previous hash, without the leading "#"
+
+
+def parse_lines(lines: Iterable[str]) -> Iterator[ParsedLine]:
+    """Classify each line of a `.git-blame-ignore-revs` file.
+
+    Lines are consumed lazily, so `lines` can be an open file or any other
+    stream, and exactly one `ParsedLine` is produced for each of them.
+    """
+    # Track the comments since the previous commit line
+    comments: tuple[str, ...] = ()
+
+    for line_number, line in enumerate(lines, start=1):
+        line = line.strip()
+
+        # Skip blank lines
+        if not line:
+            kind = LineKind.Blank
+        # Check for comments
+        elif line[0] == "#":
+            kind = LineKind.Comment
+        # Validate the commit hash
+        elif commit_hash_regex.match(line):
+            kind = LineKind.Hash
+        else:
+            kind = LineKind.Invalid
+
+        yield ParsedLine(line_number, kind, line, comments[-1] if comments else None, comments)
+
+        if kind is LineKind.Comment:
+            comments += (line.lstrip("#").strip(),)
+        elif kind is LineKind.Hash:
+            # Reset comment tracking after a commit line
+            comments = ()
+
 
 class ValidationResult(TypedDict):
     valid_hashes: HashEntries
     errors: HashEntries
     missing_commits: HashEntries
+    non_commit_objects: HashEntries  # Line number -> Object type, followed by the name for tags
+    # Line number -> (Why, explanation), for each entry in missing_commits or non_commit_objects
+    missing_reasons: dict[int, tuple[MissingReason, str]]
+    replaced_commits: HashEntries  # Line number -> Hash of the replacement from `git replace`
     strict_comment_errors: HashEntries
-    comment_diffs: dict[int, tuple[str, str]]  # Line number -> (comment, commit message)
+    # Line number -> The last comment, for entries in strict_comment_errors whose every comment is
+    # empty or a placeholder such as "TODO"
+    placeholder_comments: HashEntries
+    # Line number -> (comment, commit message or the comment `comment_template` gives)
+    comment_diffs: dict[int, tuple[str, str]]
+    comment_similarities: dict[int, float]  # Line number -> Score, in the similar mode
     missing_pre_commit_ci_commits: dict[str, str]  # Commit hash -> Commit message
+    missing_trailer_commits: dict[str, str]  # Commit hash -> Commit message
+    unused_entries: HashEntries
+    semantic_changes: dict[int, float]  # Line number -> Confidence that the commit changes behavior
+    metadata_errors: dict[int, tuple[str, ...]]  # Line number -> Problems with the comment metadata
+    pr_link_errors: dict[int, str]  # Line number -> Problem with the pull request reference
+    ticket_errors: HashEntries
+    duplicate_patches: dict[int, int]  # Line number -> Earlier line with the same patch
+    commented_out_hashes: HashEntries  # Line number -> Hash in the comment on that line
+    blank_line_errors: HashEntries  # Line number -> How the blank lines there break the style
+    near_misses: HashEntries  # Line number -> Problem with an invalid line that is almost a hash
+    suggested_hashes: HashEntries  # Line number -> The commit a near miss most likely meant
+    default_branch_errors: dict[int, str]  # Line number -> Problem with the commit's branch
+    reachability_errors: dict[int, tuple[str, ...]]  # Line number -> Refs it isn't reachable from
+    # Line number -> Whether the reflog reaches the commit, for commits no ref reaches
+    unreferenced_commits: dict[int, bool]
+    section_errors: HashEntries  # Line number -> Why the entry doesn't belong in its section
+    rename_heavy_commits: dict[int, tuple[int, int]]  # Line number -> (Renamed, changed) files
+    formatting_scores: dict[int, float]  # Line number -> How likely the commit only reformats code
+    entries_added_early: HashEntries  # Line number -> Commit that added the entry before it existed
+    old_entries: dict[int, int]  # Line number -> When the commit was committed, if too long ago
+    denied_entries: dict[int, str]  # Line number -> Why the denylist forbids listing the commit
+    spelling_errors: dict[int, tuple[str, ...]]  # Line number -> Misspelled words in the comment
+
+
+class WorkspaceResult(TypedDict):
+    files: dict[str, ValidationResult]  # Path as given -> Result for that file
+    summary: dict[str, int]  # Field of ValidationResult -> Number of entries in every file
+
+
+# Fields of ValidationResult whose entries are reported as errors: the file is malformed or
+# lists commits Git can't use
+_ERROR_FIELDS = ("errors", "missing_commits", "non_commit_objects", "denied_entries")
+
+# Fields whose entries are reported as warnings. The others either give more detail on entries
+# of these fields, such as missing_reasons, or aren't problems of themselves, such as scores
+_WARNING_FIELDS = (
+    "replaced_commits",
+    "strict_comment_errors",
+    "comment_diffs",
+    "missing_pre_commit_ci_commits",
+    "missing_trailer_commits",
+    "unused_entries",
+    "semantic_changes",
+    "metadata_errors",
+    "pr_link_errors",
+    "ticket_errors",
+    "duplicate_patches",
+    "commented_out_hashes",
+    "blank_line_errors",
+    "default_branch_errors",
+    "reachability_errors",
+    "unreferenced_commits",
+    "section_errors",
+    "rename_heavy_commits",
+    "entries_added_early",
+)
+
+# Fields whose entries are reported as info, which never fails a run
+_INFO_FIELDS = ("old_entries", "spelling_errors")
+
+
+def _with_progress(
+    entries: dict[_K, _V], label: str, progress: Optional[Progress]
+) -> Iterator[tuple[_K, _V]]:
+    """Iterate over the items of `entries`, telling `progress` how many have been handled."""
+    if progress is not None:
+        progress(label, 0, len(entries))
+    for done, item in enumerate(entries.items(), start=1):
+        yield item
+        if progress is not None:
+            progress(label, done, len(entries))
+
+
+@dataclass(frozen=True)
+class ValidationOptions:
+    """
+    Which checks `validate_git_blame_ignore_revs` runs, and how.
+
+    Every check is off by default, so the file is only checked for syntax.
+
+    Attributes:
+        call_git (bool): If True, ensures each commit is in the history of the checked-out branch and names a commit rather than another kind of object, giving the reason for each that doesn't, such as a shallow clone, reports commits replaced with `git replace` unless `GIT_NO_REPLACE_OBJECTS` is set, and suggests the commit that invalid lines which are almost a hash were meant to be when it is unambiguous.
+        strict_comments (bool): If True, requires each commit line to have one or more comment lines above it, at least one of which isn't empty or a placeholder such as `TODO`, `fix`, or `formatting`.
+        strict_comments_git (bool): If True, ensures the comment above each commit matches the commit message according to `comment_match`.
+        pre_commit_ci (bool): If True, ensures all commits authored by `pre-commit-ci[bot]` are present in the file.
+        trailer (Optional[str]): If given, ensures all commits carrying this trailer, such as `Blame-Ignore: true` or only its key, are present in the file. Requires `call_git`.
+        report_unused_entries (bool): If True, reports commits that no line at HEAD is attributed to anymore.
+        check_semantic_changes (bool): If True, reports commits whose diffs change identifiers or literals rather than only formatting.
+        semantic_threshold (float): The confidence above which a commit is reported by `check_semantic_changes`.
+        metadata_policy (Optional[MetadataPolicy]): If given, ensures the `Key: value` pairs in the comments above each commit satisfy it.
+        require_pr_link (bool): If True, requires the comments above each commit to link to or reference a pull request.
+        verify_pr_links (bool): If True, ensures each referenced pull request exists on GitHub or GitLab and contains the commit. The repository is the one `remote` points to.
+        forge_cache (Optional[ResponseCache]): If given, where the responses of the forge API are kept for later runs.
+        ticket_pattern (Optional[str]): If given, requires the comments above each commit to contain a match for this regular expression, such as a ticket ID.
+        comment_match (MatchMode): How `strict_comments_git` compares comments with commit messages. A `match: <mode>` comment above an entry overrides it for that entry.
+        similarity_threshold (float): The score at or above which a comment matches in the `similar` mode.
+        comment_template (Optional[str]): If given, `strict_comments_git` compares comments with this template, such as `{subject} ({date}, {pr})`, written for each commit by `render_comment` rather than with the commit message. The pull request is the one referenced in the comments above the entry, if any.
+        normalization (Optional[Normalization]): If given, the Unicode normalization form applied to comments and commit messages before they are compared.
+        casefold (bool): If True, ignores case when comparing comments and commit messages.
+        history_range (HistoryRange): Limits on the commits scanned by `pre_commit_ci` and `trailer`.
+        check_duplicate_patches (bool): If True, reports commits that make the same change as a commit listed earlier, such as backports.
+        fail_fast (bool): If True, stops at the first invalid line, missing commit, or object that isn't a commit, skipping the entries and checks after it.
+        encoding (str): How the file is decoded: `utf-8`, `latin1`, or `auto` for UTF-8 unless the file isn't valid UTF-8, and Latin-1 otherwise.
+        check_commented_out (bool): If True, reports comments that start with a commit hash, which are usually entries that were disabled and forgotten.
+        check_blank_lines (bool): If True, reports blank lines that break the style `blank_line_problems` checks.
+        remote (str): The remote used by the checks that look one up, such as `upstream` in a fork.
+        check_default_branch (bool): If True, reports commits that aren't reachable from the default branch of `remote`, such as those of a feature branch that was squashed when it was merged.
+        reachable_from (Sequence[str]): Refs, or glob patterns such as `release/*` matching branches and tags, from which every commit must be reachable. Each ref a commit isn't reachable from is reported, as is each pattern that matches no ref.
+        check_unreferenced (bool): If True, reports commits that no branch, tag, or other ref reaches, telling those only the reflog reaches from dangling ones. They are usually left behind by amending or squashing, and `git gc` deletes them.
+        check_sections (bool): If True, reports entries in a section for one formatter, started by a header comment such as `## black` or `# --- prettier ---`, whose commit looks like the work of another formatter, going by its subject or the files it changes.
+        check_renames (bool): If True, reports commits where most of the changed files were renamed, since `git blame` already follows renames and ignoring them usually achieves nothing.
+        score_formatting (bool): If True, scores how likely each commit is to only reformat code, from its whitespace-only changes, the number of files it touches, keywords in its subject, and whether a bot authored it.
+        check_entry_dates (bool): If True, reports entries that were added to the file in a commit older than the commit they list, a sign that the listed commit was rewritten by a force push afterwards.
+        older_than (Optional[float]): If given, reports commits committed more than this many seconds before the validation, so that entries that may no longer serve a purpose are reviewed.
+        denylist (Optional[Denylist]): If given, reports entries for commits it forbids listing. Its subjects and authors are only checked with `call_git`.
+        spelling_dictionary (Optional[Union[str, Path]]): If given, a word list such as `/usr/share/dict/words`, against which the words of every comment are checked.
+        spelling_words (Sequence[str]): Words of the project that comments may use besides those of `spelling_dictionary`, such as the names of tools.
+    """
+
+    call_git: bool = False
+    strict_comments: bool = False
+    strict_comments_git: bool = False
+    pre_commit_ci: bool = False
+    trailer: Optional[str] = None
+    report_unused_entries: bool = False
+    check_semantic_changes: bool = False
+    semantic_threshold: float = 0.0
+    metadata_policy: Optional[MetadataPolicy] = None
+    require_pr_link: bool = False
+    verify_pr_links: bool = False
+    forge_cache: Optional[ResponseCache] = None
+    ticket_pattern: Optional[str] = None
+    comment_match: MatchMode = MatchMode.Prefix
+    similarity_threshold: float = DEFAULT_SIMILARITY_THRESHOLD
+    comment_template: Optional[str] = None
+    normalization: Optional[Normalization] = None
+    casefold: bool = False
+    history_range: HistoryRange = HistoryRange()
+    check_duplicate_patches: bool = False
+    fail_fast: bool = False
+    encoding: str = "utf-8"
+    check_commented_out: bool = False
+    check_blank_lines: bool = False
+    remote: str = "origin"
+    check_default_branch: bool = False
+    reachable_from: Sequence[str] = ()
+    check_unreferenced: bool = False
+    check_sections: bool = False
+    check_renames: bool = False
+    score_formatting: bool = False
+    check_entry_dates: bool = False
+    older_than: Optional[float] = None
+    denylist: Optional[Denylist] = None
+    spelling_dictionary: Optional[Union[str, Path]] = None
+    spelling_words: Sequence[str] = ()
 
 
-def run_command(command: list[str]) -> str:
-    """Run a Git command and return its output."""
-    result = run(
-        command,
-        check=True,
-        capture_output=True,
-        text=True,
+# The parameters `validate_git_blame_ignore_revs` took before `ValidationOptions`, in order
+_LEGACY_PARAMETERS = (
+    "call_git",
+    "strict_comments",
+    "strict_comments_git",
+    "pre_commit_ci",
+    "trailer",
+    "report_unused_entries",
+    "check_semantic_changes",
+    "semantic_threshold",
+    "metadata_policy",
+    "require_pr_link",
+    "verify_pr_links",
+    "forge_cache",
+    "ticket_pattern",
+    "comment_match",
+    "similarity_threshold",
+    "comment_template",
+    "normalization",
+    "casefold",
+    "history_range",
+    "check_duplicate_patches",
+    "fail_fast",
+    "source",
+    "encoding",
+    "check_commented_out",
+    "check_blank_lines",
+    "progress",
+    "remote",
+    "check_default_branch",
+    "reachable_from",
+    "check_unreferenced",
+    "check_sections",
+    "check_renames",
+    "score_formatting",
+    "check_entry_dates",
+    "older_than",
+    "denylist",
+    "spelling_dictionary",
+    "spelling_words",
+    "git",
+)
+
+
+def _legacy_arguments(
+    args: Sequence[Any], kwargs: dict[str, Any]
+) -> tuple[ValidationOptions, dict[str, Any]]:
+    """
+    Turn the arguments of a call written before `ValidationOptions` into options.
+
+    Returns the options, and the arguments that are still parameters of their own.
+    """
+    if len(args) > len(_LEGACY_PARAMETERS):
+        raise TypeError(
+            f"validate_git_blame_ignore_revs() takes at most {len(_LEGACY_PARAMETERS) + 1} "
+            f"positional arguments but {len(args) + 1} were given"
+        )
+    arguments = dict(zip(_LEGACY_PARAMETERS, args))
+    for name, value in kwargs.items():
+        if name not in _LEGACY_PARAMETERS:
+            raise TypeError(
+                f"validate_git_blame_ignore_revs() got an unexpected keyword argument {name!r}"
+            )
+        if name in arguments:
+            raise TypeError(
+                f"validate_git_blame_ignore_revs() got multiple values for argument {name!r}"
+            )
+        arguments[name] = value
+    rest = {
+        name: arguments.pop(name) for name in ("source", "progress", "git") if name in arguments
+    }
+    warnings.warn(
+        "Passing the checks to validate_git_blame_ignore_revs as arguments of their own is "
+        "deprecated; pass them in a ValidationOptions instead",
+        DeprecationWarning,
+        stacklevel=3,
     )
-    return result.stdout.strip()
+    return ValidationOptions(**arguments), rest
 
 
 def validate_git_blame_ignore_revs(
     file_path: Union[str, Path],
-    call_git: bool = False,
-    strict_comments: bool = False,
-    strict_comments_git: bool = False,
-    pre_commit_ci: bool = False,
+    options: Optional[ValidationOptions] = None,
+    *args: Any,
+    source: Optional[ContentSource] = None,
+    progress: Optional[Progress] = None,
+    git: Optional[GitBackend] = None,
+    **kwargs: Any,
 ) -> ValidationResult:
     """
     Validates the contents of a `.git-blame-ignore-revs` file.
 
     Args:
-        file_path (Union[str, Path]): Path to the `.git-blame-ignore-revs` file.
-        call_git (bool): If True, ensures each commit is in the history of the checked-out branch.
-        strict_comments (bool): If True, requires each commit line to have one or more comment lines above it.
-        strict_comments_git (bool): If True, ensures the comment above each commit matches the first part of the commit message.
-        pre_commit_ci (bool): If True, ensures all commits authored by `pre-commit-ci[bot]` are present in the file.
+        file_path (Union[str, Path]): Path to the `.git-blame-ignore-revs` file. Symlinks are resolved before looking for the repository.
+        options (Optional[ValidationOptions]): Which checks to run, and how. Defaults to only checking the syntax.
+        source (Optional[ContentSource]): Where to read the contents of the file from, such as the Git index. Defaults to the working tree.
+        progress (Optional[Progress]): If given, called as the checks that run Git for each entry or scan history make progress, such as to show a progress bar.
+        git (Optional[GitBackend]): How the checks that need Git look things up, such as in a `FakeGit` repository held in memory for testing. Defaults to running `git`.
+        *args, **kwargs: Deprecated. The checks as arguments of their own, such as `call_git=True`, as this took them before `options`. They are turned into a `ValidationOptions`, with a `DeprecationWarning`.
 
     Returns:
-        ValidationResult: A dictionary containing valid hashes, errors, missing commits, strict comment errors, comment diffs, and missing pre-commit-ci commits.
+        ValidationResult: The valid hashes and the findings of the checks that were run.
+
+    Raises:
+        FileNotFoundError: If `source` has no such file.
+        UnicodeDecodeError: If the file isn't valid in `options.encoding`.
+        GitNotFoundError: If a check that needs Git is enabled and the `git` executable cannot be run.
+        OSError: If `options.spelling_dictionary` cannot be read.
+        TypeError: If the checks are given both in `options` and as arguments of their own.
     """
+    if args or kwargs or not isinstance(options, (ValidationOptions, type(None))):
+        if isinstance(options, ValidationOptions):
+            raise TypeError(
+                "validate_git_blame_ignore_revs() takes the checks either in options "
+                "or as arguments of their own, not both"
+            )
+        legacy_args = () if options is None and not args else (options, *args)
+        # These may also have been given by position, as they were among the checks
+        given = {"source": source, "progress": progress, "git": git}
+        kwargs.update((name, value) for name, value in given.items() if value is not None)
+        options, rest = _legacy_arguments(legacy_args, kwargs)
+        source, progress, git = rest.get("source"), rest.get("progress"), rest.get("git")
+    if options is None:
+        options = ValidationOptions()
+    if source is None:
+        source = WorkingTree()
+    if git is None:
+        git = SubprocessGit()
+
     valid_hashes: HashEntries = {}
     errors: HashEntries = {}
     missing_commits: HashEntries = {}
+    non_commit_objects: HashEntries = {}
+    missing_reasons: dict[int, tuple[MissingReason, str]] = {}
+    replaced_commits: HashEntries = {}
     strict_comment_errors: HashEntries = {}
+    placeholder_comments: HashEntries = {}
     comment_diffs: dict[int, tuple[str, str]] = {}
+    comment_similarities: dict[int, float] = {}
     missing_pre_commit_ci_commits: dict[str, str] = {}
+    missing_trailer_commits: dict[str, str] = {}
+    unused_entries: HashEntries = {}
+    semantic_changes: dict[int, float] = {}
+    rename_heavy_commits: dict[int, tuple[int, int]] = {}
+    formatting_scores: dict[int, float] = {}
+    entries_added_early: HashEntries = {}
+    old_entries: dict[int, int] = {}
+    denied_entries: dict[int, str] = {}
+    metadata_errors: dict[int, tuple[str, ...]] = {}
+    pr_link_errors: dict[int, str] = {}
+    ticket_errors: HashEntries = {}
+    duplicate_patches: dict[int, int] = {}
+    commented_out_hashes: HashEntries = {}
+    blank_line_errors: HashEntries = {}
+    near_misses: HashEntries = {}
+    suggested_hashes: HashEntries = {}
+    default_branch_errors: dict[int, str] = {}
+    reachability_errors: dict[int, tuple[str, ...]] = {}
+    unreferenced_commits: dict[int, bool] = {}
+    section_errors: HashEntries = {}
+    spelling_errors: dict[int, tuple[str, ...]] = {}
 
-    # Regular expression for a valid Git commit hash (40 hexadecimal characters)
-    commit_hash_regex = re.compile(r"^[0-9a-f]{40}$")
+    # The hashes that each near miss could have been meant as, for looking up with Git
+    near_miss_candidates: dict[int, tuple[str, ...]] = {}
+    # The pull request referenced above each commit
+    pull_requests: dict[int, int] = {}
+    # How the comment above each commit is compared with its commit message
+    match_modes: dict[int, MatchMode] = {}
 
-    file_path = Path(file_path)
-    lines = file_path.read_text(encoding="utf-8").splitlines()
+    # The last comment above each commit other than `match:` directives, or "" if there is none
+    last_comments: HashEntries = {}
+    # The pull request referenced in the comments above each commit, for `comment_template`
+    referenced_pull_requests: dict[int, Optional[int]] = {}
+    # The line and name of the section header above each commit, for `check_sections`
+    entry_sections: dict[int, tuple[int, str]] = {}
+    section: Optional[tuple[int, str]] = None
 
-    # Track whether the previous lines were comments
-    has_comment_above = False
-    last_comment = None
+    def compare_comment(line_number: int, commit_hash: str, commit_message: str) -> None:
+        if options.comment_template is not None:
+            commit_message = render_comment(
+                options.comment_template,
+                git.commit_info(commit_hash),
+                referenced_pull_requests[line_number],
+            )
+        last_comment = last_comments[line_number]
+        mode = match_modes[line_number]
+        # Report the text as written, but compare the normalized text
+        comment = normalize_comment(last_comment, options.normalization, options.casefold)
+        message = normalize_comment(commit_message, options.normalization, options.casefold)
+        if mode is MatchMode.Similar:
+            comment_similarities[line_number] = comment_similarity(comment, message)
+        if not comment_matches(comment, message, mode, options.similarity_threshold):
+            comment_diffs[line_number] = (last_comment, commit_message)
 
-    for line_number, line in enumerate(lines, start=1):
-        line = line.strip()
+    dictionary = (
+        load_dictionary(options.spelling_dictionary)
+        if options.spelling_dictionary is not None
+        else None
+    )
 
-        # Skip blank lines
-        if not line:
-            continue
+    # Resolve symlinks so Git runs in the repository that contains the file itself
+    file_path = Path(file_path).resolve()
+    with source.open_lines(file_path, options.encoding) as lines:
+        for parsed_line in parse_lines(lines):
+            line_number = parsed_line.line_number
+            if parsed_line.kind is LineKind.Hash:
+                valid_hashes[line_number] = parsed_line.text
+                comments, entry_match = split_match_directives(parsed_line.comments)
+                last_comments[line_number] = comments[-1] if comments else ""
+                if options.comment_template is not None:
+                    referenced_pull_requests[line_number] = find_pull_request(comments)
+                if section is not None:
+                    entry_sections[line_number] = section
+                match_modes[line_number] = entry_match or options.comment_match
 
-        # Check for comments
-        if line.startswith("#"):
-            has_comment_above = True
-            last_comment = line.lstrip("#").strip()
-            continue
+                # Check strict comments requirement
+                if options.strict_comments and parsed_line.comment is None:
+                    strict_comment_errors[line_number] = parsed_line.text
+                elif options.strict_comments and all(is_placeholder_comment(c) for c in comments):
+                    strict_comment_errors[line_number] = parsed_line.text
+                    placeholder_comments[line_number] = comments[-1] if comments else ""
 
-        # Validate the commit hash
-        if commit_hash_regex.match(line):
-            valid_hashes[line_number] = line
+                if options.metadata_policy is not None:
+                    problems = check_metadata(parsed_line.comments, options.metadata_policy)
+                    if problems:
+                        metadata_errors[line_number] = tuple(problems)
 
-            # Check strict comments requirement
-            if strict_comments and not has_comment_above:
-                strict_comment_errors[line_number] = line
+                if options.require_pr_link:
+                    pull_request = find_pull_request(parsed_line.comments)
+                    if pull_request is None:
+                        pr_link_errors[line_number] = "no pull request reference"
+                    else:
+                        pull_requests[line_number] = pull_request
 
-            # Reset comment tracking after a commit line
-            has_comment_above = False
-            last_comment = None
-        else:
-            errors[line_number] = line
+                if options.ticket_pattern is not None and not any(
+                    re.search(options.ticket_pattern, comment) for comment in parsed_line.comments
+                ):
+                    ticket_errors[line_number] = parsed_line.text
+            elif parsed_line.kind is LineKind.Comment:
+                if options.check_commented_out:
+                    match = commented_out_hash_regex.match(parsed_line.text)
+                    if match:
+                        commented_out_hashes[line_number] = match[1]
+                name = section_header(parsed_line.text)
+                if name is not None:
+                    section = (line_number, name)
+                if dictionary is not None:
+                    words = misspelled_words(
+                        parsed_line.text.lstrip("#"), dictionary, options.spelling_words
+                    )
+                    if words:
+                        spelling_errors[line_number] = tuple(words)
+            elif parsed_line.kind is LineKind.Invalid:
+                errors[line_number] = parsed_line.text
+                diagnosis = near_miss(parsed_line.text)
+                if diagnosis is not None:
+                    near_misses[line_number] = diagnosis.problem
+                    near_miss_candidates[line_number] = diagnosis.candidates
+                if options.fail_fast:
+                    break
 
-    if call_git or strict_comments_git:
-        os.chdir(file_path.parent)
+        if options.check_blank_lines:
+            lines.seek(0)
+            blank_line_errors = blank_line_problems(lines)
 
-        # Fetch commit messages and verify existence using `git show`
+    # Whether fail_fast stopped validation, leaving the remaining checks undone
+    stopped = options.fail_fast and bool(errors)
+
+    if options.denylist is not None and not stopped:
         for line_number, commit_hash in valid_hashes.items():
+            reason = denied_hash(commit_hash, options.denylist)
+            if reason is not None:
+                denied_entries[line_number] = reason
+
+    if (options.call_git or options.strict_comments_git) and not stopped:
+        git.enter(file_path.parent)
+
+        for line_number, candidates in near_miss_candidates.items():
+            suggestion = resolve_near_miss(candidates, git)
+            if suggestion is not None:
+                suggested_hashes[line_number] = suggestion
+
+        # Git shows the replacement for these instead, hiding the listed commit itself
+        replacements = git.replaced_objects()
+        # Whether the clone is shallow, only looked up once a commit is missing
+        shallow: Optional[bool] = None
+
+        # Fetch commit messages and verify existence using `git show`
+        for line_number, commit_hash in _with_progress(
+            valid_hashes, "Looking up commits", progress
+        ):
+            if commit_hash in replacements:
+                replaced_commits[line_number] = replacements[commit_hash]
             try:
-                git_output = run_command(
-                    ["git", "show", "--quiet", "--pretty=format:%H %s", commit_hash]
-                )
-                if not git_output:
+                # `git show` also accepts tags, trees, and blobs, so check the type first
+                if git.object_type(commit_hash) != "commit":
+                    description = git.describe_object(commit_hash)
+                    non_commit_objects[line_number] = description
+                    missing_reasons[line_number] = (
+                        MissingReason.NotACommit,
+                        f"not a commit object: {description}",
+                    )
+                    if options.fail_fast:
+                        break
+                    continue
+                commit_message = git.commit_subject(commit_hash)
+                if commit_message is None:
                     missing_commits[line_number] = commit_hash
-                else:
-                    commit_hash_from_git, commit_message = git_output.split(" ", 1)
-                    if strict_comments_git:
-                        last_comment = get_last_comment(lines, line_number)
-                        if not commit_message.startswith(last_comment):
-                            comment_diffs[line_number] = (last_comment, commit_message)
-            except CalledProcessError:
+                    missing_reasons[line_number] = (
+                        MissingReason.ObjectNotFound,
+                        "Git shows nothing for it",
+                    )
+                elif options.strict_comments_git:
+                    compare_comment(line_number, commit_hash, commit_message)
+            except CalledProcessError as e:
                 missing_commits[line_number] = commit_hash
+                if shallow is None:
+                    shallow = git.is_shallow()
+                missing_reasons[line_number] = _missing_reason(e, shallow)
+            if options.fail_fast and line_number in missing_commits:
+                break
+        stopped = options.fail_fast and bool(missing_commits or non_commit_objects)
 
-        if pre_commit_ci:
+        if options.pre_commit_ci and not stopped:
             # Fetch all commits authored by `pre-commit-ci[bot]` in the checked-out branch
             try:
-                pre_commit_ci_commits = run_command(
-                    ["git", "log", "--pretty=format:%H %s", r"--author=pre-commit-ci\[bot\]"]
-                ).splitlines()
-                for commit_entry in pre_commit_ci_commits:
-                    # Skip empty or malformed lines
-                    if not commit_entry.strip():
-                        continue
-                    parts = commit_entry.split(" ", 1)
-                    if len(parts) != 2:
-                        continue
-                    commit_hash, commit_message = parts
+                if progress is not None:
+                    progress("Scanning history for pre-commit-ci commits", 0, 1)
+                pre_commit_ci_commits = git.log_subjects(
+                    r"pre-commit-ci\[bot\]", options.history_range
+                )
+                if progress is not None:
+                    progress("Scanning history for pre-commit-ci commits", 1, 1)
+                for commit_hash, commit_message in pre_commit_ci_commits.items():
                     if commit_hash not in valid_hashes.values():
                         missing_pre_commit_ci_commits[commit_hash] = commit_message
-                    elif strict_comments or strict_comments_git:
+                    elif options.strict_comments or options.strict_comments_git:
                         # Check strict comments and strict comments git for pre-commit-ci commits
                         for line_number, line in valid_hashes.items():
                             if line == commit_hash:
-                                last_comment = get_last_comment(lines, line_number)
-                                if strict_comments and not last_comment:
+                                last_comment = last_comments[line_number]
+                                if options.strict_comments and not last_comment:
                                     strict_comment_errors[line_number] = commit_hash
-                                if strict_comments_git and not commit_message.startswith(
-                                    last_comment
-                                ):
-                                    comment_diffs[line_number] = (last_comment, commit_message)
+                                if options.strict_comments_git:
+                                    compare_comment(line_number, commit_hash, commit_message)
             except CalledProcessError:
                 raise RuntimeError("Failed to fetch commits authored by pre-commit-ci[bot].")
 
+        if options.denylist is not None and options.call_git and not stopped:
+            for line_number, commit_hash in _with_progress(
+                valid_hashes, "Checking the denylist", progress
+            ):
+                if (
+                    line_number in denied_entries
+                    or line_number in missing_commits
+                    or line_number in non_commit_objects
+                ):
+                    continue
+                reason = denied_commit(
+                    git.commit_subject(commit_hash) or "",
+                    git.commit_author(commit_hash),
+                    options.denylist,
+                )
+                if reason is not None:
+                    denied_entries[line_number] = reason
+
+        if options.trailer is not None and options.call_git and not stopped:
+            if progress is not None:
+                progress(f"Scanning history for {options.trailer} commits", 0, 1)
+            trailer_commits = git.trailer_commits(options.trailer, options.history_range)
+            for commit_hash, commit_message in trailer_commits.items():
+                if commit_hash not in valid_hashes.values():
+                    missing_trailer_commits[commit_hash] = commit_message
+            if progress is not None:
+                progress(f"Scanning history for {options.trailer} commits", 1, 1)
+
+        if options.check_semantic_changes and not stopped:
+            for line_number, commit_hash in _with_progress(
+                valid_hashes, "Scoring semantic changes", progress
+            ):
+                if line_number in missing_commits or line_number in non_commit_objects:
+                    continue
+                score = diff_semantic_change_score(git.first_parent_diff(commit_hash))
+                if score > options.semantic_threshold:
+                    semantic_changes[line_number] = score
+
+        if options.check_sections and not stopped:
+            for line_number, (header_line, name) in _with_progress(
+                entry_sections, "Checking sections", progress
+            ):
+                tool = section_formatter(name)
+                if line_number in missing_commits or line_number in non_commit_objects:
+                    continue
+                if tool is None:
+                    continue
+                commit_hash = valid_hashes[line_number]
+                detected = detect_formatter(
+                    git.commit_subject(commit_hash) or "", git.changed_paths(commit_hash)
+                )
+                if detected is not None and detected != tool:
+                    section_errors[line_number] = (
+                        f"looks like a {detected} commit, "
+                        f"but is in the {name!r} section (line {header_line})"
+                    )
+
+        if options.check_renames and not stopped:
+            for line_number, commit_hash in _with_progress(
+                valid_hashes, "Counting renames", progress
+            ):
+                if line_number in missing_commits or line_number in non_commit_objects:
+                    continue
+                renamed, changed = git.rename_counts(commit_hash)
+                # Reported when renames are most of what the commit changes
+                if renamed * 2 > changed:
+                    rename_heavy_commits[line_number] = (renamed, changed)
+
+        if options.score_formatting and not stopped:
+            for line_number, commit_hash in _with_progress(
+                valid_hashes, "Scoring formatting", progress
+            ):
+                if line_number in missing_commits or line_number in non_commit_objects:
+                    continue
+                formatting_scores[line_number] = formatting_likelihood(
+                    git.whitespace_ratio(commit_hash),
+                    git.rename_counts(commit_hash)[1],
+                    git.commit_subject(commit_hash) or "",
+                    git.commit_author(commit_hash),
+                )
+
+        if options.check_entry_dates and not stopped:
+            try:
+                added_in = git.blame_contents(
+                    file_path.name, source.read_text(file_path, options.encoding)
+                )
+            except CalledProcessError:
+                # The file isn't in HEAD, so no entry has been added yet
+                added_in = []
+            for line_number, commit_hash in _with_progress(
+                valid_hashes, "Checking when entries were added", progress
+            ):
+                if line_number in missing_commits or line_number in non_commit_objects:
+                    continue
+                if line_number > len(added_in) or added_in[line_number - 1] == "0" * 40:
+                    continue
+                adding_commit = added_in[line_number - 1]
+                if git.commit_time(commit_hash) > git.commit_time(adding_commit):
+                    entries_added_early[line_number] = adding_commit
+
+        if options.older_than is not None and not stopped:
+            cutoff = time.time() - options.older_than
+            for line_number, commit_hash in _with_progress(
+                valid_hashes, "Checking the age of entries", progress
+            ):
+                if line_number in missing_commits or line_number in non_commit_objects:
+                    continue
+                committed = git.commit_time(commit_hash)
+                if committed < cutoff:
+                    old_entries[line_number] = committed
+
+        if options.verify_pr_links and not stopped:
+            try:
+                repository = parse_remote_url(git.remote_url(options.remote))
+            except CalledProcessError:
+                repository = None
+            # Shared by the requests for every pull request, which look up the token once
+            client: Optional[ForgeClient] = None
+            unchecked = f"no {options.remote} remote"
+            if repository is not None:
+                try:
+                    client = ForgeClient(repository.host, cache=options.forge_cache)
+                except UnknownForgeError as e:
+                    unchecked = str(e)
+            for line_number, number in _with_progress(
+                pull_requests, "Checking pull requests", progress
+            ):
+                if line_number in missing_commits or line_number in non_commit_objects:
+                    continue
+                if repository is None or client is None:
+                    pr_link_errors[line_number] = f"#{number} could not be checked: {unchecked}"
+                    continue
+                try:
+                    contains = pull_request_contains(
+                        repository, number, valid_hashes[line_number], client
+                    )
+                except OSError as e:
+                    pr_link_errors[line_number] = f"#{number} could not be checked: {e}"
+                    continue
+                if contains is None:
+                    pr_link_errors[line_number] = f"#{number} does not exist"
+                elif not contains:
+                    pr_link_errors[line_number] = f"#{number} does not contain the commit"
+
+        if options.check_default_branch and not stopped:
+            default_branch = git.remote_head(options.remote)
+            for line_number, commit_hash in _with_progress(
+                valid_hashes, "Checking the default branch", progress
+            ):
+                if line_number in missing_commits or line_number in non_commit_objects:
+                    continue
+                if default_branch is None:
+                    default_branch_errors[line_number] = (
+                        f"could not be checked: the default branch of {options.remote} isn't known"
+                    )
+                elif not git.is_ancestor(commit_hash, default_branch):
+                    default_branch_errors[line_number] = f"not on {default_branch}"
+
+        if options.reachable_from and not stopped:
+            # Pattern -> The refs it matches, looked up once for every entry
+            required_refs = {
+                pattern: git.matching_refs(pattern) for pattern in options.reachable_from
+            }
+            for line_number, commit_hash in _with_progress(
+                valid_hashes, "Checking the required refs", progress
+            ):
+                if line_number in missing_commits or line_number in non_commit_objects:
+                    continue
+                problems: list[str] = []
+                for pattern, refs in required_refs.items():
+                    if not refs:
+                        problems.append(f"no ref matches {pattern}")
+                    problems.extend(
+                        f"not reachable from {ref}"
+                        for ref in refs
+                        if not git.is_ancestor(commit_hash, ref)
+                    )
+                if problems:
+                    reachability_errors[line_number] = tuple(problems)
+
+        if options.check_unreferenced and not stopped:
+            # Every commit is looked up at once, since Git walks the history of every ref to tell
+            found = {
+                line_number: commit_hash
+                for line_number, commit_hash in valid_hashes.items()
+                if line_number not in missing_commits and line_number not in non_commit_objects
+            }
+            if progress is not None:
+                progress("Checking for unreferenced commits", 0, 1)
+            unreferenced = git.unreferenced_commits(list(found.values()))
+            if progress is not None:
+                progress("Checking for unreferenced commits", 1, 1)
+            for line_number, commit_hash in found.items():
+                if commit_hash in unreferenced:
+                    unreferenced_commits[line_number] = unreferenced[commit_hash]
+
+        if options.check_duplicate_patches and not stopped:
+            # Patch ID -> First line listing a commit with that patch
+            first_lines: dict[str, int] = {}
+            for line_number, commit_hash in _with_progress(
+                valid_hashes, "Computing patch IDs", progress
+            ):
+                if line_number in missing_commits or line_number in non_commit_objects:
+                    continue
+                patch = git.patch_id(commit_hash)
+                if patch is None:
+                    continue
+                if patch in first_lines:
+                    duplicate_patches[line_number] = first_lines[patch]
+                else:
+                    first_lines[patch] = line_number
+
+        if options.report_unused_entries and not stopped:
+            # Blame paths relative to the top level, sharing the result between entries
+            git.enter(Path(git.toplevel()))
+            blamed: dict[str, set[str]] = {}
+            for line_number, commit_hash in _with_progress(
+                valid_hashes, "Blaming changed files", progress
+            ):
+                if line_number in missing_commits or line_number in non_commit_objects:
+                    continue
+                for path in git.changed_paths(commit_hash):
+                    if path not in blamed:
+                        blamed[path] = set(git.blame_attribution(path))
+                    if commit_hash in blamed[path]:
+                        break
+                else:
+                    unused_entries[line_number] = commit_hash
+
+    # Entries can be found out of order by the later checks, so order them by line number
     return ValidationResult(
         valid_hashes=valid_hashes,
         errors=errors,
-        missing_commits=missing_commits,
-        strict_comment_errors=strict_comment_errors,
-        comment_diffs=comment_diffs,
+        missing_commits=dict(sorted(missing_commits.items())),
+        non_commit_objects=non_commit_objects,
+        missing_reasons=dict(sorted(missing_reasons.items())),
+        replaced_commits=replaced_commits,
+        strict_comment_errors=dict(sorted(strict_comment_errors.items())),
+        placeholder_comments=placeholder_comments,
+        comment_diffs=dict(sorted(comment_diffs.items())),
+        comment_similarities=dict(sorted(comment_similarities.items())),
         missing_pre_commit_ci_commits=missing_pre_commit_ci_commits,
+        missing_trailer_commits=missing_trailer_commits,
+        unused_entries=dict(sorted(unused_entries.items())),
+        semantic_changes=dict(sorted(semantic_changes.items())),
+        metadata_errors=metadata_errors,
+        pr_link_errors=dict(sorted(pr_link_errors.items())),
+        ticket_errors=ticket_errors,
+        duplicate_patches=duplicate_patches,
+        commented_out_hashes=commented_out_hashes,
+        blank_line_errors=blank_line_errors,
+        near_misses=near_misses,
+        suggested_hashes=suggested_hashes,
+        default_branch_errors=default_branch_errors,
+        reachability_errors=reachability_errors,
+        unreferenced_commits=unreferenced_commits,
+        section_errors=section_errors,
+        rename_heavy_commits=rename_heavy_commits,
+        formatting_scores=formatting_scores,
+        entries_added_early=entries_added_early,
+        old_entries=old_entries,
+        denied_entries=dict(sorted(denied_entries.items())),
+        spelling_errors=spelling_errors,
     )
 
 
-def get_last_comment(lines: list[str], line_number: int) -> str:
-    return lines[line_number - 2].strip().lstrip("#").strip() if line_number > 1 else ""
+def blank_line_problems(lines: Iterable[str]) -> HashEntries:
+    """
+    Checks that blank lines separate the groups of entries in a `.git-blame-ignore-revs` file.
+
+    The style is exactly one blank line between groups, where a group is a
+    block of comments and the entries they describe, and none between a
+    comment and its entry or at the start or end of the file. So every
+    comment that directly follows an entry must have a blank line above it.
+
+    Args:
+        lines (Iterable[str]): The lines of the file.
+
+    Returns:
+        HashEntries: A dictionary mapping the first line of each problem to its description.
+    """
+    parsed_lines = list(parse_lines(lines))
+    problems: HashEntries = {}
+    # The line before each run of blank lines that isn't blank, if any
+    previous: Optional[ParsedLine] = None
+    blank_run: list[ParsedLine] = []
+    for parsed_line in parsed_lines:
+        if parsed_line.kind is LineKind.Blank:
+            blank_run.append(parsed_line)
+            continue
+        if blank_run:
+            first = blank_run[0].line_number
+            if previous is None:
+                problems[first] = "blank line at the start of the file"
+            elif previous.kind is LineKind.Comment and parsed_line.kind is LineKind.Hash:
+                problems[first] = "blank line between a comment and the entry it describes"
+            elif len(blank_run) > 1:
+                problems[first] = f"{len(blank_run)} blank lines in a row (expected 1)"
+        elif (
+            previous is not None
+            and previous.kind is not LineKind.Comment
+            and parsed_line.kind is LineKind.Comment
+        ):
+            problems[parsed_line.line_number] = "no blank line before the comment"
+        previous = parsed_line
+        blank_run = []
+    if blank_run and previous is not None:
+        problems[blank_run[0].line_number] = "blank line at the end of the file"
+    return problems
+
+
+def tracking_problems(file_path: Union[str, Path], git: Optional[GitBackend] = None) -> list[str]:
+    """
+    Checks that a `.git-blame-ignore-revs` file is committed and not ignored by Git.
+
+    A file that is untracked or only staged gives nobody else the benefit of
+    the ignored revisions, and one matched by `.gitignore` is easily lost.
+
+    Args:
+        file_path (Union[str, Path]): Path to the `.git-blame-ignore-revs` file.
+        git (Optional[GitBackend]): How the repository is looked up. Defaults to running `git`.
+
+    Returns:
+        list[str]: A description of each problem found, or an empty list.
+
+    Raises:
+        GitNotFoundError: If the `git` executable cannot be run.
+    """
+    file_path = Path(file_path).resolve()
+    if git is None:
+        git = SubprocessGit()
+    git.enter(file_path.parent)
+
+    problems: list[str] = []
+    if not git.is_tracked(file_path.name):
+        problems.append("not tracked by Git")
+    elif not git.is_committed(file_path.name):
+        problems.append("staged but not committed")
+    rule = git.ignore_rule(file_path.name)
+    if rule is not None:
+        problems.append(f"matched by the ignore rule {rule}")
+    return problems
+
+
+def empty_result() -> ValidationResult:
+    """Return a result for a file with no entries in any field, to extend with findings."""
+    return cast(ValidationResult, {field: {} for field in ValidationResult.__annotations__})
+
+
+def add_finding(result: ValidationResult, field: str, key: Any, value: Any) -> None:
+    """
+    Adds one entry to a field of the result for a file, such as a finding of a check of its own.
+
+    Entries are keyed by line number, or by commit hash for the fields about commits that
+    aren't listed, such as `missing_pre_commit_ci_commits`.
+
+    Raises:
+        KeyError: If `field` isn't a field of `ValidationResult`.
+        ValueError: If the field already has a different entry for `key`.
+    """
+    if field not in ValidationResult.__annotations__:
+        raise KeyError(f"{field!r} isn't a field of the results")
+    entries = cast(dict[str, dict[Any, Any]], result).setdefault(field, {})
+    if key in entries and entries[key] != value:
+        raise ValueError(f"The results already have a different entry for {key!r} in {field}")
+    entries[key] = value
+
+
+def extend_result(result: ValidationResult, other: ValidationResult) -> None:
+    """
+    Adds the entries of another result for the same file, such as that of a run with other checks.
+
+    Raises:
+        ValueError: If both results have different entries for the same key of a field.
+    """
+    for field, entries in cast(dict[str, dict[Any, Any]], other).items():
+        for key, value in entries.items():
+            add_finding(result, field, key, value)
+
+
+def merge_result(*results: ValidationResult) -> ValidationResult:
+    """
+    Combines several results for the same file, such as those of runs with different checks.
+
+    Raises:
+        ValueError: If two of the results have different entries for the same key of a field.
+    """
+    merged = empty_result()
+    for result in results:
+        extend_result(merged, result)
+    return merged
+
+
+def result_summary(result: ValidationResult) -> dict[str, int]:
+    """Return the number of entries in each field of the result for a file."""
+    return {field: len(entries) for field, entries in cast(dict[str, Sized], result).items()}
+
+
+def add_result(workspace: WorkspaceResult, name: str, result: ValidationResult) -> None:
+    """
+    Adds the result for one file to the results for several, counting its entries in the summary.
+
+    Raises:
+        ValueError: If the results already have a file of the same name.
+    """
+    if name in workspace["files"]:
+        raise ValueError(f"The results already have a file named {name!r}")
+    workspace["files"][name] = result
+    for field, count in result_summary(result).items():
+        workspace["summary"][field] = workspace["summary"].get(field, 0) + count
+
+
+def merge_results(*workspaces: WorkspaceResult) -> WorkspaceResult:
+    """
+    Combines the results for several sets of files, such as those of each repository validated.
+
+    Raises:
+        ValueError: If a file of the same name is in more than one of them. Give the files of each repository distinct names, such as by prefixing the name of the repository.
+    """
+    merged = WorkspaceResult(files={}, summary={})
+    for workspace in workspaces:
+        for name, result in workspace["files"].items():
+            add_result(merged, name, result)
+    return merged
+
+
+def _count(result: Union[ValidationResult, WorkspaceResult], fields: Iterable[str]) -> int:
+    if "summary" in result:
+        summary = cast(WorkspaceResult, result)["summary"]
+        return sum(summary.get(field, 0) for field in fields)
+    entries = cast(dict[str, Sized], result)
+    return sum(len(entries[field]) for field in fields if field in entries)
+
+
+def error_count(result: Union[ValidationResult, WorkspaceResult]) -> int:
+    """Return the number of problems in the result for a file, or for several, that are errors."""
+    return _count(result, _ERROR_FIELDS)
+
+
+def warning_count(result: Union[ValidationResult, WorkspaceResult]) -> int:
+    """Return the number of problems in the result for a file, or for several, that are warnings."""
+    return _count(result, _WARNING_FIELDS)
+
+
+def info_count(result: Union[ValidationResult, WorkspaceResult]) -> int:
+    """Return the number of findings in the result for a file, or for several, that are info."""
+    return _count(result, _INFO_FIELDS)
+
+
+def is_clean(result: Union[ValidationResult, WorkspaceResult]) -> bool:
+    """
+    Return whether the result for a file, or for several, has neither errors nor warnings.
+
+    Info, such as possible misspellings, doesn't count, since it never fails a run.
+    Scored checks whose threshold is applied when reporting, such as the
+    formatting likelihood of `score_formatting`, aren't counted either.
+    """
+    return not error_count(result) and not warning_count(result)
+
+
+def validate_files(
+    file_paths: Iterable[Union[str, Path]], options: Optional[ValidationOptions] = None
+) -> WorkspaceResult:
+    """
+    Validates several `.git-blame-ignore-revs` files, such as those of each repository in a workspace.
+
+    Args:
+        file_paths (Iterable[Union[str, Path]]): Paths to the `.git-blame-ignore-revs` files. Relative paths are relative to the current directory when this is called.
+        options (Optional[ValidationOptions]): Which checks to run on every file, and how. Defaults to only checking the syntax.
+
+    Returns:
+        WorkspaceResult: A dictionary containing the result for each file, and the number of entries in each field of the results added up over every file.
+    """
+    # Checks that use Git change directory, so resolve every path before running any of them
+    resolved = [(str(file_path), Path(file_path).resolve()) for file_path in file_paths]
+
+    workspace = WorkspaceResult(files={}, summary={})
+    seen: set[str] = set()
+    for name, file_path in resolved:
+        # A file given more than once is only validated once, however its path is written
+        if path_key(file_path) not in seen:
+            seen.add(path_key(file_path))
+            add_result(workspace, name, validate_git_blame_ignore_revs(file_path, options))
+    return workspace
@@ -0,0 +1,41 @@
+import re
+from collections.abc import Iterable
+from typing import TypedDict
+
+__all__ = (
+    "MetadataPolicy",
+    "check_metadata",
+    "parse_metadata",
+)
+
+# A comment of the form `Key: value`
+_metadata_regex = re.compile(r"^([A-Za-z][\w-]*):\s*(.*)$")
+
+
+class MetadataPolicy(TypedDict):
+    required_keys: list[str]
+    formats: dict[str, str]  # Key -> Regular expression the whole value must match
+
+
+def parse_metadata(comments: Iterable[str]) -> dict[str, str]:
+    """Return the `Key: value` pairs in the comments above an entry.
+
+    Comments are given without the leading "#". Other comments are ignored,
+    and if a key appears more than once, the last value wins.
+    """
+    metadata: dict[str, str] = {}
+    for comment in comments:
+        match = _metadata_regex.match(comment)
+        if match:
+            metadata[match.group(1)] = match.group(2).strip()
+    return metadata
+
+
+def check_metadata(comments: Iterable[str], policy: MetadataPolicy) -> list[str]:
+    """Return a description of each way the metadata in `comments` violates `policy`."""
+    metadata = parse_metadata(comments)
+    problems = [f"missing {key}" for key in policy["required_keys"] if key not in metadata]
+    for key, pattern in policy["formats"].items():
+        if key in metadata and not re.fullmatch(pattern, metadata[key]):
+            problems.append(f"{key} {metadata[key]!r} does not match {pattern!r}")
+    return problems
@@ -0,0 +1,119 @@
+import threading
+from bisect import bisect_left
+from collections import Counter
+from collections.abc import Iterable
+from typing import Optional
+
+from validate_git_blame_ignore_revs.cache import CacheStats
+from validate_git_blame_ignore_revs.report import Section
+
+__all__ = (
+    "CONTENT_TYPE",
+    "LATENCY_BUCKETS",
+    "Metrics",
+)
+
+# The media type of the OpenMetrics text format, which Prometheus asks for when scraping
+CONTENT_TYPE = "application/openmetrics-text; version=1.0.0; charset=utf-8"
+
+# The upper bounds, in seconds, of the buckets of the histogram of how long requests to `/validate` take
+LATENCY_BUCKETS = (0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0)
+
+# Every metric's name starts with this, so they can't clash with those of other services
+_PREFIX = "validate_git_blame_ignore_revs"
+
+
+def _labels(**labels: str) -> str:
+    pairs = (f'{name}="{_escape(value)}"' for name, value in labels.items())
+    return "{" + ",".join(pairs) + "}"
+
+
+def _escape(value: str) -> str:
+    # Label values escape backslashes, quotes, and line breaks
+    return value.replace("\\", "\\\\").replace('"', '\\"').replace("\n", "\\n")
+
+
+class Metrics:
+    """
+    Counts what `serve` does, for monitoring it with Prometheus or any tool that reads OpenMetrics.
+
+    Validations are counted by repository and outcome, findings by check and
+    severity, and requests to `/validate` by status, with a histogram of how
+    long they take. With `cache_stats`, the lookups of `--cache` are counted
+    as hits and misses, from which the hit rate follows. Repositories are only
+    the ones `serve` was given, so the number of series stays bounded.
+    """
+
+    def __init__(self, cache_stats: Optional[CacheStats] = None) -> None:
+        self.cache_stats = cache_stats
+        self._lock = threading.Lock()
+        # (Repository, Outcome) -> Number of files validated
+        self._validations: Counter[tuple[str, str]] = Counter()
+        # (Check title, Severity) -> Number of findings
+        self._findings: Counter[tuple[str, str]] = Counter()
+        # HTTP status -> Number of requests to /validate
+        self._requests: Counter[int] = Counter()
+        # Number of requests whose duration is at most each bucket's bound, not yet cumulative
+        self._latency_buckets = [0] * (len(LATENCY_BUCKETS) + 1)
+        self._latency_sum = 0.0
+
+    def record_validation(
+        self, repository: Optional[str], sections: Iterable[Section], exit_code: int
+    ) -> None:
+        """Count a file that was validated, and the findings of each check on it."""
+        outcome = "failed" if exit_code else "passed"
+        with self._lock:
+            self._validations[(repository or "", outcome)] += 1
+            for section in sections:
+                if section.findings:
+                    key = (section.title, section.severity.value)
+                    self._findings[key] += len(section.findings)
+
+    def record_request(self, status: int, seconds: float) -> None:
+        """Count a request to `/validate` and how long it took to answer."""
+        with self._lock:
+            self._requests[status] += 1
+            self._latency_buckets[bisect_left(LATENCY_BUCKETS, seconds)] += 1
+            self._latency_sum += seconds
+
+    def render(self) -> str:
+        """Return the metrics in the OpenMetrics text format."""
+        lines: list[str] = []
+
+        def family(name: str, kind: str, help_text: str) -> str:
+            lines.append(f"# TYPE {_PREFIX}_{name} {kind}")
+            lines.append(f"# HELP {_PREFIX}_{name} {help_text}")
+            return f"{_PREFIX}_{name}"
+
+        with self._lock:
+            name = family("validations", "counter", "Files validated, by repository and outcome.")
+            for (repository, outcome), count in sorted(self._validations.items()):
+                labels = _labels(repository=repository, outcome=outcome)
+                lines.append(f"{name}_total{labels} {count}")
+
+            name = family("findings", "counter", "Findings reported, by check and severity.")
+            for (check, severity), count in sorted(self._findings.items()):
+                lines.append(f"{name}_total{_labels(check=check, severity=severity)} {count}")
+
+            name = family("requests", "counter", "Requests to /validate, by HTTP status.")
+            for status, count in sorted(self._requests.items()):
+                lines.append(f"{name}_total{_labels(status=str(status))} {count}")
+
+            name = family(
+                "request_duration_seconds", "histogram", "How long requests to /validate take."
+            )
+            cumulative = 0
+            bounds = [*map(repr, LATENCY_BUCKETS), "+Inf"]
+            for bound, count in zip(bounds, self._latency_buckets):
+                cumulative += count
+                lines.append(f"{name}_bucket{_labels(le=bound)} {cumulative}")
+            lines.append(f"{name}_sum {self._latency_sum!r}")
+            lines.append(f"{name}_count {cumulative}")
+
+        if self.cache_stats is not None:
+            name = family("cache_lookups", "counter", "Lookups of cached results, by result.")
+            lines.append(f"{name}_total{_labels(result='hit')} {self.cache_stats.hits}")
+            lines.append(f"{name}_total{_labels(result='miss')} {self.cache_stats.misses}")
+
+        lines.append("# EOF")
+        return "\n".join(lines) + "\n"
@@ -0,0 +1,56 @@
+import os
+import sys
+import tempfile
+from pathlib import Path
+from typing import Optional
+
+__all__ = (
+    "append_output",
+    "write_output",
+)
+
+
+def write_output(
+    destination: str, text: str, newline: Optional[str] = None, encoding: str = "utf-8"
+) -> None:
+    """
+    Writes a report to a file, or to standard output when `destination` is `-`.
+
+    Missing parent directories are created. The text is written to a
+    temporary file in the same directory that then replaces the destination,
+    so a CI job never picks up a partly written report. Line breaks are
+    written as `newline` if it is given, like `open` does, and otherwise as
+    the platform's, and the text is encoded as `encoding`.
+
+    Raises:
+        OSError: If the file cannot be written.
+    """
+    if destination == "-":
+        sys.stdout.write(text)
+        return
+
+    path = Path(destination)
+    path.parent.mkdir(parents=True, exist_ok=True)
+    fd, temp_name = tempfile.mkstemp(dir=path.parent, prefix=f".{path.name}.", suffix=".tmp")
+    try:
+        # mkstemp makes the file private, but the report should be as readable as any other file
+        umask = os.umask(0)
+        os.umask(umask)
+        os.chmod(temp_name, 0o666 & ~umask)
+        with os.fdopen(fd, "w", encoding=encoding, newline=newline) as temp_file:
+            temp_file.write(text)
+        os.replace(temp_name, path)
+    except BaseException:
+        os.unlink(temp_name)
+        raise
+
+
+def append_output(path: str, text: str) -> None:
+    """
+    Appends a report to a file that other tools also write to, such as a CI job summary.
+
+    Raises:
+        OSError: If the file cannot be written.
+    """
+    with open(path, "a", encoding="utf-8") as file:
+        file.write(text)
@@ -0,0 +1,89 @@
+import ntpath
+import os
+import sys
+from functools import lru_cache
+from pathlib import Path
+from typing import Optional, Union
+
+__all__ = (
+    "WINDOWS",
+    "find_executable",
+    "is_git_entry",
+    "path_key",
+    "strip_long_path_prefix",
+)
+
+# Whether paths follow the rules of Windows: drive letters, UNC shares, and names that ignore case
+WINDOWS = sys.platform == "win32"
+
+# What Windows puts in front of an absolute path to lift the limit of 260 characters,
+# as `\\?\C:\repo` or `\\?\UNC\server\share\repo`
+_LONG_PATH_PREFIXES = ("\\\\?\\", "//?/")
+
+
+def strip_long_path_prefix(path: Union[str, Path]) -> str:
+    r"""
+    Returns a path without the `\\?\` prefix Windows uses for long paths.
+
+    `Path.resolve` adds the prefix to paths longer than 260 characters, and
+    users may pass such paths themselves, but Git doesn't accept them, and
+    they are hard to read in a report. `\\?\C:\repo` becomes `C:\repo`, and
+    `\\?\UNC\server\share` becomes `\\server\share`. Other paths are
+    returned as they are.
+    """
+    text = os.fspath(path)
+    for prefix in _LONG_PATH_PREFIXES:
+        if text.startswith(prefix):
+            text = text[len(prefix) :]
+            if text[:4].upper() in ("UNC\\", "UNC/"):
+                return f"\\\\{text[4:]}"
+            return text
+    return text
+
+
+def path_key(path: Union[str, Path]) -> str:
+    r"""
+    Returns a key that is the same for every way of writing the path of a file.
+
+    Symbolic links, `..`, and relative paths are resolved. On Windows, where
+    file names ignore case, `C:\Repo\File` and `c:/repo/file` have the same key.
+    """
+    resolved = strip_long_path_prefix(os.path.realpath(path))
+    return ntpath.normcase(resolved) if WINDOWS else resolved
+
+
+def is_git_entry(name: str) -> bool:
+    """
+    Return whether a file or directory is the `.git` that makes the directory it is in a repository.
+
+    On Windows, where file names ignore case, Git also finds repositories
+    through `.GIT` and any other spelling.
+    """
+    return name.casefold() == ".git" if WINDOWS else name == ".git"
+
+
+@lru_cache(maxsize=None)
+def find_executable(name: str, search_path: str, extensions: str) -> Optional[str]:
+    """
+    Finds a program as Windows does, but never in the current directory.
+
+    Each directory of `search_path`, a `PATH`, is searched for the name with
+    each extension of `extensions`, a `PATHEXT` such as `.EXE;.CMD`, in order.
+    Running a program by name would also find it in the current directory
+    first, which for this tool is a repository that may not be trusted, and
+    wouldn't find `.cmd` and `.bat` wrappers, which some installers of Git
+    put on the `PATH`. Relative directories are skipped for the same reason.
+
+    Returns:
+        Optional[str]: The path of the program, or None if it isn't found.
+    """
+    for directory in search_path.split(os.pathsep):
+        # Windows allows directories with separators in them to be quoted
+        directory = directory.strip().strip('"')
+        if not directory or not os.path.isabs(directory):
+            continue
+        for extension in extensions.split(os.pathsep):
+            candidate = os.path.join(directory, f"{name}{extension.strip().lower()}")
+            if os.path.isfile(candidate):
+                return candidate
+    return None
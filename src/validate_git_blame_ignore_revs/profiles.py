@@ -0,0 +1,62 @@
+from typing import Any, NamedTuple
+
+from validate_git_blame_ignore_revs.config import Config, EmptyFilePolicy, default_config
+
+__all__ = (
+    "PROFILES",
+    "Profile",
+    "profile_config",
+)
+
+
+class Profile(NamedTuple):
+    # Command-line option (as its argparse destination) -> Value. Options given explicitly win
+    options: dict[str, Any]
+    # Used when neither --empty-file nor the configuration file says otherwise
+    empty_file: EmptyFilePolicy
+
+
+_CI_OPTIONS = {
+    "call_git": True,
+    "check_commented_out": True,
+    "check_tracked": True,
+}
+
+_STRICT_OPTIONS = {
+    **_CI_OPTIONS,
+    "strict_comments": True,
+    "strict_comments_git": True,
+    "check_duplicate_patches": True,
+}
+
+# Name -> Profile, from the most lenient to the strictest
+PROFILES = {
+    # Only the syntax of each line
+    "minimal": Profile({}, "allow"),
+    # Also that commits are in the history, the file is committed, and nothing was commented out
+    "ci": Profile(_CI_OPTIONS, "error"),
+    # Also that comments match commit messages, and that no change is listed twice
+    "strict": Profile(_STRICT_OPTIONS, "error"),
+    # Also the checks that are slow or depend on how the project works
+    "pedantic": Profile(
+        {
+            **_STRICT_OPTIONS,
+            "report_unused_entries": True,
+            "check_semantic_changes": True,
+            "check_renames": True,
+            "score_formatting": True,
+            "check_entry_dates": True,
+            "check_default_branch": True,
+            "check_unreferenced": True,
+            "require_pr_link": True,
+        },
+        "error",
+    ),
+}
+
+
+def profile_config(name: str) -> Config:
+    """Return the settings a profile uses for those the configuration file doesn't have."""
+    config = default_config()
+    config["empty_file"] = PROFILES[name].empty_file
+    return config
@@ -0,0 +1,43 @@
+from collections.abc import Callable
+from typing import Optional, TextIO
+
+__all__ = (
+    "Progress",
+    "ProgressBar",
+    "progress_bar",
+)
+
+# Called with what is being done, the number of steps done, and the total number of steps
+Progress = Callable[[str, int, int], None]
+
+
+class ProgressBar:
+    """Shows the progress of a slow step on one line of a terminal, redrawn in place."""
+
+    width = 30
+
+    def __init__(self, stream: TextIO) -> None:
+        self.stream = stream
+        self.shown = False
+
+    def __call__(self, label: str, done: int, total: int) -> None:
+        filled = self.width * done // total if total else self.width
+        bar = "#" * filled + "-" * (self.width - filled)
+        # Return to the start of the line and erase what was drawn before
+        self.stream.write(f"\r\x1b[K{label} [{bar}] {done}/{total}")
+        self.shown = True
+        if done >= total:
+            self.clear()
+        self.stream.flush()
+
+    def clear(self) -> None:
+        """Erase the bar, such as when the step is done or was interrupted."""
+        if self.shown:
+            self.stream.write("\r\x1b[K")
+            self.stream.flush()
+            self.shown = False
+
+
+def progress_bar(stream: TextIO) -> Optional[ProgressBar]:
+    """Return a progress bar drawn on `stream` if it is a terminal, or None otherwise."""
+    return ProgressBar(stream) if stream.isatty() else None
@@ -0,0 +1,88 @@
+from typing import Optional, TypedDict
+
+from validate_git_blame_ignore_revs.config import DEFAULT_FILE_NAME
+from validate_git_blame_ignore_revs.forge import (
+    ForgeRepository,
+    commit_exists,
+    file_contents,
+    parse_remote_url,
+)
+from validate_git_blame_ignore_revs.forge_api import ForgeClient
+from validate_git_blame_ignore_revs.lib import HashEntries, LineKind, parse_lines
+from validate_git_blame_ignore_revs.sources import decode_contents
+
+__all__ = (
+    "RemoteResult",
+    "parse_repository",
+    "validate_remote",
+)
+
+
+class RemoteResult(TypedDict):
+    valid_hashes: HashEntries
+    errors: HashEntries  # Line number -> Text of each line that is neither a hash nor a comment
+    missing_commits: HashEntries  # Line number -> Hash that names no commit in the repository
+
+
+def parse_repository(value: str, host: str) -> Optional[ForgeRepository]:
+    """
+    Return the repository `owner/name` names on a host, or the one a URL points to.
+
+    The owner of a GitLab repository can be a subgroup, such as `group/subgroup/name`.
+    """
+    if "://" in value or "@" in value:
+        return parse_remote_url(value)
+    owner, _, name = value.strip("/").rpartition("/")
+    if not owner or not name:
+        return None
+    return ForgeRepository(host, owner, name.removesuffix(".git"))
+
+
+def validate_remote(
+    repository: ForgeRepository,
+    path: str = DEFAULT_FILE_NAME,
+    ref: Optional[str] = None,
+    client: Optional[ForgeClient] = None,
+) -> Optional[RemoteResult]:
+    """
+    Validates a `.git-blame-ignore-revs` file in a GitHub or GitLab repository through its API alone.
+
+    The file is read from the repository and each hash it lists is looked up
+    as a commit, so a repository can be checked without cloning it. Each
+    commit is looked up once, however many times it is listed.
+
+    Args:
+        repository (ForgeRepository): The repository on the forge.
+        path (str): The path of the file from the top level of the repository.
+        ref (Optional[str]): The branch, tag, or commit to read the file from. Defaults to the default branch.
+        client (Optional[ForgeClient]): The client for the forge. Defaults to a new one for the host of the repository.
+
+    Returns:
+        Optional[RemoteResult]: The hashes that are commits, the syntax errors, and the missing commits, by line number, or None if the repository or the file doesn't exist.
+
+    Raises:
+        OSError: If the API cannot be reached.
+        UnicodeDecodeError: If the file isn't valid UTF-8.
+        UnknownForgeError: If no client is given and the host isn't a known forge.
+    """
+    if client is None:
+        client = ForgeClient(repository.host)
+    contents = file_contents(repository, path, ref, client)
+    if contents is None:
+        return None
+
+    result = RemoteResult(valid_hashes={}, errors={}, missing_commits={})
+    # Hash -> Whether the repository has the commit
+    exists: dict[str, bool] = {}
+    for parsed_line in parse_lines(decode_contents(contents).splitlines()):
+        if parsed_line.kind is LineKind.Invalid:
+            result["errors"][parsed_line.line_number] = parsed_line.text
+        elif parsed_line.kind is LineKind.Hash:
+            commit_hash = parsed_line.text
+            if commit_hash not in exists:
+                exists[commit_hash] = commit_exists(repository, commit_hash, client)
+            if exists[commit_hash]:
+                result["valid_hashes"][parsed_line.line_number] = commit_hash
+            else:
+                result["missing_commits"][parsed_line.line_number] = commit_hash
+    return result
@@ -0,0 +1,246 @@
+from collections import Counter
+from collections.abc import Iterable
+from enum import Enum
+from typing import NamedTuple, Optional
+
+from validate_git_blame_ignore_revs.i18n import Translate, untranslated
+
+__all__ = (
+    "GROUP_BY",
+    "SORT_BY",
+    "FileReport",
+    "Finding",
+    "Section",
+    "Severity",
+    "exit_code",
+    "hyperlink",
+    "line_finding",
+    "new_findings",
+    "render_report",
+)
+
+# Ways the findings can be grouped and sorted, the first being the default
+GROUP_BY = ("check", "line", "severity")
+SORT_BY = ("line", "severity", "hash")
+
+
+class Severity(Enum):
+    Error = "error"  # The file is malformed or lists commits Git can't use
+    Warning = "warning"  # An entry doesn't follow a convention or policy that was asked for
+    Info = "info"  # Worth a look, but never fails the run
+
+
+# The heading of the findings of each severity with --group-by severity, before translation
+_SEVERITY_HEADINGS = {
+    Severity.Error: "Errors ({count}):",
+    Severity.Warning: "Warnings ({count}):",
+    Severity.Info: "Info ({count}):",
+}
+
+
+class Finding(NamedTuple):
+    label: str  # What the finding is about, such as "Line 4" or "Commit <hash>"
+    commit: str  # The hash or line the finding is about, for sorting
+    text: str = ""  # Shown after the label
+    details: tuple[str, ...] = ()  # Shown on lines of their own below the label
+    line_number: Optional[int] = None  # None for findings about commits not in the file
+    url: Optional[str] = None  # The page showing the commit on the forge that hosts the repository
+    reason: Optional[str] = None  # Why the entry isn't usable, such as "object-not-found"
+
+
+class Section(NamedTuple):
+    title: str
+    severity: Severity
+    error_code: int  # Bits of the exit code set when there are findings
+    findings: list[Finding]
+    success: Optional[str]  # Shown instead of the findings when there are none
+
+
+class FileReport(NamedTuple):
+    path: str  # The path of the file as it should appear in the report
+    sections: list[Section]  # Empty when the file couldn't be checked at all
+    notes: list[str]  # Messages about the file as a whole, such as checks that were skipped
+    exit_code: int
+
+
+def line_finding(
+    line_number: int,
+    commit: str,
+    text: str = "",
+    details: tuple[str, ...] = (),
+    reason: Optional[str] = None,
+) -> Finding:
+    """Return a finding about the entry on a line of the file."""
+    return Finding(f"Line {line_number}", commit, text, details, line_number, reason=reason)
+
+
+def exit_code(sections: Iterable[Section]) -> int:
+    """Combine the error codes of the sections that have findings."""
+    code = 0
+    for section in sections:
+        if section.findings:
+            code |= section.error_code
+    return code
+
+
+def new_findings(
+    sections: list[Section], base_sections: list[Section]
+) -> tuple[list[Section], int]:
+    """
+    Leave out the findings that the checks also made on a base version of the file.
+
+    Findings are matched by check and by the commit or line they are about
+    rather than by line number, since entries move as others are added above them.
+
+    Args:
+        sections (list[Section]): The findings of each check on the file.
+        base_sections (list[Section]): The findings of the same checks on the base version.
+
+    Returns:
+        tuple[list[Section], int]: The sections with only the new findings, and the number left out.
+    """
+    base = Counter(
+        (section.title, finding.commit) for section in base_sections for finding in section.findings
+    )
+    new_sections: list[Section] = []
+    existing = 0
+    for section in sections:
+        findings: list[Finding] = []
+        for finding in section.findings:
+            if base[section.title, finding.commit]:
+                base[section.title, finding.commit] -= 1
+                existing += 1
+            else:
+                findings.append(finding)
+        new_sections.append(section._replace(findings=findings))
+    return new_sections, existing
+
+
+def _line_key(finding: Finding) -> tuple[bool, int, str]:
+    # Findings about commits that aren't in the file go last
+    return (finding.line_number is None, finding.line_number or 0, finding.label)
+
+
+def _sort_key(sort: str, section: Section, finding: Finding) -> tuple[object, ...]:
+    if sort == "severity":
+        return (list(Severity).index(section.severity), *_line_key(finding))
+    if sort == "hash":
+        return (finding.commit, *_line_key(finding))
+    return _line_key(finding)
+
+
+def hyperlink(text: str, url: str) -> str:
+    """Return `text` marked up as a link to `url` for terminals that support OSC 8 hyperlinks."""
+    return f"\x1b]8;;{url}\x1b\\{text}\x1b]8;;\x1b\\"
+
+
+def _format(heading: str, finding: Finding, indent: str, hyperlinks: bool) -> list[str]:
+    lines = [f"{indent}{heading}: {finding.text}" if finding.text else f"{indent}{heading}:"]
+    lines.extend(f"{indent}  {detail}" for detail in finding.details)
+    if hyperlinks and finding.url is not None:
+        link = hyperlink(finding.commit, finding.url)
+        lines = [line.replace(finding.commit, link) for line in lines]
+    return lines
+
+
+def _label(finding: Finding, translate: Translate) -> str:
+    # Only the labels of findings about lines are translated, since the others name commits
+    if finding.line_number is not None and finding.label == f"Line {finding.line_number}":
+        return translate("Line {line}").format(line=finding.line_number)
+    return finding.label
+
+
+def _display_order(
+    pairs: list[tuple[Section, Finding]], sections: list[Section], group_by: str
+) -> list[tuple[Section, Finding]]:
+    """Order sorted findings the way they are laid out, group by group."""
+    if group_by == "check":
+        return [pair for section in sections for pair in pairs if pair[0] is section]
+    if group_by == "line":
+        labels = list(dict.fromkeys(finding.label for _, finding in pairs))
+        return [pair for label in labels for pair in pairs if pair[1].label == label]
+    return [pair for severity in Severity for pair in pairs if pair[0].severity is severity]
+
+
+def render_report(
+    sections: list[Section],
+    group_by: str = "check",
+    sort: str = "line",
+    max_findings: Optional[int] = None,
+    hyperlinks: bool = False,
+    translate: Translate = untranslated,
+) -> list[str]:
+    """
+    Lays out the findings of each check as lines of text.
+
+    The titles of the checks, their messages when they pass, and the
+    headings are translated with `translate`. The findings themselves are
+    left as they are, since they quote the file and Git.
+
+    Args:
+        sections (list[Section]): The findings of each check that was run, in the order of the checks.
+        group_by (str): Whether to group findings by check, by the line they are about, or by severity.
+        sort (str): Whether to order the findings in each group by line, by severity, or by hash.
+        max_findings (Optional[int]): If given, the number of findings after which the rest are only counted.
+        hyperlinks (bool): If True, commit hashes are links to the commit on the forge, for terminals.
+        translate (Translate): How messages are translated, such as by a `translator` of `i18n`.
+
+    Returns:
+        list[str]: The lines of the report. Each group starts with a blank line.
+    """
+    pairs = [(section, finding) for section in sections for finding in section.findings]
+    pairs.sort(key=lambda pair: _sort_key(sort, *pair))
+    pairs = _display_order(pairs, sections, group_by)
+    hidden = 0
+    if max_findings is not None and len(pairs) > max_findings:
+        hidden = len(pairs) - max_findings
+        pairs = pairs[:max_findings]
+
+    lines: list[str] = []
+    if group_by == "check":
+        shown = 0
+        for section in sections:
+            if hidden and shown == len(pairs):
+                # The checks after the last finding shown are left out altogether
+                break
+            findings = [finding for owner, finding in pairs if owner is section]
+            shown += len(findings)
+            if findings:
+                lines.append(f"\n{translate(section.title)} ({len(section.findings)}):")
+                for finding in findings:
+                    lines.extend(_format(_label(finding, translate), finding, "  ", hyperlinks))
+            elif section.success is not None and not section.findings:
+                lines.append(f"\n{translate(section.success)}")
+    elif not pairs and not hidden:
+        lines.append(f"\n{translate('No problems found!')}")
+    else:
+        groups: dict[str, list[tuple[Section, Finding]]] = {}
+        for section, finding in pairs:
+            key = finding.label if group_by == "line" else section.severity.value
+            groups.setdefault(key, []).append((section, finding))
+
+        if group_by == "line":
+            for members in groups.values():
+                lines.append(f"\n{_label(members[0][1], translate)}:")
+                for section, finding in members:
+                    lines.extend(_format(translate(section.title), finding, "  ", hyperlinks))
+        else:
+            # Count every finding of each severity, including those that aren't shown
+            totals = {
+                severity: sum(
+                    len(section.findings) for section in sections if section.severity is severity
+                )
+                for severity in Severity
+            }
+            for severity in Severity:
+                members = groups.get(severity.value, [])
+                if members:
+                    heading = translate(_SEVERITY_HEADINGS[severity])
+                    lines.append(f"\n{heading.format(count=totals[severity])}")
+                    for section, finding in members:
+                        heading = f"{_label(finding, translate)}: {translate(section.title)}"
+                        lines.extend(_format(heading, finding, "  ", hyperlinks))
+
+    if hidden:
+        lines.append("\n" + translate("... and {count} more not shown.").format(count=hidden))
+    return lines
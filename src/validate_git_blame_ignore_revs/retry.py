@@ -0,0 +1,55 @@
+from typing import NamedTuple
+
+__all__ = (
+    "RetryError",
+    "RetryPolicy",
+    "attempts_summary",
+    "default_policy",
+    "set_default_policy",
+)
+
+
+class RetryPolicy(NamedTuple):
+    """
+    How often an operation that failed for what is likely a passing problem is tried again.
+
+    The first retry waits `initial_delay` seconds, and each one after that
+    waits twice as long as the one before, but never more than `max_delay`.
+    """
+
+    retries: int = 3
+    initial_delay: float = 1.0
+    max_delay: float = 30.0
+
+    def delay(self, retry: int) -> float:
+        """Return how long to wait before a retry, counting from 0."""
+        return min(self.initial_delay * 2**retry, self.max_delay)
+
+
+# The policy of Git commands and forge API requests that aren't given one
+_default_policy = RetryPolicy()
+
+
+def default_policy() -> RetryPolicy:
+    """Return the policy of Git commands and forge API requests that aren't given one."""
+    return _default_policy
+
+
+def set_default_policy(policy: RetryPolicy) -> None:
+    """Change the policy of every later Git command and forge API request that isn't given one."""
+    global _default_policy
+    _default_policy = policy
+
+
+def attempts_summary(attempts: int, seconds: float) -> str:
+    """Describe how often an operation was tried, such as `4 attempts over 7.0s`."""
+    return f"{attempts} attempts over {seconds:.1f}s"
+
+
+class RetryError(OSError):
+    """Raised when an operation still fails after being retried, caused by the last error."""
+
+    def __init__(self, operation: str, attempts: int, seconds: float, error: BaseException) -> None:
+        super().__init__(f"{operation} failed after {attempts_summary(attempts, seconds)}: {error}")
+        self.attempts = attempts
+        self.error = error
@@ -0,0 +1,119 @@
+import json
+import time
+from collections.abc import Callable
+from http import HTTPStatus
+from http.server import BaseHTTPRequestHandler, HTTPServer
+from subprocess import CalledProcessError
+from typing import Any, Optional
+
+from validate_git_blame_ignore_revs.git import GitNotFoundError
+from validate_git_blame_ignore_revs.metrics import CONTENT_TYPE, Metrics
+
+__all__ = (
+    "MAX_REQUEST_SIZE",
+    "BadRequest",
+    "ValidationServer",
+    "Validator",
+)
+
+# Requests with larger bodies are refused rather than read into memory
+MAX_REQUEST_SIZE = 1024 * 1024
+
+# Called with the identifier of the repository, if the request names one, and the contents of
+# the file. Returns the JSON report on the file
+Validator = Callable[[Optional[str], str], str]
+
+
+class BadRequest(ValueError):
+    """Raised by a `Validator` when a request can't be served, such as for an unknown repository."""
+
+
+class _Handler(BaseHTTPRequestHandler):
+    server: "ValidationServer"
+
+    def _send(self, status: HTTPStatus, body: str, content_type: str = "application/json") -> None:
+        data = body.encode("utf-8")
+        self.send_response(status)
+        self.send_header("Content-Type", content_type)
+        self.send_header("Content-Length", str(len(data)))
+        self.end_headers()
+        self.wfile.write(data)
+
+    def _send_error(self, status: HTTPStatus, message: str) -> None:
+        self._send(status, json.dumps({"error": message}) + "\n")
+
+    def do_GET(self) -> None:
+        if self.path != "/metrics" or self.server.metrics is None:
+            self._send_error(HTTPStatus.NOT_FOUND, f"No such endpoint: {self.path}")
+            return
+        self._send(HTTPStatus.OK, self.server.metrics.render(), CONTENT_TYPE)
+
+    def do_POST(self) -> None:
+        if self.path != "/validate":
+            self._send_error(HTTPStatus.NOT_FOUND, f"No such endpoint: {self.path}")
+            return
+        start = time.monotonic()
+        status = self._validate()
+        if self.server.metrics is not None:
+            self.server.metrics.record_request(status.value, time.monotonic() - start)
+
+    def _validate(self) -> HTTPStatus:
+        """Answer a request to `/validate`, returning the status of the response."""
+        try:
+            length = int(self.headers.get("Content-Length", ""))
+        except ValueError:
+            self._send_error(HTTPStatus.LENGTH_REQUIRED, "Content-Length is required")
+            return HTTPStatus.LENGTH_REQUIRED
+        if length > MAX_REQUEST_SIZE:
+            self._send_error(
+                HTTPStatus.REQUEST_ENTITY_TOO_LARGE,
+                f"Requests are limited to {MAX_REQUEST_SIZE} bytes",
+            )
+            return HTTPStatus.REQUEST_ENTITY_TOO_LARGE
+
+        try:
+            request: Any = json.loads(self.rfile.read(length))
+        except ValueError as e:
+            self._send_error(HTTPStatus.BAD_REQUEST, f"Invalid JSON: {e}")
+            return HTTPStatus.BAD_REQUEST
+        if not isinstance(request, dict) or not isinstance(request.get("content"), str):
+            self._send_error(HTTPStatus.BAD_REQUEST, '"content" must be a string')
+            return HTTPStatus.BAD_REQUEST
+        repository = request.get("repository")
+        if repository is not None and not isinstance(repository, str):
+            self._send_error(HTTPStatus.BAD_REQUEST, '"repository" must be a string')
+            return HTTPStatus.BAD_REQUEST
+
+        try:
+            report = self.server.validator(repository, request["content"])
+        except BadRequest as e:
+            self._send_error(HTTPStatus.BAD_REQUEST, str(e))
+            return HTTPStatus.BAD_REQUEST
+        except (GitNotFoundError, CalledProcessError, OSError) as e:
+            # Such as Git failing in the repository, which is no fault of the request
+            self._send_error(HTTPStatus.INTERNAL_SERVER_ERROR, str(e))
+            return HTTPStatus.INTERNAL_SERVER_ERROR
+        self._send(HTTPStatus.OK, report)
+        return HTTPStatus.OK
+
+
+class ValidationServer(HTTPServer):
+    """
+    Serves `POST /validate`, which validates the contents of a `.git-blame-ignore-revs` file.
+
+    The body of a request is a JSON object with the contents of the file as
+    `content` and, optionally, the identifier of the repository it belongs to
+    as `repository`. The response is the JSON report on the file, or an object
+    with an `error` if the request is invalid or Git fails. Requests are handled one at a
+    time, since the checks that use Git change the working directory.
+
+    With `metrics`, `GET /metrics` returns them in the OpenMetrics text
+    format, and each request to `/validate` is counted and timed.
+    """
+
+    def __init__(
+        self, address: tuple[str, int], validator: Validator, metrics: Optional[Metrics] = None
+    ) -> None:
+        super().__init__(address, _Handler)
+        self.validator = validator
+        self.metrics = metrics
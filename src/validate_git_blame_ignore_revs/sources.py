@@ -0,0 +1,185 @@
+import io
+from abc import ABC, abstractmethod
+from pathlib import Path
+from subprocess import CalledProcessError
+from typing import Optional, TextIO, Union
+from urllib.error import HTTPError
+from urllib.request import urlopen
+
+from validate_git_blame_ignore_revs.backends import GitBackend, SubprocessGit
+
+__all__ = (
+    "ENCODINGS",
+    "ContentSource",
+    "GitIndex",
+    "GitRevision",
+    "InMemory",
+    "RevisionNotFoundError",
+    "URLSource",
+    "WorkingTree",
+    "content_encoding",
+    "decode_contents",
+    "line_ending",
+)
+
+# Ways a file can be decoded, the first being the default
+ENCODINGS = ("utf-8", "latin1", "auto")
+
+
+def content_encoding(content: bytes, encoding: str = "utf-8") -> str:
+    """Return the encoding `decode_contents` decodes the contents of a file with.
+
+    This is `encoding` itself, except that `auto` is UTF-8 if the contents are
+    valid UTF-8, and otherwise Latin-1, which legacy files with accented names
+    in their comments often are.
+    """
+    if encoding == "auto":
+        try:
+            content.decode("utf-8")
+        except UnicodeDecodeError:
+            return "latin1"
+        return "utf-8"
+    return encoding
+
+
+def decode_contents(content: bytes, encoding: str = "utf-8") -> str:
+    """Decode the contents of a file as one of `ENCODINGS`, as described by `content_encoding`.
+
+    Raises:
+        UnicodeDecodeError: If the contents aren't valid in the encoding.
+    """
+    return content.decode(content_encoding(content, encoding))
+
+
+def line_ending(text: str) -> str:
+    """Return the line break a file uses, `\r\n` as on Windows if any line ends with it, or `\n`."""
+    return "\r\n" if "\r\n" in text else "\n"
+
+
+class ContentSource(ABC):
+    """Where the contents of a `.git-blame-ignore-revs` file are read from."""
+
+    @abstractmethod
+    def read_bytes(self, file_path: Path) -> bytes:
+        """Return the contents of the file at `file_path`, which is absolute.
+
+        Raises:
+            FileNotFoundError: If the source has no such file.
+        """
+
+    def read_text(self, file_path: Path, encoding: str = "utf-8") -> str:
+        """Return the contents of the file decoded as one of `ENCODINGS`.
+
+        Raises:
+            FileNotFoundError: If the source has no such file.
+            UnicodeDecodeError: If the contents aren't valid in the encoding.
+        """
+        return decode_contents(self.read_bytes(file_path), encoding)
+
+    def open_lines(self, file_path: Path, encoding: str = "utf-8") -> TextIO:
+        """Return the contents of the file as a stream of lines, decoded as one of `ENCODINGS`.
+
+        Lines are split the way reading a file in text mode does, so each ends
+        with `\n` whatever line breaks the file uses. The file is read once,
+        and the stream can be rewound to go through the lines again.
+
+        Raises:
+            FileNotFoundError: If the source has no such file.
+            UnicodeDecodeError: If the contents aren't valid in the encoding.
+        """
+        return io.StringIO(self.read_text(file_path, encoding), newline=None)
+
+    def __repr__(self) -> str:
+        # Also identifies the source in the key of cached results
+        return f"{type(self).__name__}()"
+
+
+class RevisionNotFoundError(FileNotFoundError):
+    """A revision to read files from doesn't name a commit or tree."""
+
+
+def _not_found(file_path: Path, where: str) -> FileNotFoundError:
+    return FileNotFoundError(2, f"No such file in {where}", str(file_path))
+
+
+class WorkingTree(ContentSource):
+    """The file as it is on disk."""
+
+    def read_bytes(self, file_path: Path) -> bytes:
+        return file_path.read_bytes()
+
+
+class GitIndex(ContentSource):
+    """The file as it is staged in the index of the repository that contains it."""
+
+    def __init__(self, git: Optional[GitBackend] = None) -> None:
+        self.git = git if git is not None else SubprocessGit()
+
+    def read_bytes(self, file_path: Path) -> bytes:
+        try:
+            return self.git.object_contents(f":./{file_path.name}", file_path.parent)
+        except CalledProcessError:
+            raise _not_found(file_path, "the index") from None
+
+
+class GitRevision(ContentSource):
+    """The file as it is in a commit or other tree-ish of the repository that contains it."""
+
+    def __init__(self, rev: str, git: Optional[GitBackend] = None) -> None:
+        self.rev = rev
+        self.git = git if git is not None else SubprocessGit()
+
+    def read_bytes(self, file_path: Path) -> bytes:
+        try:
+            return self.git.object_contents(f"{self.rev}:./{file_path.name}", file_path.parent)
+        except CalledProcessError:
+            pass
+        try:
+            self.git.object_contents(f"{self.rev}^{{tree}}", file_path.parent)
+        except CalledProcessError:
+            raise RevisionNotFoundError(
+                2, f"No commit or tree named {self.rev}", str(file_path)
+            ) from None
+        raise _not_found(file_path, self.rev)
+
+    def __repr__(self) -> str:
+        return f"GitRevision({self.rev!r})"
+
+
+class URLSource(ContentSource):
+    """A file served over HTTP, such as the raw view of a file on a forge, whatever the path."""
+
+    def __init__(self, url: str) -> None:
+        self.url = url
+
+    def read_bytes(self, file_path: Path) -> bytes:
+        try:
+            with urlopen(self.url, timeout=30) as response:
+                content: bytes = response.read()
+                return content
+        except HTTPError as e:
+            if e.code == 404:
+                raise _not_found(file_path, self.url) from None
+            raise
+
+    def __repr__(self) -> str:
+        return f"URLSource({self.url!r})"
+
+
+class InMemory(ContentSource):
+    """Files held in memory, keyed by path, such as for an editor buffer that hasn't been saved."""
+
+    def __init__(self, files: dict[Union[str, Path], Union[str, bytes]]) -> None:
+        self.files = {
+            Path(path).resolve(): content.encode("utf-8") if isinstance(content, str) else content
+            for path, content in files.items()
+        }
+
+    def read_bytes(self, file_path: Path) -> bytes:
+        try:
+            return self.files[file_path]
+        except KeyError:
+            raise _not_found(file_path, "memory") from None
+
+    def __repr__(self) -> str:
+        return f"InMemory({self.files!r})"
@@ -0,0 +1,84 @@
+import re
+from collections.abc import Container, Iterable
+from functools import lru_cache
+from pathlib import Path
+from typing import Optional, Union
+
+__all__ = (
+    "SYSTEM_DICTIONARIES",
+    "find_dictionary",
+    "load_dictionary",
+    "misspelled_words",
+)
+
+# Word lists installed on most Unix systems, as used by `look`
+SYSTEM_DICTIONARIES = ("/usr/share/dict/words", "/usr/dict/words")
+
+# What is left out of comments before checking them: URLs, email addresses, `@mentions`,
+# `#123` references, and code in backticks
+_skipped_regex = re.compile(r"\w+://\S+|\S+@\S+\.\w+|@[\w-]+|#\d+|`[^`]*`")
+
+# A word, possibly with apostrophes, such as "don't", or a token with digits or underscores
+_token_regex = re.compile(r"\w+(?:['’]\w+)*")
+
+# A word that is probably the name of something rather than prose: an acronym such as "CI",
+# or a word in camel case such as "GitHub"
+_name_regex = re.compile(r".+[A-Z]")
+
+
+def find_dictionary() -> Optional[Path]:
+    """Return the word list of the system, if there is one."""
+    for candidate in SYSTEM_DICTIONARIES:
+        path = Path(candidate)
+        if path.is_file():
+            return path
+    return None
+
+
+@lru_cache(maxsize=None)
+def load_dictionary(path: Union[str, Path]) -> frozenset[str]:
+    """
+    Reads a word list with one word on each line, such as `/usr/share/dict/words`.
+
+    Words are compared ignoring case, so they are kept in lowercase. Each
+    word list is only read once, however many files are checked with it.
+
+    Raises:
+        OSError: If the file cannot be read.
+    """
+    with Path(path).open(encoding="utf-8", errors="replace") as f:
+        return frozenset(line.strip().lower() for line in f if line.strip())
+
+
+def _is_known(word: str, *word_lists: Container[str]) -> bool:
+    word = word.lower().replace("’", "'")
+    # Possessives and contractions that word lists don't have, such as "ruff's"
+    stem, _, suffix = word.rpartition("'")
+    if not stem or suffix not in ("s", "ll", "d", "ve", "re"):
+        stem = word
+    return any(word in words or stem in words for words in word_lists)
+
+
+def misspelled_words(
+    text: str, dictionary: frozenset[str], project_words: Iterable[str] = ()
+) -> list[str]:
+    """
+    Return the words of a comment that are in neither the dictionary nor the project's word list.
+
+    URLs, email addresses, mentions, references such as `#123`, code in
+    backticks, words with digits, acronyms, and words in camel case are
+    skipped, since a dictionary can't be expected to have them. Each word is
+    only returned once.
+    """
+    project = {word.lower() for word in project_words}
+    misspelled: list[str] = []
+    for match in _token_regex.finditer(_skipped_regex.sub(" ", text)):
+        word = match.group()
+        # Identifiers and versions, such as "snake_case" or "py39"
+        if any(char.isdigit() or char == "_" for char in word):
+            continue
+        if _name_regex.match(word) or word in misspelled:
+            continue
+        if not _is_known(word, dictionary, project):
+            misspelled.append(word)
+    return misspelled
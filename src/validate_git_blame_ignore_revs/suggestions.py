@@ -0,0 +1,79 @@
+import re
+from subprocess import CalledProcessError
+from typing import NamedTuple, Optional
+
+from validate_git_blame_ignore_revs.backends import GitBackend, SubprocessGit
+
+__all__ = (
+    "NearMiss",
+    "near_miss",
+    "resolve_near_miss",
+)
+
+# Letters that are easily typed, or read by OCR, in place of a hexadecimal digit
+_LOOKALIKES = {"O": "0", "o": "0", "l": "1", "I": "1"}
+
+_hex_regex = re.compile(r"^[0-9a-f]*$")
+
+# Git needs at least this many hexadecimal digits to look up an abbreviated hash
+_MIN_PREFIX_LENGTH = 4
+
+
+class NearMiss(NamedTuple):
+    problem: str  # Such as "hash is 39 characters, expected 40"
+    candidates: tuple[str, ...]  # Full or abbreviated hashes that may be what was meant
+
+
+def near_miss(text: str) -> Optional[NearMiss]:
+    """Return what is wrong with an invalid line if it is almost a commit hash, or None.
+
+    Lines one character too short or too long, lines with a single character
+    that isn't hexadecimal, and lines with letters that look like digits are
+    near misses.
+    """
+    if _hex_regex.match(text):
+        if len(text) == 39:
+            return NearMiss("hash is 39 characters, expected 40", (text,))
+        if len(text) == 41:
+            return NearMiss("hash is 41 characters, expected 40", (text[:40], text[1:]))
+        return None
+    if len(text) != 40:
+        return None
+
+    lookalikes = [(i, c) for i, c in enumerate(text) if c in _LOOKALIKES]
+    corrected = "".join(_LOOKALIKES.get(c, c) for c in text)
+    if lookalikes and _hex_regex.match(corrected):
+        i, c = lookalikes[0]
+        more = f" and {len(lookalikes) - 1} more" if len(lookalikes) > 1 else ""
+        return NearMiss(
+            f"character {i + 1} is {c!r}, which looks like {_LOOKALIKES[c]!r}{more}", (corrected,)
+        )
+
+    non_hex = [(i, c) for i, c in enumerate(text) if not _hex_regex.match(c)]
+    if len(non_hex) == 1:
+        i, c = non_hex[0]
+        prefix = text[:i]
+        return NearMiss(
+            f"character {i + 1} is {c!r}, which isn't hexadecimal",
+            (prefix,) if len(prefix) >= _MIN_PREFIX_LENGTH else (),
+        )
+    return None
+
+
+def resolve_near_miss(
+    candidates: tuple[str, ...], git: Optional[GitBackend] = None
+) -> Optional[str]:
+    """Return the full hash of the only commit that any of the candidates names, or None.
+
+    Must be run inside the repository, or one `git` entered.
+    """
+    if git is None:
+        git = SubprocessGit()
+    commits: set[str] = set()
+    for candidate in candidates:
+        try:
+            names = [candidate] if len(candidate) == 40 else git.disambiguate(candidate)
+            commits.update(name for name in names if git.object_type(name) == "commit")
+        except CalledProcessError:
+            continue
+    return commits.pop() if len(commits) == 1 else None
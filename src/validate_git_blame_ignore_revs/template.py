@@ -0,0 +1,70 @@
+import re
+from string import Formatter
+from typing import Optional
+
+from validate_git_blame_ignore_revs.commits import CommitInfo
+from validate_git_blame_ignore_revs.forge import find_pull_request
+
+__all__ = (
+    "DEFAULT_COMMENT_TEMPLATE",
+    "TEMPLATE_FIELDS",
+    "check_template",
+    "render_comment",
+)
+
+# The comment written for a commit when no template is configured
+DEFAULT_COMMENT_TEMPLATE = "{subject}"
+
+# The fields a template can use
+TEMPLATE_FIELDS = ("subject", "date", "author", "hash", "short_hash", "pr")
+
+
+def check_template(template: str) -> None:
+    """Check that a comment template only uses `TEMPLATE_FIELDS`.
+
+    Raises:
+        ValueError: If the template is malformed or uses another field.
+    """
+    for _, field, _, _ in Formatter().parse(template):
+        if field is not None and field not in TEMPLATE_FIELDS:
+            fields = ", ".join(TEMPLATE_FIELDS)
+            raise ValueError(f"Unknown field {{{field}}} (expected {fields})")
+
+
+def _tidy(text: str) -> str:
+    # Drop the punctuation left around fields that were empty, such as in "(2020-01-01, )"
+    text = re.sub(r"[,;]\s*(?=[)\]])", "", text)
+    text = re.sub(r"(?<=[(\[])\s*[,;]\s*", "", text)
+    text = re.sub(r"\s*(?:\(\s*\)|\[\s*\])", "", text)
+    return " ".join(text.split()).strip(" ,;:-")
+
+
+def render_comment(template: str, commit: CommitInfo, pull_request: Optional[int] = None) -> str:
+    """
+    Writes the comment describing a commit according to a template.
+
+    The fields are the commit's `subject`, its author `date` as YYYY-MM-DD,
+    the `author`'s name, its `hash` and `short_hash`, and `pr`, the pull
+    request given or otherwise referenced in the subject, such as `#123`.
+    Punctuation left around a field that is empty, such as `pr` for a commit
+    without a pull request, is dropped.
+
+    Args:
+        template (str): The template, such as `{subject} ({date}, {pr})`.
+        commit (CommitInfo): The commit the comment describes.
+        pull_request (Optional[int]): The number of the pull request that made the commit, if known.
+
+    Returns:
+        str: The comment, without the leading "#".
+    """
+    if pull_request is None:
+        pull_request = find_pull_request([commit["subject"]])
+    fields = {
+        "subject": commit["subject"],
+        "date": commit["author_date"][:10],
+        "author": commit["author_name"],
+        "hash": commit["hash"],
+        "short_hash": commit["hash"][:7],
+        "pr": "" if pull_request is None else f"#{pull_request}",
+    }
+    return _tidy(template.format_map(fields))
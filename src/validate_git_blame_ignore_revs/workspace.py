@@ -0,0 +1,86 @@
+import os
+from pathlib import Path
+from typing import Optional, Union
+
+from validate_git_blame_ignore_revs.backends import GitBackend, SubprocessGit
+from validate_git_blame_ignore_revs.config import DEFAULT_FILE_NAME
+from validate_git_blame_ignore_revs.paths import is_git_entry, path_key
+
+__all__ = (
+    "find_repositories",
+    "ignore_files",
+    "workspace_ignore_files",
+)
+
+
+def find_repositories(root: Union[str, Path]) -> list[Path]:
+    """
+    Finds the Git repositories with a working tree under a directory, such as a checkout of every repository of an organization.
+
+    The search doesn't look inside a repository once it is found, so
+    submodules and other nested repositories are skipped. On Windows, `.git`
+    is recognized whatever its case, as Git does.
+
+    Args:
+        root (Union[str, Path]): The directory to search, which may itself be a repository.
+
+    Returns:
+        list[Path]: The top level of each repository, in sorted order.
+    """
+    repositories: list[Path] = []
+    for directory, dirnames, filenames in os.walk(root):
+        # `.git` is a file rather than a directory in worktrees and submodules
+        if any(is_git_entry(name) for name in dirnames + filenames):
+            repositories.append(Path(directory))
+            dirnames.clear()
+        else:
+            dirnames.sort()
+    return sorted(repositories)
+
+
+def ignore_files(repository: Union[str, Path], git: Optional[GitBackend] = None) -> list[Path]:
+    """
+    Returns the `.git-blame-ignore-revs` files that exist in a repository.
+
+    These are the files named by `blame.ignoreRevsFile`, relative to the top
+    level like `git blame` run from there would read them, along with
+    `.git-blame-ignore-revs` at the top level, which is where GitHub looks.
+
+    Raises:
+        GitNotFoundError: If the `git` executable cannot be run.
+    """
+    repository = Path(repository)
+    if git is None:
+        git = SubprocessGit()
+    candidates: list[Path] = []
+    for value in git.config_values("blame.ignoreRevsFile", repository):
+        if value:
+            candidates.append(repository / os.path.expanduser(value))
+        else:
+            # Like Git, an empty value clears the files configured before it
+            candidates.clear()
+    candidates.append(repository / DEFAULT_FILE_NAME)
+
+    files: list[Path] = []
+    seen: set[str] = set()
+    for candidate in candidates:
+        if candidate.is_file() and path_key(candidate) not in seen:
+            seen.add(path_key(candidate))
+            files.append(candidate)
+    return files
+
+
+def workspace_ignore_files(root: Union[str, Path], git: Optional[GitBackend] = None) -> list[Path]:
+    """Return the `.git-blame-ignore-revs` files of every repository under a directory.
+
+    A file shared between repositories, such as one configured globally, is
+    only returned once.
+    """
+    files: list[Path] = []
+    seen: set[str] = set()
+    for repository in find_repositories(root):
+        for file_path in ignore_files(repository, git):
+            if path_key(file_path) not in seen:
+                seen.add(path_key(file_path))
+                files.append(file_path)
+    return files
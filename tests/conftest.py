@@ -0,0 +1,110 @@
+import os
+import subprocess
+from collections.abc import Callable
+from pathlib import Path
+from typing import Optional
+
+import pytest
+from validate_git_blame_ignore_revs.__main__ import main
+
+SNAPSHOTS = Path(__file__).parent / "snapshots"
+
+
+@pytest.fixture(autouse=True)
+def english(monkeypatch: pytest.MonkeyPatch) -> None:
+    """Print reports in English, whatever the language of the environment running the tests."""
+    monkeypatch.setenv("LANGUAGE", "en")
+
+
+class GitRepo:
+    """A throwaway Git repository with a scripted history."""
+
+    def __init__(self, path: Path) -> None:
+        self.path = path
+        self.env = {
+            **os.environ,
+            "GIT_AUTHOR_NAME": "Developer",
+            "GIT_AUTHOR_EMAIL": "developer@example.com",
+            "GIT_COMMITTER_NAME": "Developer",
+            "GIT_COMMITTER_EMAIL": "developer@example.com",
+            "GIT_AUTHOR_DATE": "2020-01-01T00:00:00+0000",
+            "GIT_COMMITTER_DATE": "2020-01-01T00:00:00+0000",
+            "GIT_CONFIG_GLOBAL": os.devnull,
+            "GIT_CONFIG_NOSYSTEM": "1",
+        }
+        self.git("init", "--quiet", "--initial-branch=main")
+
+    def git(self, *args: str, author: Optional[str] = None) -> str:
+        env = dict(self.env)
+        if author is not None:
+            env["GIT_AUTHOR_NAME"] = author
+        return subprocess.run(
+            ["git", *args],
+            cwd=self.path,
+            env=env,
+            check=True,
+            capture_output=True,
+            text=True,
+        ).stdout.strip()
+
+    def commit(self, message: str, files: dict[str, str], author: Optional[str] = None) -> str:
+        """Write `files`, commit them with `message`, and return the new commit hash."""
+        for name, content in files.items():
+            (self.path / name).parent.mkdir(parents=True, exist_ok=True)
+            (self.path / name).write_text(content, encoding="utf-8")
+        self.git("add", "--", *files)
+        self.git("commit", "--quiet", "--message", message, author=author)
+        return self.git("rev-parse", "HEAD")
+
+    def write_ignore_file(self, content: str) -> Path:
+        """Write and commit a `.git-blame-ignore-revs` file, returning its path."""
+        self.commit("Add .git-blame-ignore-revs", {".git-blame-ignore-revs": content})
+        return self.path / ".git-blame-ignore-revs"
+
+
+def run(argv: list[str], capsys: pytest.CaptureFixture[str]) -> tuple[int, str]:
+    """Run the command line interface, returning the exit code and standard output."""
+    with pytest.raises(SystemExit) as excinfo:
+        main(argv)
+    code = excinfo.value.code
+    assert isinstance(code, int)
+    return code, capsys.readouterr().out
+
+
+@pytest.fixture
+def git_repo(tmp_path: Path, monkeypatch: pytest.MonkeyPatch) -> GitRepo:
+    # Validation changes the working directory, so restore it afterwards
+    monkeypatch.chdir(tmp_path)
+    return GitRepo(tmp_path)
+
+
+@pytest.fixture
+def history(git_repo: GitRepo) -> dict[str, str]:
+    """Commit a small history and return the hashes of its commits by name."""
+    return {
+        "initial": git_repo.commit("Initial commit", {"a.py": "def f(x):\n  return x+1\n"}),
+        "black": git_repo.commit("Apply black", {"a.py": "def f(x):\n    return x+1\n"}),
+        "bot": git_repo.commit(
+            "[pre-commit.ci] auto fixes from pre-commit.com hooks",
+            {"a.py": "def f(x):\n    return x + 1\n"},
+            author="pre-commit-ci[bot]",
+        ),
+        "feature": git_repo.commit("Add g", {"a.py": "def f(x):\n    return x + 1\n\n\ng = f\n"}),
+    }
+
+
+@pytest.fixture
+def snapshot() -> Callable[[str, str], None]:
+    """Compare output with `tests/snapshots/<name>.txt`.
+
+    Set `UPDATE_SNAPSHOTS=1` to write the snapshots instead after reviewing a change.
+    """
+
+    def check(name: str, actual: str) -> None:
+        path = SNAPSHOTS / f"{name}.txt"
+        if os.environ.get("UPDATE_SNAPSHOTS") or not path.exists():
+            path.parent.mkdir(exist_ok=True)
+            path.write_text(actual, encoding="utf-8")
+        assert actual == path.read_text(encoding="utf-8"), f"Output differs from {path}"
+
+    return check
@@ -1,5 +1,35 @@
+from pathlib import Path
+from typing import Any
+
 import pytest
-from validate_git_blame_ignore_revs.lib import HashEntries
+from validate_git_blame_ignore_revs.backends import FakeCommit, FakeGit
+from validate_git_blame_ignore_revs.comments import MatchMode
+from validate_git_blame_ignore_revs.explain import explain_entry
+from validate_git_blame_ignore_revs.export import export_entries
+from validate_git_blame_ignore_revs.git import HistoryRange
+from validate_git_blame_ignore_revs.lib import (
+    HashEntries,
+    MissingReason,
+    ValidationOptions,
+    ValidationResult,
+    WorkspaceResult,
+    add_finding,
+    empty_result,
+    error_count,
+    extend_result,
+    info_count,
+    is_clean,
+    merge_result,
+    merge_results,
+    result_summary,
+    tracking_problems,
+    validate_files,
+    validate_git_blame_ignore_revs,
+    warning_count,
+)
+from validate_git_blame_ignore_revs.sources import InMemory
+
+HASH = "0123456789abcdef0123456789abcdef01234567"
 
 
 @pytest.fixture
@@ -24,3 +54,401 @@ def valid_hashes() -> HashEntries:
 @pytest.fixture
 def lines(mock_git_blame_ignore_revs_file: str) -> list[str]:
     return mock_git_blame_ignore_revs_file.strip().split("\n")
+
+
+@pytest.fixture
+def contents(mock_git_blame_ignore_revs_file: str) -> str:
+    # Without the leading blank line, so the line numbers match `valid_hashes`
+    return mock_git_blame_ignore_revs_file.lstrip()
+
+
+@pytest.fixture
+def file_path(tmp_path: Path) -> Path:
+    # Never written: the fake repository and the in-memory source don't touch the disk
+    return tmp_path / ".git-blame-ignore-revs"
+
+
+def validate(file_path: Path, contents: str, git: FakeGit, **options: Any) -> ValidationResult:
+    return validate_git_blame_ignore_revs(
+        file_path, ValidationOptions(**options), source=InMemory({file_path: contents}), git=git
+    )
+
+
+def test_missing_commits(file_path: Path, contents: str, valid_hashes: HashEntries) -> None:
+    git = FakeGit({valid_hashes[4]: FakeCommit("Another comment")})
+
+    result = validate(file_path, contents, git, call_git=True)
+
+    assert result["valid_hashes"] == valid_hashes
+    assert result["missing_commits"] == {2: valid_hashes[2]}
+    assert result["missing_reasons"] == {
+        2: (MissingReason.ObjectNotFound, "no object has this hash")
+    }
+    assert result["errors"] == {5: "invalid_hash"}
+    assert git.directory == file_path.parent
+
+    # A shallow clone may not go back far enough to have the commit
+    git.shallow = True
+    result = validate(file_path, contents, git, call_git=True)
+
+    assert result["missing_reasons"][2][0] is MissingReason.ShallowBoundary
+
+
+def test_non_commit_objects_and_replacements(
+    file_path: Path, contents: str, valid_hashes: HashEntries
+) -> None:
+    git = FakeGit(
+        {valid_hashes[4]: FakeCommit("Another comment")},
+        objects={valid_hashes[2]: "tag v1.0"},
+        replacements={valid_hashes[4]: HASH},
+    )
+
+    result = validate(file_path, contents, git, call_git=True)
+
+    assert result["non_commit_objects"] == {2: "tag v1.0"}
+    assert result["missing_reasons"] == {
+        2: (MissingReason.NotACommit, "not a commit object: tag v1.0")
+    }
+    assert result["replaced_commits"] == {4: HASH}
+    assert result["missing_commits"] == {}
+
+
+def test_strict_comments_git(file_path: Path, contents: str, valid_hashes: HashEntries) -> None:
+    git = FakeGit(
+        {
+            valid_hashes[2]: FakeCommit("This is a comment"),
+            valid_hashes[4]: FakeCommit("Reformat everything"),
+        }
+    )
+
+    result = validate(file_path, contents, git, strict_comments_git=True)
+
+    assert result["comment_diffs"] == {4: ("Another comment", "Reformat everything")}
+
+
+def test_comment_template(file_path: Path, valid_hashes: HashEntries) -> None:
+    # 2020-01-01 in UTC
+    git = FakeGit({valid_hashes[2]: FakeCommit("Apply black", time=1577836800)})
+    contents = f"# Apply black (2020-01-01, #12)\n{valid_hashes[2]}\n"
+
+    result = validate(
+        file_path,
+        contents,
+        git,
+        strict_comments_git=True,
+        comment_match=MatchMode.Exact,
+        comment_template="{subject} ({date}, {pr})",
+    )
+
+    assert result["comment_diffs"] == {}
+
+    result = validate(
+        file_path,
+        contents,
+        git,
+        strict_comments_git=True,
+        comment_match=MatchMode.Exact,
+        comment_template="{subject} ({pr}) by {author}",
+    )
+
+    assert result["comment_diffs"] == {
+        2: ("Apply black (2020-01-01, #12)", "Apply black (#12) by A U Thor")
+    }
+
+
+def test_near_miss_suggestion(file_path: Path) -> None:
+    git = FakeGit({HASH: FakeCommit()}, objects={HASH[:39] + "0": "blob"})
+
+    result = validate(file_path, f"{HASH[:39]}\n", git, call_git=True)
+
+    assert result["suggested_hashes"] == {1: HASH}
+
+
+def test_pre_commit_ci(file_path: Path, valid_hashes: HashEntries) -> None:
+    bot = "pre-commit-ci[bot] <66853113+pre-commit-ci[bot]@users.noreply.github.com>"
+    git = FakeGit(
+        {
+            HASH: FakeCommit("[pre-commit.ci] auto fixes", author=bot),
+            valid_hashes[4]: FakeCommit("Add a feature"),
+            valid_hashes[2]: FakeCommit("[pre-commit.ci] pre-commit autoupdate", author=bot),
+        }
+    )
+    contents = f"{valid_hashes[2]}\n"
+
+    result = validate(file_path, contents, git, call_git=True, pre_commit_ci=True)
+
+    assert result["missing_pre_commit_ci_commits"] == {HASH: "[pre-commit.ci] auto fixes"}
+
+    # `max_count` counts the commits by every author, not only those by pre-commit-ci
+    result = validate(
+        file_path,
+        contents,
+        git,
+        call_git=True,
+        pre_commit_ci=True,
+        history_range=HistoryRange(max_count=2),
+    )
+
+    assert result["missing_pre_commit_ci_commits"] == {HASH: "[pre-commit.ci] auto fixes"}
+
+    result = validate(
+        file_path,
+        f"{HASH}\n",
+        git,
+        call_git=True,
+        pre_commit_ci=True,
+        history_range=HistoryRange(max_count=1),
+    )
+
+    assert result["missing_pre_commit_ci_commits"] == {}
+
+
+def test_diff_checks(file_path: Path, contents: str, valid_hashes: HashEntries) -> None:
+    diff = "-x = 'a'\n+x = \"a\"\n"
+    git = FakeGit(
+        {
+            valid_hashes[2]: FakeCommit(diff=diff),
+            valid_hashes[4]: FakeCommit(diff=diff + "-y = 1\n+y = 2\n"),
+            HASH: FakeCommit(diff=diff),
+        }
+    )
+
+    result = validate(
+        file_path,
+        f"{contents}{HASH}\n",
+        git,
+        call_git=True,
+        check_semantic_changes=True,
+        check_duplicate_patches=True,
+    )
+
+    assert result["semantic_changes"] == {4: 0.25}
+    assert result["duplicate_patches"] == {6: 2}
+
+
+def test_formatting_scores(file_path: Path, contents: str, valid_hashes: HashEntries) -> None:
+    git = FakeGit(
+        {
+            valid_hashes[2]: FakeCommit(
+                "[pre-commit.ci] auto fixes",
+                "pre-commit-ci[bot] <66853113+pre-commit-ci[bot]@users.noreply.github.com>",
+                paths=tuple(f"{name}.py" for name in "abcdefghij"),
+                whitespace_ratio=1.0,
+            ),
+            valid_hashes[4]: FakeCommit("Add g", paths=("a.py",)),
+        }
+    )
+
+    result = validate(file_path, contents, git, call_git=True, score_formatting=True)
+
+    scores = {n: round(score, 2) for n, score in result["formatting_scores"].items()}
+    assert scores == {2: 1.0, 4: 0.02}
+
+
+def test_sections(file_path: Path, valid_hashes: HashEntries) -> None:
+    git = FakeGit(
+        {
+            HASH: FakeCommit("Reformat", paths=("a.py", "b.pyi")),
+            valid_hashes[2]: FakeCommit("Run prettier", paths=("a.py",)),
+            valid_hashes[4]: FakeCommit("Reformat", paths=("app.ts", "README.md")),
+        }
+    )
+    contents = (
+        f"{HASH}\n"
+        f"## Formatting\n{valid_hashes[4]}\n"
+        f"# --- Black ---\n{HASH}\n{valid_hashes[2]}\n"
+        f"## prettier\n# Apply prettier\n{valid_hashes[4]}\n{HASH}\n"
+    )
+
+    result = validate(file_path, contents, git, call_git=True, check_sections=True)
+
+    assert result["section_errors"] == {
+        6: "looks like a prettier commit, but is in the 'Black' section (line 4)",
+        10: "looks like a black commit, but is in the 'prettier' section (line 7)",
+    }
+
+
+def test_unused_entries(file_path: Path, contents: str, valid_hashes: HashEntries) -> None:
+    git = FakeGit(
+        {
+            valid_hashes[2]: FakeCommit(paths=("a.py", "b.py")),
+            valid_hashes[4]: FakeCommit(paths=("a.py",)),
+        },
+        blame={"a.py": [HASH, HASH], "b.py": [valid_hashes[2]]},
+    )
+
+    result = validate(file_path, contents, git, call_git=True, report_unused_entries=True)
+
+    assert result["unused_entries"] == {4: valid_hashes[4]}
+
+
+def test_default_branch(file_path: Path, contents: str, valid_hashes: HashEntries) -> None:
+    git = FakeGit(
+        {valid_hashes[2]: FakeCommit(), valid_hashes[4]: FakeCommit()},
+        branches={"upstream/main": {valid_hashes[2]}},
+        remote_heads={"upstream": "upstream/main"},
+    )
+
+    result = validate(
+        file_path,
+        contents,
+        git,
+        call_git=True,
+        check_default_branch=True,
+        remote="upstream",
+    )
+
+    assert result["default_branch_errors"] == {4: "not on upstream/main"}
+
+    result = validate(file_path, contents, git, call_git=True, check_default_branch=True)
+
+    assert result["default_branch_errors"] == {
+        2: "could not be checked: the default branch of origin isn't known",
+        4: "could not be checked: the default branch of origin isn't known",
+    }
+
+
+def test_reachable_from(file_path: Path, contents: str, valid_hashes: HashEntries) -> None:
+    git = FakeGit(
+        {valid_hashes[2]: FakeCommit(), valid_hashes[4]: FakeCommit()},
+        branches={
+            "main": {valid_hashes[2], valid_hashes[4]},
+            "release/1.0": {valid_hashes[2]},
+            "release/2.0": {valid_hashes[2], valid_hashes[4]},
+        },
+    )
+
+    result = validate(
+        file_path,
+        contents,
+        git,
+        call_git=True,
+        reachable_from=["main", "release/*", "stable/*"],
+    )
+
+    assert result["reachability_errors"] == {
+        2: ("no ref matches stable/*",),
+        4: ("not reachable from release/1.0", "no ref matches stable/*"),
+    }
+
+
+def test_unreferenced_commits(file_path: Path, contents: str, valid_hashes: HashEntries) -> None:
+    git = FakeGit(
+        {valid_hashes[2]: FakeCommit(), valid_hashes[4]: FakeCommit()},
+        unreferenced={valid_hashes[4]: True},
+    )
+
+    result = validate(file_path, contents, git, call_git=True, check_unreferenced=True)
+
+    assert result["unreferenced_commits"] == {4: True}
+
+    # Missing commits are only reported as missing
+    git = FakeGit({valid_hashes[4]: FakeCommit()}, unreferenced={valid_hashes[2]: False})
+    result = validate(file_path, contents, git, call_git=True, check_unreferenced=True)
+
+    assert result["unreferenced_commits"] == {}
+
+
+def test_tracking_problems(file_path: Path) -> None:
+    name = file_path.name
+
+    assert tracking_problems(file_path, FakeGit(committed_files={name})) == []
+    assert tracking_problems(file_path, FakeGit()) == ["not tracked by Git"]
+    assert tracking_problems(
+        file_path, FakeGit(staged_files={name}, ignore_rules={name: f".gitignore:1:{name}"})
+    ) == ["staged but not committed", f"matched by the ignore rule .gitignore:1:{name}"]
+
+
+def test_merge_results(tmp_path: Path, contents: str) -> None:
+    for name in ("app", "lib"):
+        (tmp_path / name).mkdir()
+        (tmp_path / name / ".git-blame-ignore-revs").write_text(contents, encoding="utf-8")
+    app = validate_files(
+        [tmp_path / "app" / ".git-blame-ignore-revs"], ValidationOptions(strict_comments=True)
+    )
+    lib = validate_files(
+        [tmp_path / "lib" / ".git-blame-ignore-revs"], ValidationOptions(check_commented_out=True)
+    )
+
+    merged = merge_results(app, lib)
+
+    assert list(merged["files"]) == [*app["files"], *lib["files"]]
+    assert merged["summary"]["valid_hashes"] == 4
+    assert merged["summary"]["errors"] == 2
+    assert (error_count(merged), warning_count(merged), info_count(merged)) == (2, 0, 0)
+    assert not is_clean(merged)
+    assert is_clean(WorkspaceResult(files={}, summary={}))
+    file_result = next(iter(merged["files"].values()))
+    assert (error_count(file_result), warning_count(file_result)) == (1, 0)
+
+    with pytest.raises(ValueError, match="already have a file named"):
+        merge_results(app, app)
+
+
+def test_result_helpers(file_path: Path, contents: str, valid_hashes: HashEntries) -> None:
+    git = FakeGit({valid_hashes[4]: FakeCommit("Another comment")})
+    syntax = validate(file_path, contents, git)
+    history = validate(file_path, contents, git, call_git=True)
+
+    merged = merge_result(syntax, history)
+
+    assert merged == history
+    assert result_summary(merged)["missing_commits"] == 1
+    assert sum(result_summary(empty_result()).values()) == 0
+
+    # A check of the caller's own adds its findings one by one
+    add_finding(merged, "ticket_errors", 2, valid_hashes[2])
+    add_finding(merged, "ticket_errors", 2, valid_hashes[2])
+    assert merged["ticket_errors"] == {2: valid_hashes[2]}
+    assert (error_count(merged), warning_count(merged)) == (2, 1)
+    with pytest.raises(ValueError, match="already have a different entry for 2 in ticket_errors"):
+        add_finding(merged, "ticket_errors", 2, valid_hashes[4])
+    with pytest.raises(KeyError, match="isn't a field"):
+        add_finding(merged, "typos", 2, "tpyo")
+
+    extended = empty_result()
+    extend_result(extended, syntax)
+    extend_result(extended, history)
+    assert extended == history
+    assert syntax["missing_commits"] == {}
+
+
+def test_legacy_arguments(file_path: Path, contents: str, valid_hashes: HashEntries) -> None:
+    git = FakeGit({valid_hashes[4]: FakeCommit("Another comment")})
+    source = InMemory({file_path: contents})
+    expected = validate(file_path, contents, git, call_git=True, strict_comments=True)
+
+    # The checks as arguments of their own still work, by name or by position
+    with pytest.warns(DeprecationWarning, match="pass them in a ValidationOptions"):
+        by_name = validate_git_blame_ignore_revs(
+            file_path, call_git=True, strict_comments=True, source=source, git=git
+        )
+    with pytest.warns(DeprecationWarning):
+        by_position = validate_git_blame_ignore_revs(file_path, True, True, source=source, git=git)
+    assert by_name == by_position == expected
+
+    with pytest.raises(TypeError, match="either in options or as arguments of their own"):
+        validate_git_blame_ignore_revs(file_path, ValidationOptions(), call_git=True)
+    with pytest.raises(TypeError, match="unexpected keyword argument 'call_gti'"):
+        validate_git_blame_ignore_revs(file_path, call_gti=True)
+
+
+def test_injected_git(file_path: Path, contents: str, valid_hashes: HashEntries) -> None:
+    # The lookups of other modules go through the same backend, without a repository
+    git = FakeGit(
+        {
+            valid_hashes[2]: FakeCommit("This is a comment"),
+            valid_hashes[4]: FakeCommit("Another comment", body="Fixes #1"),
+        }
+    )
+    # These read the file from the working tree
+    file_path.write_text(contents, encoding="utf-8")
+
+    entries = export_entries(file_path, git)
+    assert [entry["hash"] for entry in entries] == [valid_hashes[2], valid_hashes[4]]
+    assert [entry["commit"] is not None for entry in entries] == [True, True]
+
+    explanation = explain_entry(file_path, valid_hashes[4][:12], git)
+    assert explanation["line_number"] == 4
+    assert explanation["commit"]["hash"] == valid_hashes[4]
+    assert explanation["reachable"]
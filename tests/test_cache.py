@@ -0,0 +1,91 @@
+from dataclasses import replace
+import time
+from pathlib import Path
+from typing import Any
+
+import pytest
+from conftest import GitRepo
+from validate_git_blame_ignore_revs import cache
+from validate_git_blame_ignore_revs.cache import cached_validate_git_blame_ignore_revs
+from validate_git_blame_ignore_revs.lib import ValidationOptions, ValidationResult
+
+
+@pytest.fixture
+def validations(tmp_path: Path, monkeypatch: pytest.MonkeyPatch) -> list[tuple[Any, ...]]:
+    """Cache results in a fresh directory, returning the validations run from now on."""
+    monkeypatch.setenv("XDG_CACHE_HOME", str(tmp_path / "cache"))
+    validations: list[tuple[Any, ...]] = []
+    validate = cache.validate_git_blame_ignore_revs
+
+    def counting_validate(*args: Any, **kwargs: Any) -> ValidationResult:
+        validations.append(args)
+        return validate(*args, **kwargs)
+
+    monkeypatch.setattr(cache, "validate_git_blame_ignore_revs", counting_validate)
+    return validations
+
+
+def test_cache_invalidation(git_repo: GitRepo, validations: list[tuple[Any, ...]]) -> None:
+    black = git_repo.commit("Apply black", {"a.py": "x = 1\n"})
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{black}\n")
+    options = ValidationOptions(call_git=True)
+
+    def reused(**changed: Any) -> bool:
+        before = len(validations)
+        cached_validate_git_blame_ignore_revs(file_path, replace(options, **changed))
+        return len(validations) == before
+
+    assert not reused()
+    # An identical run reuses the result
+    assert reused()
+
+    # A change to the file, even one that isn't committed
+    file_path.write_text(f"# Apply black\n{black}\n# Add g\n", encoding="utf-8")
+    assert not reused()
+    assert reused()
+
+    # A new commit, which may add or remove the commits that are listed
+    git_repo.commit("Add c", {"c.py": "c = 1\n"})
+    assert not reused()
+    assert reused()
+
+    # Other options
+    assert not reused(strict_comments=True)
+    assert reused(strict_comments=True)
+    assert not reused()
+
+
+def test_cache_ignores_head_without_git(
+    git_repo: GitRepo, validations: list[tuple[Any, ...]]
+) -> None:
+    file_path = git_repo.write_ignore_file("# Not in history\n" + "0" * 40 + "\n")
+
+    cached_validate_git_blame_ignore_revs(file_path)
+    git_repo.commit("Add c", {"c.py": "c = 1\n"})
+    cached_validate_git_blame_ignore_revs(file_path)
+
+    # Without Git-based checks, the result doesn't depend on the history
+    assert len(validations) == 1
+
+
+def test_cache_time_and_forge(
+    git_repo: GitRepo, validations: list[tuple[Any, ...]], monkeypatch: pytest.MonkeyPatch
+) -> None:
+    black = git_repo.commit("Apply black", {"a.py": "x = 1\n"})
+    committed = int(git_repo.git("show", "--no-patch", "--format=%ct", black))
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{black}\n")
+    options = ValidationOptions(call_git=True, older_than=3600)
+
+    monkeypatch.setattr(time, "time", lambda: committed + 1800)
+    assert cached_validate_git_blame_ignore_revs(file_path, options)["old_entries"] == {}
+    # An hour later, the cached result finds the entry old enough
+    monkeypatch.setattr(time, "time", lambda: committed + 7200)
+    result = cached_validate_git_blame_ignore_revs(file_path, options)
+    assert result["old_entries"] == {2: committed}
+    assert len(validations) == 1
+
+    # Pull requests can change on the forge, so they are always checked again
+    options = ValidationOptions(verify_pr_links=True)
+    cached_validate_git_blame_ignore_revs(file_path, options)
+    cached_validate_git_blame_ignore_revs(file_path, options)
+    assert len(validations) == 3
@@ -0,0 +1,2577 @@
+"""End-to-end tests of the command line interface against real temporary repositories."""
+
+import base64
+import json
+import os
+import re
+import signal
+import subprocess
+import sys
+import time
+import xml.etree.ElementTree as ET
+from email.message import Message
+from pathlib import Path
+from typing import Any, Optional
+from urllib.error import HTTPError
+
+import pytest
+from conftest import GitRepo, run
+from validate_git_blame_ignore_revs import cache, forge, forge_api, git, retry
+from validate_git_blame_ignore_revs.__main__ import (
+    ErrorCode,
+    build_parser,
+    main,
+    result_sections,
+    serve_validator,
+)
+from validate_git_blame_ignore_revs.checks import CHECKS, rule_id
+from validate_git_blame_ignore_revs.config import default_config
+from validate_git_blame_ignore_revs.denylist import Denylist
+from validate_git_blame_ignore_revs.lib import ValidationResult, validate_git_blame_ignore_revs
+from validate_git_blame_ignore_revs.metrics import Metrics
+from validate_git_blame_ignore_revs.server import BadRequest
+
+MISSING_COMMIT = "0123456789abcdef0123456789abcdef01234567"
+
+
+def test_valid_file(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n")
+
+    code, out = run([str(file_path)], capsys)
+
+    assert code == 0
+    assert f"  Line 2: {history['black']}" in out
+    assert "No errors found!" in out
+
+
+@pytest.mark.parametrize(
+    ("contents", "empty_file", "expected_code", "message"),
+    [
+        ("", "allow", 0, None),
+        ("", "warn", 0, "Warning: {path} has no entries, so it ignores no commits."),
+        (
+            "# Nothing to ignore yet\n",
+            "error",
+            ErrorCode.FailedAdditionalChecks.value,
+            "Empty file: {path} has only comments, so it ignores no commits.",
+        ),
+    ],
+)
+def test_empty_file(
+    tmp_path: Path,
+    capsys: pytest.CaptureFixture[str],
+    contents: str,
+    empty_file: str,
+    expected_code: int,
+    message: Optional[str],
+) -> None:
+    file_path = tmp_path / ".git-blame-ignore-revs"
+    file_path.write_text(contents, encoding="utf-8")
+
+    code, out = run([str(file_path), "--empty-file", empty_file], capsys)
+
+    assert code == expected_code
+    if message is None:
+        assert "ignores no commits" not in out
+    else:
+        assert message.format(path=file_path) in out
+
+
+def test_expected_name(tmp_path: Path, capsys: pytest.CaptureFixture[str]) -> None:
+    file_path = tmp_path / ".git-blame-ignore-refs"
+    file_path.write_text("", encoding="utf-8")
+    warning = f"Warning: {file_path} is not named .git-blame-ignore-revs"
+
+    code, out = run([str(file_path)], capsys)
+
+    assert code == 0
+    assert warning in out
+
+    code, out = run([str(file_path), "--expected-name", ".git-blame-ignore-refs"], capsys)
+
+    assert code == 0
+    assert "is not named" not in out
+
+
+def test_missing_file(tmp_path: Path, capsys: pytest.CaptureFixture[str]) -> None:
+    code, _ = run([str(tmp_path / ".git-blame-ignore-revs")], capsys)
+
+    assert code == ErrorCode.FileNotFound.value
+
+
+def test_syntax_errors(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\nnot-a-hash\n")
+
+    code, out = run([str(file_path)], capsys)
+
+    assert code == ErrorCode.SyntaxProblem.value
+    assert "Errors (1):\n  Line 3: not-a-hash" in out
+
+
+def test_near_miss_suggestions(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    black = history["black"]
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{black[:39]}\n")
+
+    code, out = run([str(file_path)], capsys)
+
+    assert code == ErrorCode.SyntaxProblem.value
+    assert f"  Line 2: {black[:39]}\n    Hash is 39 characters, expected 40\n" in out
+    assert "Did you mean" not in out
+
+    code, out = run([str(file_path), "--call-git"], capsys)
+
+    assert code == ErrorCode.SyntaxProblem.value
+    assert (
+        f"  Line 2: {black[:39]}\n"
+        "    Hash is 39 characters, expected 40\n"
+        f"    Did you mean {black}?\n"
+    ) in out
+
+
+def test_fix(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    black, feature = history["black"], history["feature"]
+    file_path = git_repo.write_ignore_file(f"# Apply black\r\n{black.upper()}\r\nnot-a-hash\r\n")
+
+    code, out = run([str(file_path), "--fix"], capsys)
+
+    assert code == 0
+    assert out.startswith(f"Fixed {file_path}:\n2: Lowercase hash\n3: Remove invalid line\n")
+    assert file_path.read_bytes() == f"# Apply black\r\n{black}\r\n".encode()
+
+    file_path.write_text(f"{feature}\n# Apply black\n{black[:10]}\n", encoding="utf-8")
+
+    code, out = run([str(file_path), "--fix", "--call-git"], capsys)
+
+    assert code == 0
+    assert file_path.read_text(encoding="utf-8") == f"# Add g\n{feature}\n# Apply black\n{black}\n"
+
+    # A near miss is reported with the commit it likely meant rather than removed
+    file_path.write_text(f"# Apply black\n{black[:39]}\n", encoding="utf-8")
+
+    code, out = run([str(file_path), "--fix"], capsys)
+
+    assert code == ErrorCode.SyntaxProblem.value
+    assert "Hash is 39 characters, expected 40" in out
+    assert file_path.read_text(encoding="utf-8") == f"# Apply black\n{black[:39]}\n"
+
+
+def test_fix_emit_edits(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    contents = f"# Apply black\n{history['black'].upper()}\n"
+    file_path = git_repo.write_ignore_file(contents)
+
+    code, out = run([str(file_path), "--fix", "--emit-edits", "json"], capsys)
+
+    assert code == 0
+    assert json.loads(out) == [
+        {
+            "path": str(file_path),
+            "line": 2,
+            "title": "Lowercase hash",
+            "range": {"start": {"line": 1, "character": 0}, "end": {"line": 1, "character": 40}},
+            "newText": history["black"],
+        }
+    ]
+    assert file_path.read_text(encoding="utf-8") == contents
+
+    with pytest.raises(SystemExit):
+        main([str(file_path), "--emit-edits", "json"])
+    assert "--emit-edits requires --fix." in capsys.readouterr().err
+
+
+def test_fix_interrupted(
+    git_repo: GitRepo,
+    history: dict[str, str],
+    monkeypatch: pytest.MonkeyPatch,
+    capsys: pytest.CaptureFixture[str],
+) -> None:
+    contents = f"# Apply black\n{history['black'].upper()}\n"
+    file_path = git_repo.write_ignore_file(contents)
+
+    def replace(*args: Any) -> None:
+        # Like a CI runner cancelling the job while the fixed file is written
+        os.kill(os.getpid(), signal.SIGTERM)
+
+    monkeypatch.setattr(os, "replace", replace)
+
+    code, out = run([str(file_path), "--fix"], capsys)
+
+    assert code == 128 + signal.SIGTERM
+    assert out == "Interrupted by SIGTERM while fixing the files.\n"
+    assert file_path.read_text(encoding="utf-8") == contents
+    assert sorted(path.name for path in git_repo.path.iterdir()) == [
+        ".git",
+        ".git-blame-ignore-revs",
+        "a.py",
+    ]
+
+
+def test_call_git(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(
+        f"# Apply black\n{history['black']}\n# Not in history\n{MISSING_COMMIT}\n"
+    )
+
+    code, out = run([str(file_path), "--call-git"], capsys)
+
+    assert code == ErrorCode.CommitsNotPresent.value
+    assert (
+        f"Missing commits (1):\n"
+        f"  Line 4: {MISSING_COMMIT} (no object has this hash) [object-not-found]\n"
+    ) in out
+
+
+def test_call_git_shallow_clone(
+    git_repo: GitRepo,
+    history: dict[str, str],
+    tmp_path: Path,
+    capsys: pytest.CaptureFixture[str],
+) -> None:
+    git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n")
+    clone = tmp_path / "clone"
+    git_repo.git("clone", "--quiet", "--depth=1", git_repo.path.as_uri(), str(clone))
+
+    code, out = run([str(clone / ".git-blame-ignore-revs"), "--call-git"], capsys)
+
+    assert code == ErrorCode.CommitsNotPresent.value
+    assert f"  Line 2: {history['black']} (not in this shallow clone" in out
+    assert "[shallow-boundary]\n" in out
+
+
+def test_call_git_all_present(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n")
+
+    code, out = run([str(file_path), "--call-git"], capsys)
+
+    assert code == 0
+    assert "All commits are present in the Git history!" in out
+
+
+def test_strict_comments(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(f"{history['black']}\n")
+
+    code, out = run([str(file_path), "--strict-comments"], capsys)
+
+    assert code == ErrorCode.MissingComments.value
+    assert f"Strict comment errors (1):\n  Line 1: {history['black']}" in out
+
+
+def test_check_commented_out(tmp_path: Path, capsys: pytest.CaptureFixture[str]) -> None:
+    file_path = tmp_path / ".git-blame-ignore-revs"
+    file_path.write_text(
+        f"# Apply black\n#{MISSING_COMMIT}\n# Follow-up to {MISSING_COMMIT}\n"
+        f"#  {MISSING_COMMIT}  # Apply ruff\n",
+        encoding="utf-8",
+    )
+
+    code, out = run([str(file_path), "--check-commented-out"], capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert (
+        "Commented-out entries (2):\n"
+        f"  Line 2: {MISSING_COMMIT}\n"
+        f"    To ignore the commit again, uncomment the line: {MISSING_COMMIT}\n"
+        "    Otherwise, delete the line.\n"
+        f"  Line 4: {MISSING_COMMIT}\n"
+    ) in out
+
+
+def test_check_blank_lines(tmp_path: Path, capsys: pytest.CaptureFixture[str]) -> None:
+    file_path = tmp_path / ".git-blame-ignore-revs"
+    file_path.write_text(
+        f"\n# Formatting\n\n# Apply black\n\n{MISSING_COMMIT}\n# Apply ruff\n{MISSING_COMMIT}\n"
+        f"\n\n# Apply isort\n{MISSING_COMMIT}\n{MISSING_COMMIT}\n\n",
+        encoding="utf-8",
+    )
+
+    code, out = run([str(file_path), "--check-blank-lines"], capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert (
+        "Blank line problems (5):\n"
+        "  Line 1: blank line at the start of the file\n"
+        "  Line 5: blank line between a comment and the entry it describes\n"
+        "  Line 7: no blank line before the comment\n"
+        "  Line 9: 2 blank lines in a row (expected 1)\n"
+        "  Line 14: blank line at the end of the file\n"
+    ) in out
+
+    file_path.write_text(
+        f"# Formatting\n\n# Apply black\n{MISSING_COMMIT}\n\n# Apply ruff\n{MISSING_COMMIT}\n",
+        encoding="utf-8",
+    )
+    code, out = run([str(file_path), "--check-blank-lines"], capsys)
+
+    assert code == 0
+    assert "Blank lines separate every group of entries!" in out
+
+
+def test_check_spelling(tmp_path: Path, capsys: pytest.CaptureFixture[str]) -> None:
+    dictionary = tmp_path / "words"
+    dictionary.write_text("apply\nblack\nformat\nwith\nthe\n", encoding="utf-8")
+    file_path = tmp_path / ".git-blame-ignore-revs"
+    file_path.write_text(
+        f"# Aply black\n{MISSING_COMMIT}\n# Format with isort's CI (see `isort .`)\n"
+        f"{MISSING_COMMIT}\n",
+        encoding="utf-8",
+    )
+    args = [str(file_path), "--check-spelling", "--spelling-dictionary", str(dictionary)]
+
+    code, out = run(args, capsys)
+
+    # Misspellings don't fail the run
+    assert code == 0
+    assert (
+        "Possible misspellings (2):\n"
+        "  Line 1: Aply\n"
+        "    If a word is right, add it to words in the [spelling] section "
+        "of the configuration file.\n"
+        "  Line 3: isort's, see\n"
+    ) in out
+
+    (tmp_path / ".validate-git-blame-ignore-revs.cfg").write_text(
+        "[spelling]\nwords = Aply, isort, see\n", encoding="utf-8"
+    )
+    code, out = run(args, capsys)
+
+    assert code == 0
+    assert "No misspellings in the comments!" in out
+
+    with pytest.raises(SystemExit):
+        main([str(file_path), "--spelling-dictionary", str(dictionary)])
+    assert "--spelling-dictionary requires --check-spelling." in capsys.readouterr().err
+
+
+def test_strict_comments_git(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(
+        f"# Apply black\n{history['black']}\n# Run the formatter\n{history['feature']}\n"
+    )
+
+    code, out = run(
+        [str(file_path), "--call-git", "--strict-comments", "--strict-comments-git"], capsys
+    )
+
+    assert code == ErrorCode.MissingCommitMessageComments.value
+    assert (
+        "Comment diffs (1):\n"
+        "  Line 4:\n"
+        "    Comment: Run the formatter\n"
+        "    Commit message: Add g"
+    ) in out
+
+
+def test_comment_template(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    (git_repo.path / ".validate-git-blame-ignore-revs.cfg").write_text(
+        "[comments]\nmatch = exact\ntemplate = {subject} ({date}, {pr})\n", encoding="utf-8"
+    )
+    oneline = git_repo.path / "oneline.txt"
+    oneline.write_text(f"{history['black'][:10]}\n{history['feature'][:10]}\n", encoding="utf-8")
+
+    code, out = run(["import", str(oneline), "--append", ".git-blame-ignore-revs"], capsys)
+
+    assert code == 0
+    file_path = git_repo.path / ".git-blame-ignore-revs"
+    assert file_path.read_text(encoding="utf-8") == (
+        f"# Apply black (2020-01-01)\n{history['black']}\n"
+        f"# Add g (2020-01-01)\n{history['feature']}\n"
+    )
+
+    # The comments the template writes pass, but not the commit subjects
+    code, out = run(
+        [str(file_path), "--call-git", "--strict-comments", "--strict-comments-git"], capsys
+    )
+
+    assert code == 0
+    file_path.write_text(f"# Apply black\n{history['black']}\n", encoding="utf-8")
+    code, out = run(
+        [str(file_path), "--call-git", "--strict-comments", "--strict-comments-git"], capsys
+    )
+
+    assert code == ErrorCode.MissingCommitMessageComments.value
+    assert ("  Line 2:\n    Comment: Apply black\n    Expected: Apply black (2020-01-01)\n") in out
+
+
+def test_pre_commit_ci(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n")
+
+    code, out = run([str(file_path), "--call-git", "--pre-commit-ci"], capsys)
+
+    assert code == ErrorCode.MissingPreCommitCICommits.value
+    assert (
+        f"Missing pre-commit-ci commits (1):\n  Commit {history['bot']}: "
+        "[pre-commit.ci] auto fixes from pre-commit.com hooks"
+    ) in out
+
+    # The two most recent commits come after the one by pre-commit-ci
+    code, out = run([str(file_path), "--call-git", "--pre-commit-ci", "--max-count", "2"], capsys)
+
+    assert code == 0
+    assert "All pre-commit-ci commits are present in the file!" in out
+
+    # Only commits committed since the given date are scanned
+    git_repo.env["GIT_AUTHOR_DATE"] = git_repo.env["GIT_COMMITTER_DATE"] = "2022-01-01T00:00:00Z"
+    later = git_repo.commit(
+        "[pre-commit.ci] auto fixes from pre-commit.com hooks",
+        {"a.py": "def f(x):\n    return x + 1\n\n\ng = f  # noqa\n"},
+        author="pre-commit-ci[bot]",
+    )
+
+    code, out = run(
+        [str(file_path), "--call-git", "--pre-commit-ci", "--since", "2021-01-01"], capsys
+    )
+
+    assert code == ErrorCode.MissingPreCommitCICommits.value
+    assert (
+        f"Missing pre-commit-ci commits (1):\n  Commit {later}: "
+        "[pre-commit.ci] auto fixes from pre-commit.com hooks\n"
+    ) in out
+
+
+def test_trailer(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    reformat = git_repo.commit(
+        "Reformat\n\nBlame-Ignore: true", {"a.py": "def f(x):\n    return (x + 1)\n"}
+    )
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n")
+
+    code, out = run([str(file_path), "--call-git", "--trailer", "blame-ignore: TRUE"], capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert f"Missing trailer commits (1):\n  Commit {reformat}: Reformat\n" in out
+
+    (git_repo.path / ".validate-git-blame-ignore-revs.cfg").write_text(
+        "[commits]\ntrailer = Blame-Ignore: false\n", encoding="utf-8"
+    )
+    code, out = run([str(file_path), "--call-git"], capsys)
+
+    assert code == 0
+    assert "All commits carrying Blame-Ignore: false are present in the file!" in out
+
+    code, out = run(["audit", str(file_path), "--trailer", "Blame-Ignore"], capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert f"  Commit {reformat} (Blame-Ignore): Reformat\n" in out
+
+
+def test_denylist(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    (git_repo.path / ".validate-git-blame-ignore-revs.cfg").write_text(
+        f"[denylist]\ncommits = {history['initial'][:12]}\nsubjects = ^Add\\b\n",
+        encoding="utf-8",
+    )
+    file_path = git_repo.write_ignore_file(
+        f"# Initial commit\n{history['initial']}\n"
+        f"# Apply black\n{history['black']}\n"
+        f"# Add g\n{history['feature']}\n"
+    )
+
+    code, out = run([str(file_path), "--call-git"], capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert (
+        "Denied commits (2):\n"
+        f"  Line 2: {history['initial']} (denied commit {history['initial'][:12]})\n"
+        f"  Line 6: {history['feature']} (subject matches '^Add\\\\b')\n"
+    ) in out
+
+    # Only hashes are checked without Git
+    code, out = run([str(file_path), "--no-git"], capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert "Denied commits (1):\n" in out
+
+
+def test_pre_commit_ci_listed(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(
+        f"# Apply black\n{history['black']}\n"
+        f"# [pre-commit.ci] auto fixes\n{history['bot']}\n"
+    )
+
+    code, out = run(
+        [
+            str(file_path),
+            "--call-git",
+            "--strict-comments",
+            "--strict-comments-git",
+            "--pre-commit-ci",
+        ],
+        capsys,
+    )
+
+    assert code == 0
+    assert "All pre-commit-ci commits are present in the file!" in out
+
+
+def test_exit_code_combines_failures(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(f"{MISSING_COMMIT}\nnot-a-hash\n")
+
+    code, _ = run([str(file_path), "--call-git", "--strict-comments"], capsys)
+
+    assert code == (
+        ErrorCode.SyntaxProblem.value
+        | ErrorCode.CommitsNotPresent.value
+        | ErrorCode.MissingComments.value
+    )
+
+
+def test_at_rev(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\nnot-a-hash\n")
+    git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n")
+    git_repo.git("tag", "v1.0", "HEAD~")
+
+    code, out = run([str(file_path), "--at-rev", "v1.0"], capsys)
+
+    assert code == ErrorCode.SyntaxProblem.value
+    assert "Errors (1):\n  Line 3: not-a-hash" in out
+
+    code, out = run([str(file_path), "--at-rev", "v2.0"], capsys)
+
+    assert code == ErrorCode.FileNotFound.value
+    assert out == f"No commit or tree named v2.0, so {file_path} can't be read from it.\n"
+
+
+def test_staged(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n")
+    file_path.write_text(f"# Apply black\n{history['black']}\nnot-a-hash\n", encoding="utf-8")
+    git_repo.git("add", file_path.name)
+    file_path.write_text(f"# Apply black\n{history['black']}\n", encoding="utf-8")
+
+    code, _ = run([str(file_path)], capsys)
+
+    assert code == 0
+
+    code, out = run([str(file_path), "--staged"], capsys)
+
+    assert code == ErrorCode.SyntaxProblem.value
+    assert "Errors (1):\n  Line 3: not-a-hash" in out
+
+
+def test_diff_base(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\nold-problem\n")
+    git_repo.git("tag", "base")
+    file_path.write_text(
+        f"new-problem\n# Apply black\n{history['black']}\nold-problem\n", encoding="utf-8"
+    )
+
+    code, out = run([str(file_path), "--diff-base", "base"], capsys)
+
+    assert code == ErrorCode.SyntaxProblem.value
+    assert "Errors (1):\n  Line 1: new-problem\n" in out
+    assert "Left out 1 problem(s) that are also in base because of --diff-base." in out
+
+    file_path.write_text(f"# Apply black\n{history['black']}\nold-problem\n", encoding="utf-8")
+
+    code, out = run([str(file_path), "--diff-base", "base"], capsys)
+
+    assert code == 0
+    assert "No errors found!" in out
+
+    code, out = run([file_path.name, "--diff-base", "nonexistent"], capsys)
+
+    assert code == ErrorCode.FileNotFound.value
+    assert out.endswith(
+        "No commit or tree named nonexistent, so .git-blame-ignore-revs can't be read from it.\n"
+    )
+
+
+def test_max_errors(git_repo: GitRepo, capsys: pytest.CaptureFixture[str]) -> None:
+    file_path = git_repo.write_ignore_file("".join(f"not-a-hash-{i}\n" for i in range(1, 6)))
+
+    code, out = run([str(file_path), "--max-errors", "2"], capsys)
+
+    assert code == ErrorCode.SyntaxProblem.value
+    assert out.endswith(
+        "\nErrors (5):\n"
+        "  Line 1: not-a-hash-1\n"
+        "  Line 2: not-a-hash-2\n"
+        "\n"
+        "... and 3 more not shown.\n"
+    )
+
+
+def test_fail_fast(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(
+        f"# Apply black\n{history['black']}\n{MISSING_COMMIT}\nnot-a-hash\n"
+    )
+    other = git_repo.path / "other" / ".git-blame-ignore-revs"
+    other.parent.mkdir()
+    other.write_text("not-a-hash\n", encoding="utf-8")
+
+    code, out = run([str(file_path), "--call-git", "--strict-comments", "--fail-fast"], capsys)
+
+    # The missing commit is never looked up, and the strict comments check isn't reported
+    assert code == ErrorCode.SyntaxProblem.value
+    assert out.endswith(
+        "\nErrors (1):\n"
+        "  Line 4: not-a-hash\n"
+        "\n"
+        "Stopped at the first error because of --fail-fast.\n"
+    )
+
+    file_path.write_text(f"{MISSING_COMMIT}\n# Apply black\n{history['black']}\n", encoding="utf-8")
+
+    code, out = run([str(file_path), str(other), "--call-git", "--fail-fast"], capsys)
+
+    # The second file isn't checked
+    assert code == ErrorCode.CommitsNotPresent.value
+    assert (
+        f"Missing commits (1):\n"
+        f"  Line 1: {MISSING_COMMIT} (no object has this hash) [object-not-found]\n"
+    ) in out
+    assert str(other) not in out
+    assert out.endswith("\nChecked 1 of 2 files, 1 with problems.\n")
+
+
+def test_strict_comments_git_requires_call_git(
+    git_repo: GitRepo, capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file("")
+
+    with pytest.raises(SystemExit) as excinfo:
+        main([str(file_path), "--strict-comments", "--strict-comments-git"])
+
+    assert excinfo.value.code == 2
+    assert "--strict-comments-git requires --strict-comments and --call-git." in (
+        capsys.readouterr().err
+    )
+
+
+def test_impact(git_repo: GitRepo, capsys: pytest.CaptureFixture[str]) -> None:
+    git_repo.commit("Add b", {"b.py": "x=1\ny=2\nz=3\n"})
+    reformat = git_repo.commit("Reformat b", {"b.py": "x = 1\ny = 2\nz=3\n"})
+    rename = git_repo.commit("Rename z", {"b.py": "x = 1\ny = 2\nw = 3\n"})
+    file_path = git_repo.write_ignore_file(
+        f"# Reformat b\n{reformat}\n# Rename z\n{rename}\n# Not in history\n{MISSING_COMMIT}\n"
+    )
+
+    code, out = run(["impact", str(file_path)], capsys)
+
+    assert code == ErrorCode.CommitsNotPresent.value
+    assert out == (
+        "Blame impact:\n"
+        f"  Line 2: {reformat} changes 2 line(s)\n"
+        f"  Line 4: {rename} changes 1 line(s)\n"
+        f"  Line 6: {MISSING_COMMIT} is not present in the Git history\n"
+    )
+
+
+def test_coverage(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    # Both reformatting commits only change whitespace, in a single line
+    file_path = git_repo.write_ignore_file(
+        f"# Apply black\n{history['black']}\n# pre-commit.ci\n{history['bot']}\n"
+    )
+
+    code, out = run(["coverage", str(file_path), "--min-lines", "1"], capsys)
+
+    assert code == 0
+    assert out.startswith("Formatting sweep coverage: 2 of 2 (100.0%)\n")
+    assert "Uncovered" not in out
+
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n")
+
+    code, out = run(["coverage", str(file_path), "--min-lines", "1"], capsys)
+
+    # Coverage is only reported, so it doesn't fail the run
+    assert code == 0
+    assert out == (
+        "Formatting sweep coverage: 1 of 2 (50.0%)\n"
+        "\n"
+        "Covered (1):\n"
+        f"  Commit {history['black']}: Apply black\n"
+        "\n"
+        "Uncovered (1):\n"
+        f"  Commit {history['bot']}: [pre-commit.ci] auto fixes from pre-commit.com hooks\n"
+    )
+
+
+def test_verify_blame(git_repo: GitRepo, capsys: pytest.CaptureFixture[str]) -> None:
+    # The last line is only whitespace
+    add = git_repo.commit("Add b", {"b.py": "x=1\ny=2\nz=3\n  \n"})
+    reformat = git_repo.commit("Reformat b", {"b.py": "x = 1\ny = 2\nz=3\n  \n"})
+    file_path = git_repo.write_ignore_file(f"# Reformat b\n{reformat}\n")
+
+    code, out = run(["verify-blame", "b.py"], capsys)
+
+    assert code == 0
+    assert out == (
+        f"Ignoring the revisions in {file_path} changes the attribution of 2 of 4 line(s) in "
+        "b.py.\n"
+        "\n"
+        "Reattributed lines:\n"
+        f"  {reformat} -> {add}: 2 line(s)\n"
+    )
+
+
+def test_sections_are_ordered_by_line(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    # The empty comment above the pre-commit-ci commit is also checked after parsing
+    file_path = git_repo.write_ignore_file(f"#\n{history['bot']}\n\n{history['black']}\n")
+
+    code, out = run([str(file_path), "--call-git", "--strict-comments", "--pre-commit-ci"], capsys)
+
+    assert code == ErrorCode.MissingComments.value
+    assert (
+        f"Strict comment errors (2):\n  Line 2: {history['bot']} (empty comment)\n"
+        f"  Line 4: {history['black']}\n"
+    ) in out
+
+
+def test_strict_comments_placeholders(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(
+        f"# TODO\n{history['black']}\n#   \n# Formatting.\n{history['bot']}\n"
+        f"# Formatting\n# Apply the pre-commit hooks\n{history['feature']}\n"
+    )
+
+    code, out = run([str(file_path), "--strict-comments"], capsys)
+
+    assert code == ErrorCode.MissingComments.value
+    assert (
+        "Strict comment errors (2):\n"
+        f"  Line 2: {history['black']} (placeholder comment 'TODO')\n"
+        f"  Line 5: {history['bot']} (placeholder comment 'Formatting.')\n"
+    ) in out
+
+
+def test_strict_comments_git_skips_blank_lines(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(
+        f"# Apply black\n\n{history['black']}\n# Run the formatter\n\n{history['feature']}\n"
+    )
+
+    code, out = run(
+        [str(file_path), "--call-git", "--strict-comments", "--strict-comments-git"], capsys
+    )
+
+    assert code == ErrorCode.MissingCommitMessageComments.value
+    assert "Comment diffs (1):\n  Line 6:\n    Comment: Run the formatter\n" in out
+
+
+@pytest.mark.skipif(sys.platform != "linux", reason="Requires file names that aren't valid UTF-8")
+def test_non_utf8_paths(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch, capsys: pytest.CaptureFixture[str]
+) -> None:
+    monkeypatch.chdir(tmp_path)
+    name = os.fsdecode(b"caf\xe9")
+    (tmp_path / name).mkdir()
+    git_repo = GitRepo(tmp_path / name)
+    git_repo.commit("Initial commit", {f"{name}.py": "x=1\n"})
+    black = git_repo.commit("Apply black", {f"{name}.py": "x = 1\n"})
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{black}\n")
+
+    code, out = run([str(file_path), "--call-git", "--report-unused-entries"], capsys)
+
+    assert code == 0
+    assert "All entries still affect the blame output!" in out
+
+    file_path.write_bytes(b"# caf\xe9\n")
+    code, out = run([str(file_path)], capsys)
+
+    assert code == ErrorCode.SyntaxProblem.value
+    assert "caf�/.git-blame-ignore-revs is not valid UTF-8" in out
+
+
+def test_encoding(git_repo: GitRepo, capsys: pytest.CaptureFixture[str]) -> None:
+    git_repo.commit("Initial commit", {"a.py": "x=1\n"})
+    black = git_repo.commit("Apply black to café", {"a.py": "x = 1\n"})
+    file_path = git_repo.write_ignore_file("")
+    file_path.write_bytes(f"# Apply black to café\n{black}\n".encode("latin1"))
+    strict = ["--call-git", "--strict-comments", "--strict-comments-git"]
+
+    code, out = run([str(file_path), *strict], capsys)
+
+    assert code == ErrorCode.SyntaxProblem.value
+    assert "Pass --encoding auto for a file with Latin-1 comments." in out
+
+    for encoding in ("latin1", "auto"):
+        code, out = run([str(file_path), *strict, "--encoding", encoding], capsys)
+
+        assert code == 0
+        assert "All comments match the corresponding commit messages!" in out
+
+    # A file that is valid UTF-8 is still decoded as UTF-8
+    file_path.write_text(f"# Apply black to café\n{black}\n", encoding="utf-8")
+    code, out = run([str(file_path), *strict, "--encoding", "auto"], capsys)
+
+    assert code == 0
+
+    # Fixes are written back in the encoding the file was read in
+    file_path.write_bytes(f"# Apply black to café\n{black.upper()}\n".encode("latin1"))
+    code, out = run([str(file_path), "--fix", "--encoding", "auto"], capsys)
+
+    assert code == 0
+    assert file_path.read_bytes() == f"# Apply black to café\n{black}\n".encode("latin1")
+
+
+def test_git_dir_relative(
+    git_repo: GitRepo,
+    history: dict[str, str],
+    monkeypatch: pytest.MonkeyPatch,
+    capsys: pytest.CaptureFixture[str],
+) -> None:
+    git_repo.commit(
+        "Add sub/.git-blame-ignore-revs",
+        {"sub/.git-blame-ignore-revs": f"# Apply black\n{history['black']}\n"},
+    )
+    # As Git exports it to hooks, which run at the top level of the working tree
+    monkeypatch.setenv("GIT_DIR", ".git")
+    monkeypatch.delenv("GIT_WORK_TREE", raising=False)
+
+    code, out = run(["sub/.git-blame-ignore-revs", "--call-git", "--check-tracked"], capsys)
+
+    assert code == 0
+    assert "All commits are present in the Git history!" in out
+
+
+def test_separate_git_dir(
+    git_repo: GitRepo,
+    history: dict[str, str],
+    tmp_path_factory: pytest.TempPathFactory,
+    monkeypatch: pytest.MonkeyPatch,
+    capsys: pytest.CaptureFixture[str],
+) -> None:
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n")
+    git_dir = tmp_path_factory.mktemp("git-dirs") / "repository.git"
+    # Moves the repository, leaving a .git file that points to it
+    git_repo.git("init", "--quiet", f"--separate-git-dir={git_dir}")
+
+    code, out = run([str(file_path), "--call-git", "--check-tracked"], capsys)
+
+    assert code == 0
+
+    # As tools that keep the repository apart from its working tree export it
+    (git_repo.path / ".git").unlink()
+    monkeypatch.setenv("GIT_DIR", str(git_dir))
+    monkeypatch.setenv("GIT_WORK_TREE", str(git_repo.path))
+    monkeypatch.chdir(tmp_path_factory.mktemp("elsewhere"))
+
+    code, out = run([str(file_path), "--call-git", "--check-tracked"], capsys)
+
+    assert code == 0
+
+
+def test_symlinked_file(
+    git_repo: GitRepo,
+    history: dict[str, str],
+    tmp_path_factory: pytest.TempPathFactory,
+    capsys: pytest.CaptureFixture[str],
+) -> None:
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n")
+    # A link from outside the repository, where Git would otherwise find no commits
+    link = tmp_path_factory.mktemp("elsewhere") / ".git-blame-ignore-revs"
+    link.symlink_to(file_path)
+
+    code, out = run([str(link), "--call-git"], capsys)
+
+    assert code == 0
+    assert "All commits are present in the Git history!" in out
+
+
+@pytest.mark.parametrize("report_path", ["given", "resolved"])
+def test_report_path(tmp_path: Path, capsys: pytest.CaptureFixture[str], report_path: str) -> None:
+    link = tmp_path / "link"
+    link.symlink_to(tmp_path / ".git-blame-ignore-revs")
+
+    code, out = run([str(link), "--report-path", report_path], capsys)
+
+    assert code == ErrorCode.FileNotFound.value
+    expected = link if report_path == "given" else (tmp_path / ".git-blame-ignore-revs").resolve()
+    assert out == f"[Errno 2] No such file or directory: '{expected}'\n"
+
+
+def test_output(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\nnot-a-hash\n")
+    _, expected = run([str(file_path)], capsys)
+    output = git_repo.path / "reports" / "blame" / "report.txt"
+
+    code, out = run([str(file_path), "--output", str(output)], capsys)
+
+    assert code == ErrorCode.SyntaxProblem.value
+    assert out == ""
+    assert output.read_text(encoding="utf-8") == expected
+    assert [path.name for path in output.parent.iterdir()] == ["report.txt"]
+
+    code, out = run([str(file_path), "--output", "-"], capsys)
+
+    assert code == ErrorCode.SyntaxProblem.value
+    assert out == expected
+
+
+def test_multiple_reports(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\nnot-a-hash\n")
+    _, expected = run([str(file_path)], capsys)
+    json_path = git_repo.path / "report.json"
+    sarif_path = git_repo.path / "report.sarif"
+
+    code, out = run(
+        [
+            str(file_path),
+            "--report",
+            "human=-",
+            "--report",
+            f"json={json_path}",
+            "--report",
+            f"sarif={sarif_path}",
+        ],
+        capsys,
+    )
+
+    assert code == ErrorCode.SyntaxProblem.value
+    assert out == expected
+    document = json.loads(json_path.read_text(encoding="utf-8"))
+    assert document["exit_code"] == code
+    assert document["files"][0]["checks"][0]["findings"][0]["text"] == "not-a-hash"
+    log = json.loads(sarif_path.read_text(encoding="utf-8"))
+    assert log["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["region"] == {
+        "startLine": 3
+    }
+
+    # Without --output or human=, only the requested formats are written
+    code, out = run([str(file_path), "--report", f"json={json_path}"], capsys)
+
+    assert code == ErrorCode.SyntaxProblem.value
+    assert out == ""
+
+
+def test_porcelain(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    line = history["feature"][:39]
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n{line}\n")
+
+    code, out = run([str(file_path), "--call-git", "--porcelain"], capsys)
+
+    assert code == ErrorCode.SyntaxProblem.value
+    assert out.splitlines() == [
+        "version\t1",
+        f"file\t{file_path}\t{code}",
+        f"finding\terror\terrors\t3\t{line}\tLine 3\t{line}",
+        "detail\tHash is 39 characters, expected 40",
+        f"detail\tDid you mean {history['feature']}?",
+        f"exit\t{code}",
+    ]
+    # Asking for the version makes no difference while there is only one
+    assert run([str(file_path), "--call-git", "--porcelain=v1"], capsys) == (code, out)
+
+
+def test_compact_format(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    line = history["feature"][:39]
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n{line}\n")
+
+    code, out = run(
+        [str(file_path), "--call-git", "--pre-commit-ci", "--format", "compact"], capsys
+    )
+
+    assert code == ErrorCode.SyntaxProblem.value | ErrorCode.MissingPreCommitCICommits.value
+    assert out.splitlines() == [
+        f"{file_path}:3:1: error[errors]: {line}",
+        f"{file_path}:3:1: note: Hash is 39 characters, expected 40",
+        f"{file_path}:3:1: note: Did you mean {history['feature']}?",
+        f"{file_path}: warning[missing-pre-commit-ci-commits]: "
+        f"Commit {history['bot']}: [pre-commit.ci] auto fixes from pre-commit.com hooks",
+    ]
+
+
+def test_azure_format(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\nnot;a]hash%\n")
+
+    code, out = run([str(file_path), "--call-git", "--pre-commit-ci", "--format", "azure"], capsys)
+
+    assert code == ErrorCode.SyntaxProblem.value | ErrorCode.MissingPreCommitCICommits.value
+    assert out.splitlines() == [
+        f"##vso[task.logissue type=error;sourcepath={file_path};code=errors;linenumber=3;"
+        "columnnumber=1]Line 3: not;a]hash%AZP25",
+        f"##vso[task.logissue type=warning;sourcepath={file_path};"
+        f"code=missing-pre-commit-ci-commits]Commit {history['bot']}: "
+        "[pre-commit.ci] auto fixes from pre-commit.com hooks",
+    ]
+
+
+def test_porcelain_with_format(tmp_path: Path, capsys: pytest.CaptureFixture[str]) -> None:
+    with pytest.raises(SystemExit) as excinfo:
+        main([str(tmp_path / ".git-blame-ignore-revs"), "--porcelain", "--format", "json"])
+
+    assert excinfo.value.code == 2
+    assert "--porcelain and --format can't be used together." in capsys.readouterr().err
+
+
+def test_reports_to_the_same_destination(
+    tmp_path: Path, capsys: pytest.CaptureFixture[str]
+) -> None:
+    with pytest.raises(SystemExit) as excinfo:
+        main([str(tmp_path / ".git-blame-ignore-revs"), "--output", "-", "--report", "json=-"])
+
+    assert excinfo.value.code == 2
+    assert "More than one report would be written to standard output." in (capsys.readouterr().err)
+
+
+@pytest.mark.parametrize("jobs", ["1", "2"])
+def test_multiple_files(
+    git_repo: GitRepo,
+    history: dict[str, str],
+    monkeypatch: pytest.MonkeyPatch,
+    capsys: pytest.CaptureFixture[str],
+    jobs: str,
+) -> None:
+    git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n")
+    other = git_repo.path / "docs" / ".git-blame-ignore-revs"
+    other.parent.mkdir()
+    other.write_text(f"# Not in history\n{MISSING_COMMIT}\n", encoding="utf-8")
+    junit_path = git_repo.path / "report.xml"
+    monkeypatch.chdir(git_repo.path)
+
+    code, out = run(
+        [
+            ".git-blame-ignore-revs",
+            "docs/.git-blame-ignore-revs",
+            "--call-git",
+            "--jobs",
+            jobs,
+            "--report",
+            "human=-",
+            "--report",
+            f"junit={junit_path}",
+        ],
+        capsys,
+    )
+
+    assert code == ErrorCode.CommitsNotPresent.value
+    assert out.startswith("==> .git-blame-ignore-revs <==\nValidation Results:\n")
+    assert (
+        "\n==> docs/.git-blame-ignore-revs <==\n"
+        "Validation Results:\n"
+        "Valid hashes (1):\n"
+        f"  Line 2: {MISSING_COMMIT}\n"
+    ) in out
+    assert f"Missing commits (1):\n  Line 2: {MISSING_COMMIT}" in out
+    assert out.endswith("\nChecked 2 files, 1 with problems.\n")
+    testsuites = ET.parse(junit_path).getroot()
+    assert [testsuite.get("name") for testsuite in testsuites] == [
+        ".git-blame-ignore-revs",
+        "docs/.git-blame-ignore-revs",
+    ]
+    assert [testsuite.get("failures") for testsuite in testsuites] == ["0", "1"]
+
+
+def test_interrupted(
+    git_repo: GitRepo,
+    history: dict[str, str],
+    monkeypatch: pytest.MonkeyPatch,
+    capsys: pytest.CaptureFixture[str],
+) -> None:
+    first = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n")
+    second = git_repo.path / "docs" / ".git-blame-ignore-revs"
+    second.parent.mkdir()
+    second.write_text(f"# Apply black\n{history['black']}\n", encoding="utf-8")
+    json_path = git_repo.path / "report.json"
+
+    def validate(file_path: Path, *args: Any, **kwargs: Any) -> ValidationResult:
+        if file_path.parent.name == "docs":
+            # Like a CI runner cancelling the job partway through
+            os.kill(os.getpid(), signal.SIGTERM)
+        return validate_git_blame_ignore_revs(file_path, *args, **kwargs)
+
+    monkeypatch.setattr(
+        "validate_git_blame_ignore_revs.__main__.validate_git_blame_ignore_revs", validate
+    )
+    handler = signal.getsignal(signal.SIGTERM)
+
+    code, out = run(
+        [str(first), str(second), "--report", "human=-", "--report", f"json={json_path}"],
+        capsys,
+    )
+
+    assert code == 128 + signal.SIGTERM
+    assert f"==> {first} <==\nValidation Results:\n" in out
+    assert str(second) not in out
+    assert out.endswith(
+        "\nChecked 1 of 2 files, 0 with problems.\n"
+        "\nInterrupted by SIGTERM, so the report is incomplete.\n"
+    )
+    assert [file["file"] for file in json.loads(json_path.read_text())["files"]] == [str(first)]
+    assert signal.getsignal(signal.SIGTERM) == handler
+
+
+def test_workspace(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch, capsys: pytest.CaptureFixture[str]
+) -> None:
+    monkeypatch.chdir(tmp_path)
+    for name in ["app", "lib", "tool"]:
+        (tmp_path / "mirrors" / name).mkdir(parents=True)
+    app = GitRepo(tmp_path / "mirrors" / "app")
+    app.write_ignore_file(f"# Not in history\n{MISSING_COMMIT}\n")
+    lib = GitRepo(tmp_path / "mirrors" / "lib")
+    lib.commit("Add ignore file", {"docs/ignore-revs": "\n"})
+    lib.git("config", "blame.ignoreRevsFile", "docs/ignore-revs")
+    # A repository without an ignore file is skipped
+    GitRepo(tmp_path / "mirrors" / "tool")
+
+    code, out = run(["--workspace", "mirrors", "--call-git"], capsys)
+
+    assert code == ErrorCode.CommitsNotPresent.value
+    assert "==> mirrors/app/.git-blame-ignore-revs <==" in out
+    assert "\n\n==> mirrors/lib/docs/ignore-revs <==" in out
+    assert out.endswith("\nChecked 2 files, 1 with problems.\n")
+
+    code, out = run(["--workspace", "mirrors/tool"], capsys)
+
+    assert code == ErrorCode.FileNotFound.value
+    assert out == "No .git-blame-ignore-revs files found in the repositories under mirrors/tool.\n"
+
+
+def test_git_not_found(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch, capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = tmp_path / ".git-blame-ignore-revs"
+    file_path.write_text(f"# Not in history\n{MISSING_COMMIT}\n", encoding="utf-8")
+    monkeypatch.chdir(tmp_path)
+    monkeypatch.setenv("PATH", str(tmp_path))
+
+    code, out = run([str(file_path), "--call-git"], capsys)
+
+    assert code == ErrorCode.GitNotFound.value
+    assert "Could not run git" in out
+    assert "--no-git" in out
+
+    code, out = run([str(file_path), "--call-git", "--strict-comments", "--no-git"], capsys)
+
+    assert code == 0
+    assert "Skipping checks that require Git: --call-git" in out
+    assert "All commit lines have comments above them!" in out
+
+
+def test_non_commit_objects(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    git_repo.git("tag", "--annotate", "--message", "Release", "v1.2.0", history["black"])
+    tag = git_repo.git("rev-parse", "v1.2.0")
+    tree = git_repo.git("rev-parse", f"{history['black']}^{{tree}}")
+    blob = git_repo.git("rev-parse", f"{history['black']}:a.py")
+    file_path = git_repo.write_ignore_file(
+        f"# Apply black\n{history['black']}\n# Tag\n{tag}\n# Tree\n{tree}\n# Blob\n{blob}\n"
+    )
+
+    code, out = run([str(file_path), "--call-git"], capsys)
+
+    assert code == ErrorCode.CommitsNotPresent.value
+    assert (
+        "Objects that aren't commits (3):\n"
+        f"  Line 4: {tag} (not a commit object: tag v1.2.0) [not-a-commit]\n"
+        f"  Line 6: {tree} (not a commit object: tree) [not-a-commit]\n"
+        f"  Line 8: {blob} (not a commit object: blob) [not-a-commit]\n"
+    ) in out
+    assert "Missing commits" not in out
+
+
+def test_explain_ambiguous_hash(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n")
+    # The blobs with these contents both have hashes starting with 6bb2
+    git_repo.commit("Add numbers", {"195.txt": "195\n", "389.txt": "389\n"})
+    blobs = [git_repo.git("rev-parse", f"HEAD:{name}") for name in ("195.txt", "389.txt")]
+
+    code, out = run(["explain", str(file_path), "6bb2"], capsys)
+
+    assert code == ErrorCode.CommitsNotPresent.value
+    assert out.startswith("6bb2 is ambiguous. It could be any of:\n")
+    for blob in blobs:
+        assert f"  {blob} blob\n" in out
+
+
+def test_check_metadata(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    (git_repo.path / ".validate-git-blame-ignore-revs.cfg").write_text(
+        "[metadata]\nrequired_keys = Tool\n\n[metadata.formats]\nTool = black|ruff\n",
+        encoding="utf-8",
+    )
+    file_path = git_repo.write_ignore_file(
+        f"# Apply black\n# Tool: black\n{history['black']}\n"
+        f"# Add g\n# Tool: yapf\n{history['feature']}\n"
+        f"# pre-commit autofix\n{history['bot']}\n"
+    )
+
+    code, out = run([str(file_path), "--check-metadata"], capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert (
+        "Metadata errors (2):\n"
+        f"  Line 6: {history['feature']} (Tool 'yapf' does not match 'black|ruff')\n"
+        f"  Line 8: {history['bot']} (missing Tool)\n"
+    ) in out
+
+
+def test_check_metadata_requires_config(
+    git_repo: GitRepo, capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file("")
+
+    with pytest.raises(SystemExit) as excinfo:
+        main([str(file_path), "--check-metadata"])
+
+    assert excinfo.value.code == 2
+    assert "--check-metadata requires a [metadata] section" in capsys.readouterr().err
+
+
+def test_require_pr_link(
+    git_repo: GitRepo,
+    history: dict[str, str],
+    tmp_path: Path,
+    monkeypatch: pytest.MonkeyPatch,
+    capsys: pytest.CaptureFixture[str],
+) -> None:
+    monkeypatch.setenv("XDG_CACHE_HOME", str(tmp_path / "cache"))
+    git_repo.git("remote", "add", "origin", "https://github.com/owner/name.git")
+    # Every pull request was squashed as the black commit
+    monkeypatch.setattr(
+        forge_api,
+        "_open",
+        lambda request: (
+            [] if "/commits" in request.full_url else {"merge_commit_sha": history["black"]},
+            Message(),
+        ),
+    )
+    file_path = git_repo.write_ignore_file(
+        f"# Apply black (#1)\n{history['black']}\n"
+        f"# Add g (#2)\n{history['feature']}\n"
+        f"# pre-commit autofix\n{history['bot']}\n"
+    )
+
+    code, out = run([str(file_path), "--require-pr-link"], capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert (
+        "Pull request reference errors (1):\n"
+        f"  Line 6: {history['bot']} (no pull request reference)\n"
+    ) in out
+
+    code, out = run(
+        [str(file_path), "--call-git", "--require-pr-link", "--verify-pr-links"], capsys
+    )
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert (
+        "Pull request reference errors (2):\n"
+        f"  Line 4: {history['feature']} (#2 does not contain the commit)\n"
+        f"  Line 6: {history['bot']} (no pull request reference)\n"
+    ) in out
+
+
+def test_offline(
+    git_repo: GitRepo,
+    history: dict[str, str],
+    monkeypatch: pytest.MonkeyPatch,
+    capsys: pytest.CaptureFixture[str],
+) -> None:
+    # Restore the environment, which --offline changes for the Git commands it runs
+    monkeypatch.delenv("GIT_NO_LAZY_FETCH", raising=False)
+    file_path = git_repo.write_ignore_file(f"# Apply black (#1)\n{history['black']}\n")
+
+    with pytest.raises(SystemExit) as excinfo:
+        main([str(file_path), "--call-git", "--require-pr-link", "--verify-pr-links", "--offline"])
+
+    assert excinfo.value.code == 2
+    assert "--verify-pr-links needs the network, so it can't be used with --offline." in (
+        capsys.readouterr().err
+    )
+
+    # Nothing is sent over the network either
+    with pytest.raises(SystemExit) as excinfo:
+        main([str(file_path), "--bitbucket-insights", "--offline"])
+
+    assert excinfo.value.code == 2
+    assert "--bitbucket-insights needs the network, so it can't be used with --offline." in (
+        capsys.readouterr().err
+    )
+
+    code, _ = run([str(file_path), "--call-git", "--require-pr-link", "--offline"], capsys)
+
+    assert code == 0
+    assert os.environ["GIT_NO_LAZY_FETCH"] == "1"
+
+
+def test_hyperlinks(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    git_repo.git("remote", "add", "origin", "git@github.com:owner/name.git")
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n{MISSING_COMMIT}\n")
+    json_path = git_repo.path / "report.json"
+    black_url = f"https://github.com/owner/name/commit/{history['black']}"
+    missing_url = f"https://github.com/owner/name/commit/{MISSING_COMMIT}"
+
+    code, out = run(
+        [
+            str(file_path),
+            "--call-git",
+            "--hyperlinks",
+            "always",
+            "--output",
+            "-",
+            "--report",
+            f"json={json_path}",
+        ],
+        capsys,
+    )
+
+    assert code == ErrorCode.CommitsNotPresent.value
+    assert f"  Line 2: \x1b]8;;{black_url}\x1b\\{history['black']}\x1b]8;;\x1b\\\n" in out
+    assert (
+        "Missing commits (1):\n"
+        f"  Line 3: \x1b]8;;{missing_url}\x1b\\{MISSING_COMMIT}\x1b]8;;\x1b\\ "
+        "(no object has this hash) [object-not-found]\n"
+    ) in out
+    findings = json.loads(json_path.read_text())["files"][0]["checks"][1]["findings"]
+    assert [finding["url"] for finding in findings] == [missing_url]
+
+    # By default, only a report printed to a terminal has links
+    code, out = run([str(file_path), "--call-git"], capsys)
+
+    assert "\x1b]8;;" not in out
+
+
+def test_lang(
+    git_repo: GitRepo,
+    history: dict[str, str],
+    monkeypatch: pytest.MonkeyPatch,
+    capsys: pytest.CaptureFixture[str],
+) -> None:
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n{MISSING_COMMIT}\n")
+    json_path = git_repo.path / "report.json"
+
+    code, out = run(
+        [
+            str(file_path),
+            "--call-git",
+            "--lang",
+            "de",
+            "--output",
+            "-",
+            "--report",
+            f"json={json_path}",
+            "--fail-fast",
+        ],
+        capsys,
+    )
+
+    assert code == ErrorCode.CommitsNotPresent.value
+    assert f"Fehlende Commits (1):\n  Zeile 3: {MISSING_COMMIT}" in out
+    assert "\nWegen --fail-fast beim ersten Fehler angehalten.\n" in out
+    # Machine-readable reports stay in English
+    report = json.loads(json_path.read_text())["files"][0]
+    assert "Missing commits" in [check["title"] for check in report["checks"]]
+    assert report["notes"] == ["Stopped at the first error because of --fail-fast."]
+
+    # Without --lang, the language comes from the environment
+    monkeypatch.setenv("LANGUAGE", "de_DE")
+    code, out = run([str(file_path), "--call-git"], capsys)
+
+    assert "Fehlende Commits (1):" in out
+
+
+def test_github_summary(
+    git_repo: GitRepo,
+    history: dict[str, str],
+    monkeypatch: pytest.MonkeyPatch,
+    capsys: pytest.CaptureFixture[str],
+) -> None:
+    git_repo.git("remote", "add", "origin", "git@github.com:owner/name.git")
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n{MISSING_COMMIT}\n")
+    summary = git_repo.path / "summary.md"
+    summary.write_text("# Earlier step\n\n", encoding="utf-8")
+    monkeypatch.setenv("GITHUB_STEP_SUMMARY", str(summary))
+
+    code, out = run([str(file_path), "--call-git", "--github-summary"], capsys)
+
+    assert code == ErrorCode.CommitsNotPresent.value
+    assert "Missing commits (1):" in out
+    assert summary.read_text(encoding="utf-8") == (
+        "# Earlier step\n"
+        "\n"
+        "\n"
+        f"### `{file_path}`\n"
+        "\n"
+        "#### Missing commits (1 error)\n"
+        "\n"
+        "| Entry | Problem |\n"
+        "| --- | --- |\n"
+        f"| [Line 3](https://github.com/owner/name/commit/{MISSING_COMMIT}) | {MISSING_COMMIT} "
+        "(no object has this hash) [object-not-found] |\n"
+        "\n"
+        "Passed: Errors, Objects that aren't commits, Replaced commits.\n"
+    )
+
+    # Outside of GitHub Actions, there is no summary to write to
+    monkeypatch.delenv("GITHUB_STEP_SUMMARY")
+    assert run([str(file_path), "--call-git", "--github-summary"], capsys) == (code, out)
+
+
+def test_bitbucket_insights(
+    git_repo: GitRepo,
+    history: dict[str, str],
+    monkeypatch: pytest.MonkeyPatch,
+    capsys: pytest.CaptureFixture[str],
+) -> None:
+    git_repo.git("remote", "add", "origin", "git@bitbucket.org:owner/name.git")
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n{MISSING_COMMIT}\n")
+    requests: list[tuple[str, str, Any]] = []
+    monkeypatch.setattr(forge, "_send_bitbucket_json", lambda *request: requests.append(request))
+    monkeypatch.setenv("BITBUCKET_COMMIT", history["feature"])
+
+    code, out = run([str(file_path), "--call-git", "--bitbucket-insights"], capsys)
+
+    assert code == ErrorCode.CommitsNotPresent.value
+    assert f"Published a Code Insights report on {history['feature'][:12]} to Bitbucket." in out
+    url = (
+        f"https://api.bitbucket.org/2.0/repositories/owner/name/commit/{history['feature']}"
+        "/reports/validate-git-blame-ignore-revs-git-blame-ignore-revs"
+    )
+    assert [(method, request_url) for method, request_url, _ in requests] == [
+        ("PUT", url),
+        ("POST", f"{url}/annotations"),
+    ]
+    assert requests[0][2]["result"] == "FAILED"
+    assert [annotation["line"] for annotation in requests[1][2]] == [3]
+
+    # Only Bitbucket Cloud has the API
+    git_repo.git("remote", "set-url", "origin", "git@github.com:owner/name.git")
+    code, out = run([str(file_path), "--call-git", "--bitbucket-insights"], capsys)
+
+    assert code == ErrorCode.CommitsNotPresent.value | ErrorCode.FileNotFound.value
+    assert (
+        "Could not publish the Code Insights report to Bitbucket: the remote isn't on bitbucket.org"
+    ) in out
+
+
+def test_warm_cache(
+    git_repo: GitRepo,
+    history: dict[str, str],
+    tmp_path: Path,
+    monkeypatch: pytest.MonkeyPatch,
+    capsys: pytest.CaptureFixture[str],
+) -> None:
+    monkeypatch.setenv("XDG_CACHE_HOME", str(tmp_path / "cache"))
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n")
+    options = ["--call-git", "--check-semantic-changes"]
+
+    code, out = run(["warm-cache", str(file_path), *options], capsys)
+
+    assert code == 0
+    assert out == f"Cached the results for {file_path}.\n"
+
+    # Validating again with the same options only reads the cache
+    def validate(*args: Any, **kwargs: Any) -> None:
+        raise AssertionError("validated again")
+
+    monkeypatch.setattr(cache, "validate_git_blame_ignore_revs", validate)
+    code, out = run([str(file_path), *options, "--cache"], capsys)
+
+    assert code == 0
+    assert "No commits appear to contain semantic changes!" in out
+
+
+def test_warm_cache_partial_clone(
+    git_repo: GitRepo,
+    history: dict[str, str],
+    tmp_path: Path,
+    monkeypatch: pytest.MonkeyPatch,
+    capsys: pytest.CaptureFixture[str],
+) -> None:
+    monkeypatch.setenv("XDG_CACHE_HOME", str(tmp_path / "cache"))
+    git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n")
+    git_repo.git("config", "uploadpack.allowFilter", "true")
+    git_repo.git("config", "uploadpack.allowAnySHA1InWant", "true")
+    clone = tmp_path / "clone"
+    git_repo.git("clone", "--quiet", "--filter=blob:none", git_repo.path.as_uri(), str(clone))
+    file_path = clone / ".git-blame-ignore-revs"
+    # The blob the commit replaced isn't checked out, so a clone without blobs doesn't have it
+    blob = git_repo.git("rev-parse", f"{history['initial']}:a.py")
+
+    def has_blob() -> bool:
+        command = ["git", "-C", str(clone), "cat-file", "-e", blob]
+        env = {**os.environ, "GIT_NO_LAZY_FETCH": "1"}
+        return subprocess.run(command, env=env, capture_output=True).returncode == 0
+
+    assert not has_blob()
+
+    code, out = run(["warm-cache", str(file_path), "--call-git"], capsys)
+
+    assert code == 0
+    assert out == (
+        f"Fetched the objects for the 1 commit(s) in {file_path}.\n"
+        f"Cached the results for {file_path}.\n"
+    )
+    assert has_blob()
+
+
+def test_remote(
+    git_repo: GitRepo,
+    history: dict[str, str],
+    tmp_path: Path,
+    monkeypatch: pytest.MonkeyPatch,
+    capsys: pytest.CaptureFixture[str],
+) -> None:
+    monkeypatch.setenv("XDG_CACHE_HOME", str(tmp_path / "cache"))
+    git_repo.git("remote", "add", "origin", "git@github.com:fork/name.git")
+    git_repo.git("remote", "add", "upstream", "git@github.com:owner/name.git")
+    urls: list[str] = []
+    monkeypatch.setattr(
+        forge_api,
+        "_open",
+        lambda request: (
+            urls.append(request.full_url) or {"merge_commit_sha": history["black"]},
+            Message(),
+        ),
+    )
+    file_path = git_repo.write_ignore_file(f"# Apply black (#1)\n{history['black']}\n")
+
+    code, out = run(
+        [
+            str(file_path),
+            "--call-git",
+            "--require-pr-link",
+            "--verify-pr-links",
+            "--remote",
+            "upstream",
+            "--hyperlinks",
+            "always",
+        ],
+        capsys,
+    )
+
+    assert code == 0
+    assert urls == ["https://api.github.com/repos/owner/name/pulls/1"]
+    assert f"https://github.com/owner/name/commit/{history['black']}" in out
+
+    # The pull request is looked up again only without the cache
+    verify = [str(file_path), "--call-git", "--require-pr-link", "--verify-pr-links"]
+    code, _ = run([*verify, "--remote", "upstream"], capsys)
+    assert code == 0
+    code, _ = run([*verify, "--remote", "upstream", "--forge-cache-ttl", "0"], capsys)
+    assert code == 0
+    assert len(urls) == 2
+
+    code, out = run(
+        [str(file_path), "--call-git", "--require-pr-link", "--verify-pr-links", "--remote", "x"],
+        capsys,
+    )
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert f"Line 2: {history['black']} (#1 could not be checked: no x remote)" in out
+
+
+def test_ticket_pattern(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    (git_repo.path / ".validate-git-blame-ignore-revs.cfg").write_text(
+        "[comments]\nticket_pattern = JIRA-[0-9]+\n", encoding="utf-8"
+    )
+    file_path = git_repo.write_ignore_file(
+        f"# Apply black\n# JIRA-12\n{history['black']}\n# Add g (PROJ-3)\n{history['feature']}\n"
+    )
+
+    code, out = run([str(file_path)], capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert f"Missing ticket references (1):\n  Line 5: {history['feature']}\n" in out
+
+    code, out = run([str(file_path), "--ticket-pattern", "[A-Z]+-[0-9]+"], capsys)
+
+    assert code == 0
+    assert "All comments reference a ticket matching [A-Z]+-[0-9]+!" in out
+
+
+def test_comment_match(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(
+        f"# black\n{history['black']}\n# match: prefix\n# Add\n{history['feature']}\n"
+    )
+    argv = [str(file_path), "--call-git", "--strict-comments", "--strict-comments-git"]
+
+    code, out = run(argv, capsys)
+
+    assert code == ErrorCode.MissingCommitMessageComments.value
+    assert "Comment diffs (1):\n  Line 2:\n    Comment: black\n" in out
+
+    code, out = run([*argv, "--comment-match", "substring"], capsys)
+
+    assert code == 0
+
+
+def test_comment_match_similar(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(
+        f"# Applied Black.\n{history['black']}\n# Add a function\n{history['feature']}\n"
+    )
+    argv = [str(file_path), "--call-git", "--strict-comments", "--strict-comments-git"]
+
+    code, out = run([*argv, "--comment-match", "similar"], capsys)
+
+    assert code == ErrorCode.MissingCommitMessageComments.value
+    assert (
+        "Comment diffs (1):\n"
+        "  Line 4:\n"
+        "    Comment: Add a function\n"
+        "    Commit message: Add g\n"
+        "    Similarity: 0.42\n"
+    ) in out
+
+    code, out = run([*argv, "--comment-match", "similar", "--similarity-threshold", "0.4"], capsys)
+
+    assert code == 0
+
+
+def test_normalize(git_repo: GitRepo, capsys: pytest.CaptureFixture[str]) -> None:
+    commit = git_repo.commit("Don’t indent with tabs", {"a.py": "x = 1\n"})
+    file_path = git_repo.write_ignore_file(f"# don't indent with tabs\n{commit}\n")
+    argv = [str(file_path), "--call-git", "--strict-comments", "--strict-comments-git"]
+
+    code, _ = run(argv, capsys)
+
+    assert code == ErrorCode.MissingCommitMessageComments.value
+
+    code, _ = run([*argv, "--normalize", "NFKC", "--casefold"], capsys)
+
+    assert code == 0
+
+
+def test_check_tracked(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n")
+    argv = [str(file_path), "--call-git", "--check-tracked"]
+
+    code, out = run(argv, capsys)
+
+    assert code == 0
+    assert "The file is committed and not ignored by Git!" in out
+
+    git_repo.git("rm", "--cached", "--quiet", ".git-blame-ignore-revs")
+    git_repo.git("commit", "--quiet", "--message", "Stop tracking .git-blame-ignore-revs")
+    (git_repo.path / ".gitignore").write_text(".git-blame-*\n", encoding="utf-8")
+
+    code, out = run(argv, capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert (
+        "Tracking problems (2):\n"
+        f"  {file_path}: not tracked by Git\n"
+        f"  {file_path}: matched by the ignore rule .gitignore:1:.git-blame-*\n"
+    ) in out
+
+    git_repo.git("add", "--force", ".git-blame-ignore-revs")
+
+    code, out = run(argv, capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert f"  {file_path}: staged but not committed\n" in out
+
+
+def test_append_only(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    black = f"# Apply black\n{history['black']}\n"
+    bot = f"# [pre-commit.ci] auto fixes\n{history['bot']}\n"
+    feature = f"# Add g\n{history['feature']}\n"
+    file_path = git_repo.write_ignore_file(f"{black}\n{feature}")
+
+    # Appending to the end of a section other than the last is fine
+    file_path.write_text(f"{black}{bot}\n{feature}", encoding="utf-8")
+    code, out = run([str(file_path), "--append-only", "HEAD"], capsys)
+
+    assert code == 0
+    assert "Entries were only appended since HEAD!" in out
+
+    file_path.write_text(
+        f"{bot}{black}\n# Add g to a.py\n{history['feature']}\n", encoding="utf-8"
+    )
+    code, out = run([str(file_path), "--append-only", "HEAD"], capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert (
+        "Ordering problems (2):\n"
+        f"  Line 2: {history['bot']} (inserted before existing entries of its section)\n"
+        f"  Line 7: {history['feature']} (comments changed)\n"
+    ) in out
+
+
+def test_compare_remote(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    black = f"# Apply black\n{history['black']}\n"
+    feature = f"# Add g\n{history['feature']}\n"
+    file_path = git_repo.write_ignore_file(f"{black}{feature}")
+    # The repository is its own remote, whose default branch has the committed file
+    git_repo.git("remote", "add", "origin", str(git_repo.path))
+
+    code, out = run([str(file_path), "--compare-remote"], capsys)
+
+    assert code == 0
+    assert "The entries match the default branch of origin!" in out
+
+    file_path.write_text(f"{black}# Fix the bot's formatting\n{history['bot']}\n", encoding="utf-8")
+    code, out = run([str(file_path), "--compare-remote"], capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert (
+        "Differences from the default branch (2):\n"
+        f"  Line 4: {history['bot']} (not on the default branch of origin)\n"
+        f"  Commit {history['feature']}: only on the default branch of origin, on line 4\n"
+    ) in out
+
+    # Offline, the default branch as last fetched is used, if it is known
+    code, out = run([str(file_path), "--compare-remote", "--offline"], capsys)
+
+    assert code == 0
+    assert "could not be compared with the default branch of origin" in out
+
+    git_repo.git("update-ref", "refs/remotes/origin/main", history["initial"])
+    git_repo.git("symbolic-ref", "refs/remotes/origin/HEAD", "refs/remotes/origin/main")
+    code, out = run([str(file_path), "--compare-remote", "--offline"], capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert f"  Line 2: {history['black']} (not on the default branch of origin)\n" in out
+
+
+def test_check_duplicate_patches(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    git_repo.git("revert", "--no-edit", history["feature"])
+    git_repo.git("cherry-pick", history["feature"])
+    backport = git_repo.git("rev-parse", "HEAD")
+    file_path = git_repo.write_ignore_file(
+        f"# Apply black\n{history['black']}\n# Add g\n{history['feature']}\n# Add g\n{backport}\n"
+    )
+
+    code, out = run([str(file_path), "--call-git", "--check-duplicate-patches"], capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert (
+        "Duplicate patches (1):\n"
+        f"  Line 6: {backport} (same change as line 4: {history['feature']})\n"
+    ) in out
+
+
+def test_check_renames(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    git_repo.git("mv", "a.py", "b.py")
+    git_repo.git("commit", "--quiet", "--message", "Rename a.py")
+    rename = git_repo.git("rev-parse", "HEAD")
+    file_path = git_repo.write_ignore_file(
+        f"# Apply black\n{history['black']}\n# Rename a.py\n{rename}\n"
+    )
+
+    code, out = run([str(file_path), "--call-git", "--check-renames"], capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert f"Rename-heavy commits (1):\n  Line 4: {rename} (1 of 1 changed files renamed)\n" in out
+
+
+def test_check_sections(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(
+        f"## prettier\n# Apply black\n{history['black']}\n\n## black\n{history['bot']}\n"
+    )
+
+    code, out = run([str(file_path), "--call-git", "--check-sections"], capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert (
+        "Entries in the wrong section (1):\n"
+        f"  Line 3: {history['black']} "
+        "(looks like a black commit, but is in the 'prettier' section (line 1))\n"
+    ) in out
+
+
+def test_score_formatting(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(
+        f"# Apply black\n{history['black']}\n# Add g\n{history['feature']}\n"
+    )
+
+    code, out = run([str(file_path), "--call-git", "--score-formatting"], capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert (
+        "Unlikely formatting commits (1):\n"
+        f"  Line 4: {history['feature']} (formatting likelihood 2%)\n"
+    ) in out
+
+    code, out = run(
+        [str(file_path), "--call-git", "--score-formatting", "--formatting-threshold", "0"], capsys
+    )
+
+    assert code == 0
+    assert "All commits look like formatting changes!" in out
+
+
+def test_check_entry_dates(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    git_repo.env["GIT_COMMITTER_DATE"] = "2021-01-01T00:00:00+0000"
+    reformat = git_repo.commit("Reformat", {"a.py": "def f(x):\n    return (x + 1)\n"})
+    # Committed with an earlier date than the commit it lists, as if that was rewritten since
+    git_repo.env["GIT_COMMITTER_DATE"] = "2020-01-01T00:00:00+0000"
+    file_path = git_repo.write_ignore_file(
+        f"# Apply black\n{history['black']}\n# Reformat\n{reformat}\n"
+    )
+    added_in = git_repo.git("rev-parse", "HEAD")
+
+    code, out = run([str(file_path), "--call-git", "--check-entry-dates"], capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert (
+        "Entries added before their commits (1):\n"
+        f"  Line 4: {reformat} (added in {added_in}, which is older)\n"
+    ) in out
+
+
+def test_warn_older_than(
+    git_repo: GitRepo,
+    history: dict[str, str],
+    tmp_path: Path,
+    monkeypatch: pytest.MonkeyPatch,
+    capsys: pytest.CaptureFixture[str],
+) -> None:
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n")
+
+    code, out = run([str(file_path), "--call-git", "--warn-older-than", "2y"], capsys)
+
+    # Old entries don't fail the run
+    assert code == 0
+    assert (
+        "Old entries (1):\n"
+        f"  Line 2: {history['black']} (committed on 2020-01-01)\n"
+        "    Check whether ignoring it still makes a difference, "
+        "such as with --report-unused-entries.\n"
+    ) in out
+
+    code, out = run([str(file_path), "--call-git", "--warn-older-than", "100y"], capsys)
+
+    assert code == 0
+    assert "No entries are older than 100y!" in out
+
+    with pytest.raises(SystemExit):
+        main([str(file_path), "--call-git", "--warn-older-than", "2 decades"])
+    assert "Invalid --warn-older-than: expected a number and a unit" in capsys.readouterr().err
+
+    # The results are cached on the duration rather than on when it started
+    monkeypatch.setenv("XDG_CACHE_HOME", str(tmp_path / "cache"))
+    options = [str(file_path), "--call-git", "--warn-older-than", "2y", "--cache"]
+    run(options, capsys)
+
+    def validate(*args: Any, **kwargs: Any) -> None:
+        raise AssertionError("validated again")
+
+    monkeypatch.setattr(cache, "validate_git_blame_ignore_revs", validate)
+    monkeypatch.setattr(time, "time", lambda: 2_000_000_000.0)
+    code, out = run(options, capsys)
+
+    assert code == 0
+    assert "Old entries (1):\n" in out
+
+
+def test_check_default_branch(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(
+        f"# Apply black\n{history['black']}\n# Add g\n{history['feature']}\n"
+    )
+
+    code, out = run([str(file_path), "--call-git", "--check-default-branch"], capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert (
+        f"  Line 2: {history['black']} (could not be checked: the default branch of origin "
+        "isn't known)\n"
+    ) in out
+
+    # As if the feature commit was only pushed to a branch that was squashed when merged
+    git_repo.git("update-ref", "refs/remotes/origin/main", history["bot"])
+    git_repo.git("symbolic-ref", "refs/remotes/origin/HEAD", "refs/remotes/origin/main")
+
+    code, out = run([str(file_path), "--call-git", "--check-default-branch"], capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert (
+        "Commits not on the default branch (1):\n"
+        f"  Line 4: {history['feature']} (not on origin/main) [unreachable-from-ref]\n"
+    ) in out
+
+
+def test_check_unreferenced(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    # As if the commit was amended, leaving the original in the reflog
+    amended = git_repo.commit("Apply prettier", {"style.css": "a {}\n"})
+    git_repo.git("reset", "--quiet", "--hard", "HEAD~1")
+    # A commit that no ref or reflog entry ever pointed to
+    dangling = git_repo.git("commit-tree", "HEAD^{tree}", "-p", "HEAD", "-m", "Apply isort")
+    file_path = git_repo.write_ignore_file(
+        f"# Apply black\n{history['black']}\n# Apply prettier\n{amended}\n"
+        f"# Apply isort\n{dangling}\n"
+    )
+
+    code, out = run([str(file_path), "--call-git", "--check-unreferenced"], capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert (
+        "Unreferenced commits (2):\n"
+        f"  Line 4: {amended} (only reachable through the reflog) [reflog-only]\n"
+        "    git gc will delete it once the reflog entry expires. List the commit that replaced "
+        "it, such as the amended or squashed one, instead.\n"
+        f"  Line 6: {dangling} (not reachable from any ref or the reflog) [dangling]\n"
+    ) in out
+
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n")
+    code, out = run([str(file_path), "--call-git", "--check-unreferenced"], capsys)
+
+    assert code == 0
+    assert "Every commit is reachable from a ref!" in out
+
+
+def test_reachable_from(
+    git_repo: GitRepo,
+    history: dict[str, str],
+    tmp_path: Path,
+    monkeypatch: pytest.MonkeyPatch,
+    capsys: pytest.CaptureFixture[str],
+) -> None:
+    monkeypatch.setenv("XDG_CACHE_HOME", str(tmp_path / "cache"))
+    file_path = git_repo.write_ignore_file(
+        f"# Apply black\n{history['black']}\n# Add g\n{history['feature']}\n"
+    )
+    # As if a release branch was cut before the feature commit
+    git_repo.git("branch", "release/1.0", history["black"])
+
+    code, out = run([str(file_path), "--call-git", "--reachable-from", "release/*"], capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert (
+        "Commits not reachable from required refs (1):\n"
+        f"  Line 4: {history['feature']} [unreachable-from-ref]\n"
+        "    not reachable from release/1.0\n"
+    ) in out
+
+    (git_repo.path / ".validate-git-blame-ignore-revs.cfg").write_text(
+        "[commits]\nreachable_from = release/1.0\n", encoding="utf-8"
+    )
+    code, out = run([str(file_path), "--call-git"], capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert "    not reachable from release/1.0\n" in out
+
+    git_repo.git("branch", "release/2.0")
+    code, out = run([str(file_path), "--call-git", "--reachable-from", "release/2.0"], capsys)
+
+    assert code == 0
+    assert "All commits are reachable from release/2.0!" in out
+
+    # Moving a ref without moving HEAD doesn't reuse a cached result
+    options = [str(file_path), "--call-git", "--reachable-from", "release/1.0", "--cache"]
+    code, _ = run(options, capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+
+    git_repo.git("branch", "--force", "release/1.0", "main")
+    code, out = run(options, capsys)
+
+    assert code == 0
+    assert "All commits are reachable from release/1.0!" in out
+
+
+def test_profile(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(f"{history['black']}\n# {MISSING_COMMIT}\n")
+
+    code, out = run([str(file_path), "--profile", "minimal"], capsys)
+
+    assert code == 0
+    assert "Missing commits" not in out
+
+    code, out = run([str(file_path), "--profile", "ci"], capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert "All commits are present in the Git history!" in out
+    assert "Commented-out entries (1):" in out
+    assert "Strict comment errors" not in out
+
+    # Checks given as options are added to those of the profile
+    code, out = run([str(file_path), "--profile", "ci", "--strict-comments"], capsys)
+
+    assert code == ErrorCode.MissingComments.value | ErrorCode.FailedAdditionalChecks.value
+    assert "Strict comment errors (1):" in out
+
+    code, out = run([str(file_path), "--profile", "strict"], capsys)
+
+    assert code == ErrorCode.MissingComments.value | ErrorCode.FailedAdditionalChecks.value
+    assert "No two entries make the same change!" in out
+
+
+def test_profile_empty_file(git_repo: GitRepo, capsys: pytest.CaptureFixture[str]) -> None:
+    file_path = git_repo.write_ignore_file("")
+
+    code, _ = run([str(file_path), "--profile", "ci"], capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+
+    # The configuration file overrides the profile, and options override both
+    (git_repo.path / ".validate-git-blame-ignore-revs.cfg").write_text(
+        "[file]\nempty = warn\n", encoding="utf-8"
+    )
+    code, out = run([str(file_path), "--profile", "ci"], capsys)
+
+    assert code == 0
+    assert "Warning: " in out
+
+    code, out = run([str(file_path), "--profile", "ci", "--empty-file", "allow"], capsys)
+
+    assert code == 0
+    assert "ignores no commits" not in out
+
+
+def test_explain_check(capsys: pytest.CaptureFixture[str]) -> None:
+    code, out = run(["explain-check"], capsys)
+
+    assert code == 0
+    assert out.splitlines()[:2] == ["errors: Errors", "denied-commits: Denied commits"]
+
+    code, out = run(["explain-check", "strict-comment-errors"], capsys)
+
+    assert code == 0
+    assert out.startswith(
+        "strict-comment-errors: Strict comment errors\n"
+        "  Severity: warning\n"
+        "  Options: --strict-comments\n"
+    )
+
+    with pytest.raises(SystemExit) as excinfo:
+        main(["explain-check", "no-such-check"])
+
+    assert excinfo.value.code == 2
+    assert "Unknown check 'no-such-check'" in capsys.readouterr().err
+
+
+def test_check_registry(tmp_path: Path) -> None:
+    # Every check that can be reported is documented, in the order of the report
+    file_path = tmp_path / ".git-blame-ignore-revs"
+    file_path.write_text("", encoding="utf-8")
+    args = build_parser().parse_args(
+        [
+            "--call-git",
+            "--check-tracked",
+            "--append-only",
+            "HEAD",
+            "--compare-remote",
+            "--strict-comments",
+            "--strict-comments-git",
+            "--pre-commit-ci",
+            "--trailer",
+            "Blame-Ignore",
+            "--report-unused-entries",
+            "--check-duplicate-patches",
+            "--check-default-branch",
+            "--check-unreferenced",
+            "--check-semantic-changes",
+            "--check-sections",
+            "--check-renames",
+            "--score-formatting",
+            "--check-entry-dates",
+            "--warn-older-than",
+            "1y",
+            "--check-metadata",
+            "--require-pr-link",
+            "--check-commented-out",
+            "--check-blank-lines",
+            "--check-spelling",
+        ]
+    )
+
+    result = validate_git_blame_ignore_revs(file_path)
+    denylist = Denylist(commits=[], subjects=[], authors=[])
+    sections = result_sections(
+        args, result, "JIRA-[0-9]+", "Blame-Ignore", ["release/*"], None, denylist, [], [], []
+    )
+
+    assert [(rule_id(section.title), section.severity) for section in sections] == [
+        (check_id, check.severity) for check_id, check in CHECKS.items()
+    ]
+
+
+def test_serve_validator(git_repo: GitRepo, history: dict[str, str]) -> None:
+    args = build_parser().parse_args(["--call-git"])
+    args.hyperlinks = False
+    configs = {"app": default_config(), None: default_config()}
+    metrics = Metrics()
+    validate = serve_validator(args, {"app": git_repo.path}, configs, metrics)
+
+    document = json.loads(validate("app", f"# Apply black\n{history['black']}\n{MISSING_COMMIT}\n"))
+
+    assert document["exit_code"] == ErrorCode.CommitsNotPresent.value
+    assert document["files"][0]["file"].endswith(".git-blame-ignore-revs")
+    findings = document["files"][0]["checks"][1]["findings"]
+    assert [(finding["line"], finding["commit"]) for finding in findings] == [(3, MISSING_COMMIT)]
+    # The contents are only held in memory
+    assert not (git_repo.path / ".git-blame-ignore-revs").exists()
+    lines = metrics.render().replace("validate_git_blame_ignore_revs_", "").splitlines()
+    assert 'validations_total{repository="app",outcome="failed"} 1' in lines
+    assert 'findings_total{check="Missing commits",severity="error"} 1' in lines
+
+    with pytest.raises(BadRequest, match="Unknown repository: other"):
+        validate("other", "")
+    with pytest.raises(BadRequest, match="A repository is required"):
+        validate(None, "")
+
+
+def test_history(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    name = ".git-blame-ignore-revs"
+    git_repo.commit("Add the ignore file", {name: f"# Apply black\n{history['black']}\n"})
+    added = git_repo.commit(
+        "List the commit of the bot",
+        {name: f"# Apply black\n{history['black']}\n# Bot\n{MISSING_COMMIT}\n"},
+        author="Contributor",
+    )
+    # Moving the entry doesn't introduce it again
+    content = f"# Bot\n{MISSING_COMMIT}\n# Apply black\n{history['black']}\n"
+    git_repo.commit("Reorder the entries", {name: content})
+    (git_repo.path / name).write_text(f"{content}not-a-hash\n", encoding="utf-8")
+
+    code, out = run(["history", name, "--call-git"], capsys)
+
+    assert code == ErrorCode.SyntaxProblem.value | ErrorCode.CommitsNotPresent.value
+    assert out.splitlines() == [
+        "Errors (1):",
+        "  Line 5: not-a-hash",
+        "    Not committed yet",
+        "",
+        "Missing commits (1):",
+        f"  Line 2: {MISSING_COMMIT} (no object has this hash) [object-not-found]",
+        f"    Introduced in {added[:12]} on 2020-01-01 by Contributor <developer@example.com>: "
+        "List the commit of the bot",
+    ]
+
+    # The oldest version checked may not be where a finding started
+    (git_repo.path / name).write_text(content, encoding="utf-8")
+
+    code, out = run(["history", name, "--call-git", "--max-versions", "1"], capsys)
+
+    assert code == ErrorCode.CommitsNotPresent.value
+    assert "    At or before " in out
+
+    code, out = run(["history", name, "--no-git"], capsys)
+
+    assert code == 2
+
+
+def test_serve_options(capsys: pytest.CaptureFixture[str]) -> None:
+    with pytest.raises(SystemExit) as excinfo:
+        main(["serve", "--", ".git-blame-ignore-revs"])
+
+    assert excinfo.value.code == 2
+    assert "Files are sent in requests" in capsys.readouterr().err
+
+    with pytest.raises(SystemExit) as excinfo:
+        main(["serve", "--", "--pre-commit-ci"])
+
+    assert excinfo.value.code == 2
+    assert "--pre-commit-ci requires --call-git." in capsys.readouterr().err
+
+
+def test_trace_git(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n{MISSING_COMMIT}\n")
+
+    with pytest.raises(SystemExit) as excinfo:
+        main([str(file_path), "--call-git", "--trace-git"])
+
+    assert excinfo.value.code == ErrorCode.CommitsNotPresent.value
+    err = capsys.readouterr().err
+    assert re.search(
+        rf"^git trace: git cat-file -t {history['black']} -> exit 0 in \d+\.\d{{3}}s: commit$",
+        err,
+        re.MULTILINE,
+    )
+    assert f"git trace: git cat-file -t {MISSING_COMMIT} -> exit 128 in " in err
+
+    # Tracing stops when the run is over
+    with pytest.raises(SystemExit):
+        main([str(file_path), "--call-git"])
+
+    assert capsys.readouterr().err == ""
+
+
+def test_retries(
+    git_repo: GitRepo,
+    history: dict[str, str],
+    monkeypatch: pytest.MonkeyPatch,
+    capsys: pytest.CaptureFixture[str],
+) -> None:
+    # Restored after the test, since --retries sets it for the whole process
+    monkeypatch.setattr(retry, "_default_policy", retry.default_policy())
+    waits: list[float] = []
+    monkeypatch.setattr(git.time, "sleep", waits.append)
+    # The number of times each commit is looked up fails as if the network file system hiccuped
+    failures = {history["black"]: 1, history["bot"]: 5}
+
+    def flaky_run(command: list[str], **kwargs: Any) -> subprocess.CompletedProcess[Any]:
+        if command[1:3] == ["cat-file", "-t"] and failures.get(command[3], 0):
+            failures[command[3]] -= 1
+            stderr = "fatal: unable to read index file: Stale file handle\n"
+            return subprocess.CompletedProcess(command, 128, "", stderr)
+        return subprocess.run(command, **kwargs)
+
+    monkeypatch.setattr(git, "run", flaky_run)
+    file_path = git_repo.write_ignore_file(
+        f"# Apply black\n{history['black']}\n# Bot\n{history['bot']}\n"
+    )
+
+    with pytest.raises(SystemExit) as excinfo:
+        main([str(file_path), "--call-git", "--retries", "2", "--retry-delay", "0.5"])
+
+    assert excinfo.value.code == ErrorCode.CommitsNotPresent.value
+    assert waits == [0.5, 0.5, 1.0]
+    out = capsys.readouterr().out
+    assert f"Line 2: {history['black']}" in out.split("Missing commits")[0]
+    assert re.search(r"Stale file handle \(gave up after 3 attempts over \d+\.\ds\)", out)
+
+    with pytest.raises(SystemExit) as excinfo:
+        main([str(file_path), "--retries", "-1"])
+
+    assert excinfo.value.code == 2
+    assert "--retries and --retry-delay must not be negative." in capsys.readouterr().err
+
+
+def test_replaced_commits(
+    git_repo: GitRepo,
+    history: dict[str, str],
+    monkeypatch: pytest.MonkeyPatch,
+    capsys: pytest.CaptureFixture[str],
+) -> None:
+    # Restored after the test, since --no-replace-objects sets it for the whole process
+    monkeypatch.setenv("GIT_NO_REPLACE_OBJECTS", "")
+    git_repo.git("replace", history["black"], history["bot"])
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n")
+
+    code, out = run([str(file_path), "--call-git"], capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert (
+        f"Replaced commits (1):\n  Line 2: {history['black']} (replaced by {history['bot']})\n"
+    ) in out
+
+    code, out = run([str(file_path), "--call-git", "--no-replace-objects"], capsys)
+
+    assert code == 0
+    assert "Replaced commits" not in out
+
+
+def test_annotate(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(
+        f"# Formatting\n\n# Apply black\n{history['black']}\n{MISSING_COMMIT}\n"
+    )
+
+    code, out = run(["annotate", str(file_path), "--write"], capsys)
+
+    assert code == ErrorCode.CommitsNotPresent.value
+    assert out.startswith(
+        f"Line 4: {history['black']}\n"
+        "  Author: Developer <developer@example.com>\n"
+        "  Date: 2020-01-01T00:00:00+00:00\n"
+        "  Subject: Apply black\n"
+        "  Diffstat: 1 file changed, 1 insertion(+), 1 deletion(-)\n"
+        "Line 5: not a commit in the Git history\n"
+    )
+    annotated = (
+        "# Formatting\n"
+        "\n"
+        "# Author: Developer <developer@example.com>\n"
+        "# Date: 2020-01-01T00:00:00+00:00\n"
+        "# Stat: 1 file changed, 1 insertion(+), 1 deletion(-)\n"
+        "# Apply black\n"
+        f"{history['black']}\n"
+        f"{MISSING_COMMIT}\n"
+    )
+    assert file_path.read_text(encoding="utf-8") == annotated
+
+    run(["annotate", str(file_path), "--write"], capsys)
+
+    assert file_path.read_text(encoding="utf-8") == annotated
+
+
+def test_import(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    oneline = git_repo.path / "oneline.txt"
+    oneline.write_text(
+        f"{history['black'][:10]} Apply black\n{history['bot'][:7]}\n{MISSING_COMMIT[:8]} Gone\n",
+        encoding="utf-8",
+    )
+
+    with pytest.raises(SystemExit) as excinfo:
+        main(["import", str(oneline)])
+    captured = capsys.readouterr()
+
+    assert excinfo.value.code == ErrorCode.CommitsNotPresent.value
+    assert captured.out == (
+        f"# Apply black\n{history['black']}\n"
+        f"# [pre-commit.ci] auto fixes from pre-commit.com hooks\n{history['bot']}\n"
+    )
+    assert captured.err == (
+        f"{oneline}, line 3: {MISSING_COMMIT[:8]} is not a commit in the repository\n"
+    )
+
+    exported = git_repo.path / "exported.json"
+    exported.write_text(
+        json.dumps({"revs": [{"sha": history["feature"], "message": "Add g (formatting only)"}]}),
+        encoding="utf-8",
+    )
+    code, out = run(["import", str(exported)], capsys)
+
+    assert code == 0
+    assert out == f"# Add g (formatting only)\n{history['feature']}\n"
+
+    exported = git_repo.path / "exported.yaml"
+    exported.write_text(f"- commit: {history['black']}\n  reason: Black\n", encoding="utf-8")
+    code, out = run(["import", str(exported)], capsys)
+
+    assert out == f"# Black\n{history['black']}\n"
+
+    changelog = git_repo.path / "CHANGELOG.md"
+    changelog.write_text(
+        f"## 1.0\n\n- Apply black ([{history['black'][:7]}](https://github.com/o/r/commit/"
+        f"{history['black']}))\n",
+        encoding="utf-8",
+    )
+    code, out = run(["import", str(changelog)], capsys)
+
+    assert out == f"# Apply black\n{history['black']}\n"
+
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n")
+    code, out = run(["import", str(oneline), "--append", str(file_path)], capsys)
+
+    assert code == ErrorCode.CommitsNotPresent.value
+    assert out == (
+        f"Appended 1 entry(s) to {file_path}.\n"
+        "Skipped 1 commit(s) that were already listed.\n"
+    )
+    assert file_path.read_text(encoding="utf-8") == (
+        f"# Apply black\n{history['black']}\n"
+        f"\n# [pre-commit.ci] auto fixes from pre-commit.com hooks\n{history['bot']}\n"
+    )
+
+
+def test_windows_line_breaks(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(f"# Apply black\r\n{history['black']}\r\n")
+
+    code, _ = run(["annotate", str(file_path), "--write"], capsys)
+
+    assert code == 0
+    assert file_path.read_bytes() == (
+        "# Author: Developer <developer@example.com>\r\n"
+        "# Date: 2020-01-01T00:00:00+00:00\r\n"
+        "# Stat: 1 file changed, 1 insertion(+), 1 deletion(-)\r\n"
+        f"# Apply black\r\n{history['black']}\r\n"
+    ).encode()
+
+    file_path = git_repo.write_ignore_file(f"# Apply black\r\n{history['black']}")
+    oneline = git_repo.path / "oneline.txt"
+    oneline.write_text(f"{history['feature']} Add g\n", encoding="utf-8")
+    code, _ = run(["import", str(oneline), "--append", str(file_path)], capsys)
+
+    assert code == 0
+    assert file_path.read_bytes() == (
+        f"# Apply black\r\n{history['black']}\r\n\r\n# Add g\r\n{history['feature']}\r\n"
+    ).encode()
+
+
+def test_export(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(
+        f"# Tool: black\n# Apply black | 88 columns\n{history['black']}\n{MISSING_COMMIT}\n"
+    )
+
+    code, out = run(["export", str(file_path)], capsys)
+
+    assert code == ErrorCode.CommitsNotPresent.value
+    assert out == (
+        "| Commit | Date | Author | Subject | Comment |\n"
+        "| --- | --- | --- | --- | --- |\n"
+        f"| `{history['black']}` | 2020-01-01 | Developer | Apply black "
+        "| Apply black \\| 88 columns |\n"
+        f"| `{MISSING_COMMIT}` |  |  |  |  |\n"
+    )
+
+    code, out = run(
+        ["export", str(file_path), "--format", "json", "--output", "out/revs.json"], capsys
+    )
+
+    assert code == ErrorCode.CommitsNotPresent.value
+    assert out == ""
+    assert json.loads((git_repo.path / "out" / "revs.json").read_text(encoding="utf-8")) == [
+        {
+            "line": 3,
+            "hash": history["black"],
+            "date": "2020-01-01T00:00:00+00:00",
+            "author": "Developer",
+            "author_email": "developer@example.com",
+            "subject": "Apply black",
+            "comment": "Apply black | 88 columns",
+        },
+        {
+            "line": 4,
+            "hash": MISSING_COMMIT,
+            "date": None,
+            "author": None,
+            "author_email": None,
+            "subject": None,
+            "comment": None,
+        },
+    ]
+
+
+def test_remote_validate(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch, capsys: pytest.CaptureFixture[str]
+) -> None:
+    monkeypatch.setenv("XDG_CACHE_HOME", str(tmp_path / "cache"))
+    commit = "a" * 40
+    contents = f"# Apply black\n{commit}\nnot-a-hash\n# Apply ruff\n{MISSING_COMMIT}\n"
+    api = "https://api.github.com/repos"
+    responses = {
+        f"{api}/owner/app/contents/.git-blame-ignore-revs": {
+            "encoding": "base64",
+            "content": base64.b64encode(contents.encode()).decode(),
+        },
+        f"{api}/owner/app/commits/{commit}": {"sha": commit},
+    }
+    urls: list[str] = []
+
+    def open_url(request: Any) -> tuple[Any, Message]:
+        urls.append(request.full_url)
+        if request.full_url not in responses:
+            raise HTTPError(request.full_url, 404, "Not Found", Message(), None)
+        return responses[request.full_url], Message()
+
+    monkeypatch.setattr(forge_api, "_open", open_url)
+
+    code, out = run(["remote-validate", "owner/app", "https://github.com/owner/lib.git"], capsys)
+
+    assert code == (
+        ErrorCode.FileNotFound.value
+        | ErrorCode.SyntaxProblem.value
+        | ErrorCode.CommitsNotPresent.value
+    )
+    assert out == (
+        "==> owner/app <==\n"
+        "Errors (1):\n"
+        "  Line 3: not-a-hash\n"
+        "Missing commits (1):\n"
+        f"  Line 5: {MISSING_COMMIT}\n"
+        "\n"
+        "==> owner/lib <==\n"
+        "owner/lib on github.com has no .git-blame-ignore-revs.\n"
+    )
+
+    # The responses are reused
+    requests = len(urls)
+    code, out = run(["remote-validate", "owner/app"], capsys)
+
+    assert code == ErrorCode.SyntaxProblem.value | ErrorCode.CommitsNotPresent.value
+    assert len(urls) == requests
+
+    with pytest.raises(SystemExit):
+        main(["remote-validate", "app"])
+    assert "Invalid repository 'app': expected owner/name or a URL." in capsys.readouterr().err
+
+
+def test_diff(
+    git_repo: GitRepo, history: dict[str, str], capsys: pytest.CaptureFixture[str]
+) -> None:
+    file_path = git_repo.write_ignore_file(
+        f"# Initial commit\n{history['initial']}\n# Apply black\n{history['black']}\n"
+        f"# Add g\n{history['feature']}\n"
+    )
+    file_path.write_text(
+        f"# Add g\n{history['feature']}\n# Apply black to everything\n{history['black']}\n"
+        f"# Autofix\n{history['bot']}\n",
+        encoding="utf-8",
+    )
+
+    code, out = run(["diff", str(file_path), "--base", "HEAD"], capsys)
+
+    assert code == ErrorCode.FailedAdditionalChecks.value
+    assert out == (
+        "Added (1):\n"
+        f"  Line 6: {history['bot']} (fails --strict-comments-git)\n"
+        "\n"
+        "Removed (1):\n"
+        f"  Line 2: {history['initial']}\n"
+        "\n"
+        "Comments changed (1):\n"
+        f"  Line 4: {history['black']}\n"
+        "    Old: Apply black\n"
+        "    New: Apply black to everything\n"
+        "\n"
+        "Reordered (1):\n"
+        f"  Line 2: {history['feature']}\n"
+    )
@@ -0,0 +1,114 @@
+from typing import Optional
+
+import pytest
+from validate_git_blame_ignore_revs.comments import (
+    MatchMode,
+    Normalization,
+    comment_matches,
+    comment_similarity,
+    is_placeholder_comment,
+    normalize_comment,
+    split_match_directives,
+)
+from validate_git_blame_ignore_revs.commits import CommitInfo
+from validate_git_blame_ignore_revs.template import render_comment
+
+SUBJECT = "Apply black to the whole repository"
+
+
+@pytest.mark.parametrize(
+    ("comment", "mode", "matches"),
+    [
+        (SUBJECT, MatchMode.Exact, True),
+        ("Apply black", MatchMode.Exact, False),
+        ("Apply black", MatchMode.Prefix, True),
+        ("whole repository", MatchMode.Prefix, False),
+        ("whole repository", MatchMode.Substring, True),
+        ("apply black", MatchMode.Substring, False),
+        (r"^Apply (black|ruff)\b", MatchMode.Regex, True),
+        (r"^Apply (isort", MatchMode.Regex, False),
+        ("APPLY BLACK", MatchMode.IgnoreCase, True),
+        ("Apply ruff", MatchMode.IgnoreCase, False),
+        ("Apply Black to the whole repo.", MatchMode.Similar, True),
+        ("Reformat everything", MatchMode.Similar, False),
+    ],
+)
+def test_comment_matches(comment: str, mode: MatchMode, matches: bool) -> None:
+    assert comment_matches(comment, SUBJECT, mode) is matches
+
+
+@pytest.mark.parametrize(
+    ("comment", "score"),
+    [
+        (SUBJECT, 1.0),
+        ("apply black, to the whole repository!", 1.0),
+        ("Apply black", 1.0),
+        ("", 0.0),
+    ],
+)
+def test_comment_similarity(comment: str, score: float) -> None:
+    assert comment_similarity(comment, SUBJECT) == score
+
+
+@pytest.mark.parametrize(
+    ("normalization", "casefold", "normalized"),
+    [
+        (None, False, "Don\u2019t use Cafe\u0301 \ufb01les"),
+        ("NFC", False, "Don\u2019t use Caf\u00e9 \ufb01les"),
+        ("NFKC", False, "Don't use Caf\u00e9 files"),
+        ("NFKC", True, "don't use caf\u00e9 files"),
+    ],
+)
+def test_normalize_comment(
+    normalization: Optional[Normalization], casefold: bool, normalized: str
+) -> None:
+    text = "Don\u2019t use Cafe\u0301 \ufb01les"
+
+    assert normalize_comment(text, normalization, casefold) == normalized
+
+
+@pytest.mark.parametrize(
+    ("comment", "placeholder"),
+    [
+        ("", True),
+        ("TODO:", True),
+        ("Formatting.", True),
+        ("fix", True),
+        ("Fix formatting", False),
+        ("Apply black", False),
+    ],
+)
+def test_is_placeholder_comment(comment: str, placeholder: bool) -> None:
+    assert is_placeholder_comment(comment) is placeholder
+
+
+def test_split_match_directives() -> None:
+    comments = ["Apply black", "match: Substring", "Match: nonsense"]
+
+    assert split_match_directives(comments) == (
+        ["Apply black", "Match: nonsense"],
+        MatchMode.Substring,
+    )
+
+
+@pytest.mark.parametrize(
+    ("template", "subject", "comment"),
+    [
+        ("{subject}", "Apply black (#12)", "Apply black (#12)"),
+        ("{subject} ({date}, {pr})", "Apply black (#12)", "Apply black (#12) (2020-01-02, #12)"),
+        ("{subject} ({date}, {pr})", "Apply black", "Apply black (2020-01-02)"),
+        ("{subject} [{pr}]", "Apply black", "Apply black"),
+        ("{short_hash}: {subject} by {author}", "Apply black", "0123456: Apply black by Dev"),
+    ],
+)
+def test_render_comment(template: str, subject: str, comment: str) -> None:
+    commit = CommitInfo(
+        hash="0123456789abcdef0123456789abcdef01234567",
+        author_name="Dev",
+        author_email="dev@example.com",
+        author_date="2020-01-02T03:04:05+01:00",
+        parents=[],
+        subject=subject,
+    )
+
+    assert render_comment(template, commit) == comment
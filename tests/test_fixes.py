@@ -0,0 +1,94 @@
+from pathlib import Path
+
+from validate_git_blame_ignore_revs.backends import FakeCommit, FakeGit
+from validate_git_blame_ignore_revs.fixes import (
+    Fix,
+    Position,
+    Range,
+    TextEdit,
+    apply_edits,
+    find_fixes,
+)
+
+HASH = "0123456789abcdef0123456789abcdef01234567"
+OTHER_HASH = "89abcdef0123456789abcdef0123456789abcdef"
+
+
+def test_find_fixes_without_git(tmp_path: Path) -> None:
+    contents = f"# Apply black\n{HASH.upper()}\nnot a hash\n# Add g\n{OTHER_HASH}\n"
+
+    fixes = find_fixes(tmp_path / ".git-blame-ignore-revs", contents)
+
+    assert fixes == [
+        Fix("Lowercase hash", 2, (TextEdit(Range(Position(1, 0), Position(1, 40)), HASH),)),
+        Fix("Remove invalid line", 3, (TextEdit(Range(Position(2, 0), Position(3, 0)), ""),)),
+    ]
+
+
+def test_find_fixes_with_git(tmp_path: Path) -> None:
+    git = FakeGit({HASH: FakeCommit("Apply black"), OTHER_HASH: FakeCommit("Add g")})
+    contents = f"  {OTHER_HASH}\r\n# Apply black\r\n{HASH[:12]}\r\n"
+
+    fixes = find_fixes(tmp_path / ".git-blame-ignore-revs", contents, call_git=True, git=git)
+
+    assert fixes == [
+        Fix(
+            "Insert comment from commit subject",
+            1,
+            (TextEdit(Range(Position(0, 0), Position(0, 0)), "  # Add g\n"),),
+        ),
+        Fix(
+            "Expand abbreviated hash",
+            3,
+            (TextEdit(Range(Position(2, 0), Position(2, 12)), HASH),),
+        ),
+    ]
+    assert git.directory == tmp_path
+
+
+def test_find_fixes_keeps_near_misses(tmp_path: Path) -> None:
+    lookalike = HASH.replace("0", "O")
+    contents = f"# Apply black\n{HASH[:39]}\n# Add g\n{OTHER_HASH}0\n# Apply black\n{lookalike}\n"
+
+    # Without Git, there's no telling which commit was meant, so they are left to the report
+    assert find_fixes(tmp_path / ".git-blame-ignore-revs", contents) == []
+
+    git = FakeGit({HASH: FakeCommit("Apply black"), OTHER_HASH: FakeCommit("Add g")})
+    fixes = find_fixes(tmp_path / ".git-blame-ignore-revs", contents, call_git=True, git=git)
+
+    assert fixes == [
+        Fix(
+            "Replace with suggested hash",
+            2,
+            (TextEdit(Range(Position(1, 0), Position(1, 39)), HASH),),
+        ),
+        Fix(
+            "Replace with suggested hash",
+            4,
+            (TextEdit(Range(Position(3, 0), Position(3, 41)), OTHER_HASH),),
+        ),
+        Fix(
+            "Replace with suggested hash",
+            6,
+            (TextEdit(Range(Position(5, 0), Position(5, 40)), HASH),),
+        ),
+    ]
+
+
+def test_text_edit_to_lsp() -> None:
+    edit = TextEdit(Range(Position(1, 0), Position(2, 0)), "")
+
+    assert edit.to_lsp() == {
+        "range": {"start": {"line": 1, "character": 0}, "end": {"line": 2, "character": 0}},
+        "newText": "",
+    }
+
+
+def test_apply_edits(tmp_path: Path) -> None:
+    contents = f"# Café ☕ 🎨\r\n{HASH.upper()}\r\nnot a hash"
+    fixes = find_fixes(tmp_path / ".git-blame-ignore-revs", contents)
+    # The emoji is two UTF-16 code units
+    edits = [TextEdit(Range(Position(0, 9), Position(0, 11)), "palette")]
+    edits += [edit for fix in fixes for edit in fix.edits]
+
+    assert apply_edits(contents, edits) == f"# Café ☕ palette\n{HASH}\n"
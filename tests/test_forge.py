@@ -0,0 +1,429 @@
+from email.message import Message
+from pathlib import Path
+from typing import Any
+from urllib.error import HTTPError, URLError
+from urllib.request import Request
+
+import pytest
+from validate_git_blame_ignore_revs import forge, forge_api
+from validate_git_blame_ignore_revs.forge import (
+    ForgeRepository,
+    code_insights,
+    commit_exists,
+    commit_url,
+    file_contents,
+    find_pull_request,
+    parse_remote_url,
+    publish_code_insights,
+    pull_request_contains,
+)
+from validate_git_blame_ignore_revs.forge_api import (
+    ForgeClient,
+    ResponseCache,
+    UnknownForgeError,
+    discover_token,
+    forge_kind,
+)
+from validate_git_blame_ignore_revs.report import FileReport, Finding, Section, Severity
+from validate_git_blame_ignore_revs.retry import RetryError, RetryPolicy
+
+REPOSITORY = ForgeRepository("github.com", "berquist", "validate-git-blame-ignore-revs")
+
+
+@pytest.mark.parametrize(
+    "url",
+    [
+        "https://github.com/berquist/validate-git-blame-ignore-revs.git",
+        "https://github.com/berquist/validate-git-blame-ignore-revs",
+        "git@github.com:berquist/validate-git-blame-ignore-revs.git",
+        "ssh://git@github.com:22/berquist/validate-git-blame-ignore-revs.git",
+    ],
+)
+def test_parse_remote_url(url: str) -> None:
+    assert parse_remote_url(url) == REPOSITORY
+
+
+def test_parse_remote_url_local_path() -> None:
+    assert parse_remote_url("/srv/git/project") is None
+
+
+@pytest.mark.parametrize(
+    ("url", "expected"),
+    [
+        ("git@github.com:owner/name.git", "https://github.com/owner/name/commit/{}"),
+        ("https://gitlab.com/owner/name.git", "https://gitlab.com/owner/name/-/commit/{}"),
+        (
+            "git@gitlab.example.com:owner/name.git",
+            "https://gitlab.example.com/owner/name/-/commit/{}",
+        ),
+        ("https://bitbucket.org/owner/name.git", "https://bitbucket.org/owner/name/commits/{}"),
+        ("https://git.example.com/owner/name", "https://git.example.com/owner/name/commit/{}"),
+    ],
+)
+def test_commit_url(url: str, expected: str) -> None:
+    repository = parse_remote_url(url)
+
+    assert repository is not None
+    assert commit_url(repository, "0" * 40) == expected.format("0" * 40)
+
+
+@pytest.mark.parametrize(
+    ("comments", "number"),
+    [
+        (["Apply black (#123)"], 123),
+        (["Apply black", "See https://github.com/owner/name/pull/45"], 45),
+        (["https://gitlab.com/group/name/-/merge_requests/6"], 6),
+        (["Reformat with ruff !7"], 7),
+        (["Format C# sources"], None),
+        (["Apply black"], None),
+    ],
+)
+def test_find_pull_request(comments: list[str], number: int) -> None:
+    assert find_pull_request(comments) == number
+
+
+@pytest.fixture
+def api(tmp_path: Path, monkeypatch: pytest.MonkeyPatch) -> dict[str, Any]:
+    """Serve API responses from a dictionary keyed by URL, with the Link header for pages."""
+    responses: dict[str, Any] = {}
+
+    def open_url(request: Request) -> tuple[Any, Message]:
+        url = request.full_url
+        if url not in responses:
+            raise HTTPError(url, 404, "Not Found", Message(), None)
+        headers = Message()
+        data = responses[url]
+        if isinstance(data, tuple):
+            data, headers["Link"] = data
+        return data, headers
+
+    monkeypatch.setattr(forge_api, "_open", open_url)
+    for variable in ("GH_TOKEN", "GITHUB_TOKEN", "GITLAB_TOKEN", "GH_HOST", "GITHUB_SERVER_URL"):
+        monkeypatch.delenv(variable, raising=False)
+    monkeypatch.setenv("GH_CONFIG_DIR", str(tmp_path / "gh"))
+    monkeypatch.setenv("GLAB_CONFIG_DIR", str(tmp_path / "glab"))
+    monkeypatch.setenv("GITLAB_HOST", "https://gitlab.example.com")
+    return responses
+
+
+def test_pull_request_contains(api: dict[str, Any]) -> None:
+    base = "https://api.github.com/repos/berquist/validate-git-blame-ignore-revs/pulls/12"
+    api[base] = {"merge_commit_sha": "a" * 40}
+    api[f"{base}/commits?per_page=100"] = (
+        [{"sha": "b" * 40}],
+        f'<{base}/commits?per_page=100&page=2>; rel="next"',
+    )
+    api[f"{base}/commits?per_page=100&page=2"] = [{"sha": "d" * 40}]
+
+    assert pull_request_contains(REPOSITORY, 12, "a" * 40)
+    assert pull_request_contains(REPOSITORY, 12, "b" * 40)
+    assert pull_request_contains(REPOSITORY, 12, "d" * 40)
+    assert pull_request_contains(REPOSITORY, 12, "c" * 40) is False
+    assert pull_request_contains(REPOSITORY, 13, "a" * 40) is None
+
+
+def test_merge_request_contains(api: dict[str, Any]) -> None:
+    base = "https://gitlab.example.com/api/v4/projects/group%2Fname/merge_requests/6"
+    api[base] = {"merge_commit_sha": None, "squash_commit_sha": "a" * 40}
+    api[f"{base}/commits?per_page=100"] = [{"id": "b" * 40}]
+    repository = ForgeRepository("gitlab.example.com", "group", "name")
+
+    assert pull_request_contains(repository, 6, "a" * 40)
+    assert pull_request_contains(repository, 6, "b" * 40)
+    assert pull_request_contains(repository, 6, "c" * 40) is False
+
+
+def test_file_contents(api: dict[str, Any]) -> None:
+    base = "https://api.github.com/repos/berquist/validate-git-blame-ignore-revs"
+    api[f"{base}/contents/a%20b.txt"] = {"encoding": "base64", "content": "aGVsbG8K\n"}
+    api["https://gitlab.com/api/v4/projects/group%2Fsub%2Fname"] = {"default_branch": "trunk"}
+    api[
+        "https://gitlab.com/api/v4/projects/group%2Fsub%2Fname/repository/files/docs%2Fa.txt"
+        "?ref=trunk"
+    ] = {"encoding": "base64", "content": "aGk="}
+
+    assert file_contents(REPOSITORY, "a b.txt") == b"hello\n"
+    assert file_contents(REPOSITORY, "missing.txt") is None
+    gitlab = ForgeRepository("gitlab.com", "group/sub", "name")
+    assert file_contents(gitlab, "docs/a.txt") == b"hi"
+
+
+def test_commit_exists(api: dict[str, Any]) -> None:
+    base = "https://api.github.com/repos/berquist/validate-git-blame-ignore-revs"
+    api[f"{base}/commits/{'a' * 40}"] = {}
+    api[f"https://gitlab.com/api/v4/projects/group%2Fname/repository/commits/{'a' * 40}"] = {}
+
+    assert commit_exists(REPOSITORY, "a" * 40)
+    assert not commit_exists(REPOSITORY, "b" * 40)
+    assert commit_exists(ForgeRepository("gitlab.com", "group", "name"), "a" * 40)
+
+
+def test_forge_client_retries(monkeypatch: pytest.MonkeyPatch) -> None:
+    url = "https://api.github.com/repos/owner/name"
+    rate_limited = Message()
+    rate_limited["X-RateLimit-Remaining"] = "0"
+    rate_limited["X-RateLimit-Reset"] = "1000"
+    errors = [
+        HTTPError(url, 403, "Forbidden", rate_limited, None),
+        HTTPError(url, 502, "Bad Gateway", Message(), None),
+        URLError("Connection reset"),
+    ]
+
+    def open_url(request: Request) -> tuple[Any, Message]:
+        if errors:
+            raise errors.pop(0)
+        return {"name": "name"}, Message()
+
+    waits: list[float] = []
+    monkeypatch.setattr(forge_api, "_open", open_url)
+    monkeypatch.setattr(forge_api.time, "time", lambda: 990.0)
+    monkeypatch.setattr(forge_api.time, "sleep", waits.append)
+    client = ForgeClient("github.com", "token")
+
+    assert client.get("repos/owner/name") == {"name": "name"}
+    assert waits == [11.0, 2.0, 4.0]
+
+    # The limit resets too late to wait for
+    errors.append(HTTPError(url, 403, "Forbidden", rate_limited, None))
+    monkeypatch.setattr(forge_api.time, "time", lambda: 0.0)
+    with pytest.raises(HTTPError):
+        client.get("repos/owner/name")
+
+    # The last error is reported with how often the request was made
+    errors.extend(URLError("Connection reset") for _ in range(4))
+    with pytest.raises(RetryError, match=r"failed after 4 attempts over .*: .*Connection reset"):
+        client.get("repos/owner/name")
+
+    waits.clear()
+    errors.extend(URLError("Connection reset") for _ in range(3))
+    client = ForgeClient("github.com", "token", retry_policy=RetryPolicy(2, 5.0, 8.0))
+    with pytest.raises(RetryError, match="failed after 3 attempts"):
+        client.get("repos/owner/name")
+    assert waits == [5.0, 8.0]
+
+    # A request that was only made once fails with its own error
+    errors.append(URLError("Connection reset"))
+    client = ForgeClient("github.com", "token", retry_policy=RetryPolicy(retries=0))
+    with pytest.raises(URLError):
+        client.get("repos/owner/name")
+
+
+def test_forge_client_cache(tmp_path: Path, monkeypatch: pytest.MonkeyPatch) -> None:
+    base = "https://api.github.com/repos/o/n/pulls"
+    urls: list[str] = []
+
+    def open_url(request: Request) -> tuple[Any, Message]:
+        urls.append(request.full_url)
+        headers = Message()
+        if request.full_url == f"{base}/1/commits?per_page=100":
+            headers["Link"] = f'<{base}/1/commits?per_page=100&page=2>; rel="next"'
+            return [{"sha": "a" * 40}], headers
+        if request.full_url == f"{base}/1/commits?per_page=100&page=2":
+            return [{"sha": "b" * 40}], headers
+        raise HTTPError(request.full_url, 404, "Not Found", Message(), None)
+
+    now = 1000.0
+    monkeypatch.setattr(forge_api, "_open", open_url)
+    monkeypatch.setattr(forge_api.time, "time", lambda: now)
+    cache = ResponseCache(tmp_path, ttl=3600, not_found_ttl=60)
+
+    for _ in range(2):
+        client = ForgeClient("github.com", "token", cache=cache)
+        assert client.get_all("repos/o/n/pulls/1/commits") == [{"sha": "a" * 40}, {"sha": "b" * 40}]
+        with pytest.raises(HTTPError):
+            client.get("repos/o/n/pulls/2")
+    assert len(urls) == 3
+
+    # Pull requests that weren't found are looked up again sooner
+    now += 120
+    with pytest.raises(HTTPError):
+        client.get("repos/o/n/pulls/2")
+    assert len(client.get_all("repos/o/n/pulls/1/commits")) == 2
+    assert len(urls) == 4
+
+    now += 3600
+    assert len(client.get_all("repos/o/n/pulls/1/commits")) == 2
+    assert len(urls) == 6
+
+
+def test_forge_client_cache_token(tmp_path: Path, monkeypatch: pytest.MonkeyPatch) -> None:
+    requests: list[Request] = []
+
+    def open_url(request: Request) -> tuple[Any, Message]:
+        requests.append(request)
+        return {"private": request.has_header("Authorization")}, Message()
+
+    monkeypatch.setattr(forge_api, "_open", open_url)
+    cache = ResponseCache(tmp_path)
+
+    # What a token could see is only reused for that token
+    for token in ("secret", "secret", "other", "", ""):
+        client = ForgeClient("github.com", token, cache=cache)
+        assert client.get("repos/o/n/pulls/1") == {"private": bool(token)}
+    assert len(requests) == 3
+
+    for path in tmp_path.iterdir():
+        assert "secret" not in path.read_text(encoding="utf-8")
+
+
+@pytest.fixture
+def forge_config(tmp_path: Path, monkeypatch: pytest.MonkeyPatch) -> Path:
+    """Clear the forge tokens and hosts, returning the directory gh and glab configure from."""
+    for variable in ("GH_TOKEN", "GITHUB_TOKEN", "GH_ENTERPRISE_TOKEN", "GITHUB_ENTERPRISE_TOKEN"):
+        monkeypatch.delenv(variable, raising=False)
+    for variable in ("GITLAB_TOKEN", "GITLAB_ACCESS_TOKEN", "OAUTH_TOKEN"):
+        monkeypatch.delenv(variable, raising=False)
+    for variable in ("GH_HOST", "GITLAB_HOST", "GITHUB_SERVER_URL"):
+        monkeypatch.delenv(variable, raising=False)
+    monkeypatch.setenv("GH_CONFIG_DIR", str(tmp_path / "gh"))
+    monkeypatch.setenv("GLAB_CONFIG_DIR", str(tmp_path / "glab"))
+    return tmp_path
+
+
+def test_forge_kind(forge_config: Path, monkeypatch: pytest.MonkeyPatch) -> None:
+    assert forge_kind("github.com") == "github"
+    assert forge_kind("gitlab.com") == "gitlab"
+    # Hosts aren't guessed at from their name
+    for host in ("bitbucket.org", "git.example.com", "gitlab.example.com", "github.example.com"):
+        assert forge_kind(host) is None
+        with pytest.raises(UnknownForgeError, match=f"{host} isn't a known GitHub or GitLab host"):
+            ForgeClient(host)
+
+    (forge_config / "gh").mkdir()
+    (forge_config / "gh" / "hosts.yml").write_text(
+        "github.example.com:\n    user: octocat\n", encoding="utf-8"
+    )
+    (forge_config / "glab").mkdir()
+    (forge_config / "glab" / "config.yml").write_text(
+        "hosts:\n    gitlab.example.com:\n        api_protocol: https\n", encoding="utf-8"
+    )
+    monkeypatch.setenv("GH_HOST", "ghe.example.com")
+    monkeypatch.setenv("GITHUB_SERVER_URL", "https://actions.example.com")
+    monkeypatch.setenv("GITLAB_HOST", "https://code.example.com")
+
+    assert forge_kind("github.example.com") == "github"
+    assert forge_kind("ghe.example.com") == "github"
+    assert forge_kind("actions.example.com") == "github"
+    assert forge_kind("gitlab.example.com") == "gitlab"
+    assert forge_kind("code.example.com") == "gitlab"
+    assert forge_kind("bitbucket.org") is None
+
+
+def test_discover_token(forge_config: Path, monkeypatch: pytest.MonkeyPatch) -> None:
+    tmp_path = forge_config
+    assert discover_token("github.com") is None
+
+    (tmp_path / "gh").mkdir()
+    (tmp_path / "gh" / "hosts.yml").write_text(
+        "github.com:\n    user: octocat\n    oauth_token: gho_public\n"
+        "github.example.com:\n    oauth_token: gho_enterprise\n",
+        encoding="utf-8",
+    )
+    (tmp_path / "glab").mkdir()
+    (tmp_path / "glab" / "config.yml").write_text(
+        "git_protocol: ssh\nhosts:\n    gitlab.com:\n        token: glpat-saved\n",
+        encoding="utf-8",
+    )
+
+    assert discover_token("github.com") == "gho_public"
+    assert discover_token("github.example.com") == "gho_enterprise"
+    assert discover_token("gitlab.com") == "glpat-saved"
+    assert discover_token("gitlab.example.com") is None
+
+    monkeypatch.setenv("GITHUB_TOKEN", "ghp_environment")
+    monkeypatch.setenv("GITLAB_TOKEN", "glpat-environment")
+
+    assert discover_token("github.com") == "ghp_environment"
+    assert discover_token("github.example.com") == "ghp_environment"
+    assert discover_token("gitlab.com") == "glpat-environment"
+    # Tokens are only for the hosts they were configured for
+    assert discover_token("bitbucket.org") is None
+    assert discover_token("git.example.com") is None
+
+
+def test_forge_client_token_stays_on_api(monkeypatch: pytest.MonkeyPatch) -> None:
+    base = "https://api.github.com/repos/o/n/pulls/1/commits"
+    requests: list[Request] = []
+
+    def open_url(request: Request) -> tuple[Any, Message]:
+        requests.append(request)
+        headers = Message()
+        if request.full_url == f"{base}?per_page=100":
+            headers["Link"] = f'<{base}?per_page=100&page=2>; rel="next"'
+        elif request.full_url == f"{base}?per_page=100&page=2":
+            headers["Link"] = '<https://elsewhere.example.com/page=3>; rel="next"'
+        return [{"sha": "a" * 40}], headers
+
+    monkeypatch.setattr(forge_api, "_open", open_url)
+    client = ForgeClient("github.com", "token")
+
+    assert len(client.get_all("repos/o/n/pulls/1/commits")) == 3
+    assert [request.get_header("Authorization") for request in requests] == [
+        "Bearer token",
+        "Bearer token",
+        None,
+    ]
+
+
+def test_code_insights() -> None:
+    file = FileReport(
+        ".git-blame-ignore-revs",
+        [
+            Section(
+                "Errors",
+                Severity.Error,
+                2,
+                [Finding("Line 3", "not-a-hash", "not-a-hash", ("Did you mean it?",), 3)],
+                None,
+            ),
+            Section(
+                "Missing pre-commit-ci commits",
+                Severity.Warning,
+                32,
+                [Finding(f"Commit {'a' * 40}", "a" * 40, "auto fixes", url="https://example")],
+                None,
+            ),
+        ],
+        [],
+        34,
+    )
+
+    report, annotations = code_insights(file, "docs/.git-blame-ignore-revs")
+
+    assert report["result"] == "FAILED"
+    assert report["data"][0]["value"] == report["data"][1]["value"] == 1
+    assert annotations == [
+        {
+            "external_id": "errors-1",
+            "annotation_type": "BUG",
+            "severity": "HIGH",
+            "summary": "Errors: Line 3: not-a-hash",
+            "path": "docs/.git-blame-ignore-revs",
+            "details": "Did you mean it?",
+            "line": 3,
+        },
+        {
+            "external_id": "missing-pre-commit-ci-commits-2",
+            "annotation_type": "CODE_SMELL",
+            "severity": "MEDIUM",
+            "summary": f"Missing pre-commit-ci commits: Commit {'a' * 40}: auto fixes",
+            "path": "docs/.git-blame-ignore-revs",
+            "link": "https://example",
+        },
+    ]
+
+
+def test_publish_code_insights(monkeypatch: pytest.MonkeyPatch) -> None:
+    requests: list[tuple[str, str, Any]] = []
+    monkeypatch.setattr(forge, "_send_bitbucket_json", lambda *request: requests.append(request))
+    repository = ForgeRepository("bitbucket.org", "owner", "name")
+    annotations = [{"external_id": str(n)} for n in range(150)]
+
+    publish_code_insights(repository, "a" * 40, "report", {"title": "Report"}, annotations)
+
+    url = f"https://api.bitbucket.org/2.0/repositories/owner/name/commit/{'a' * 40}/reports/report"
+    assert requests == [
+        ("PUT", url, {"title": "Report"}),
+        ("POST", f"{url}/annotations", annotations[:100]),
+        ("POST", f"{url}/annotations", annotations[100:]),
+    ]
@@ -0,0 +1,36 @@
+import pytest
+from validate_git_blame_ignore_revs.checks import CHECKS
+from validate_git_blame_ignore_revs.i18n import (
+    LANGUAGES,
+    load_catalog,
+    requested_languages,
+    translator,
+    untranslated,
+)
+
+
+@pytest.mark.parametrize("language", LANGUAGES)
+def test_catalog_translates_every_check(language: str) -> None:
+    catalog = load_catalog(language)
+
+    assert [check.title for check in CHECKS.values() if check.title not in catalog] == []
+
+
+def test_requested_languages(monkeypatch: pytest.MonkeyPatch) -> None:
+    assert requested_languages("de_AT.UTF-8@euro:fr") == ["de_AT", "de", "fr"]
+    assert requested_languages("C.UTF-8") == []
+
+    for variable in ("LANGUAGE", "LC_ALL", "LC_MESSAGES"):
+        monkeypatch.delenv(variable, raising=False)
+    monkeypatch.setenv("LANG", "de_DE.UTF-8")
+
+    assert requested_languages() == ["de_DE", "de"]
+
+
+def test_translator() -> None:
+    translate = translator("de_CH")
+
+    assert translate("Missing commits") == "Fehlende Commits"
+    assert translate("Not in the catalog") == "Not in the catalog"
+    assert translator("xx") is untranslated
+    assert translator("en:de") is untranslated
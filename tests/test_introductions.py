@@ -0,0 +1,61 @@
+from collections.abc import Iterator
+
+from validate_git_blame_ignore_revs.introductions import (
+    FileVersion,
+    FindingKey,
+    Introduction,
+    find_introductions,
+)
+
+NEWEST = FileVersion("c" * 40, "Developer <developer@example.com>", "2024-03-01", "Fix a comment")
+MIDDLE = FileVersion("b" * 40, "Contributor <c@example.com>", "2024-02-01", "List a commit")
+OLDEST = FileVersion("a" * 40, "Developer <developer@example.com>", "2024-01-01", "Add the file")
+
+MISSING = ("Missing commits", "0" * 40)
+INVALID = ("Errors", "not-a-hash")
+READDED = ("Missing commits", "1" * 40)
+
+
+def test_find_introductions() -> None:
+    # Versions are only checked until every finding is accounted for
+    checked: list[FileVersion] = []
+
+    def versions(
+        *findings: tuple[FileVersion, set[FindingKey]],
+    ) -> Iterator[tuple[FileVersion, set[FindingKey]]]:
+        for version, keys in findings:
+            checked.append(version)
+            yield version, keys
+
+    introductions = find_introductions(
+        [MISSING, INVALID, READDED],
+        versions(
+            (NEWEST, {MISSING, READDED}),
+            (MIDDLE, {MISSING}),
+            (OLDEST, {READDED}),
+        ),
+    )
+
+    assert introductions == {
+        MISSING: Introduction(MIDDLE),
+        INVALID: Introduction(None),
+        # Removed and listed again, so only the latest listing counts
+        READDED: Introduction(NEWEST),
+    }
+    assert checked == [NEWEST, MIDDLE, OLDEST]
+
+    checked.clear()
+
+    assert find_introductions([MISSING], versions((NEWEST, set()), (MIDDLE, {MISSING}))) == {
+        MISSING: Introduction(None)
+    }
+    assert checked == [NEWEST]
+
+
+def test_find_introductions_in_limited_history() -> None:
+    versions = [(NEWEST, {MISSING}), (MIDDLE, {MISSING})]
+
+    assert find_introductions([MISSING], versions) == {MISSING: Introduction(MIDDLE)}
+    assert find_introductions([MISSING], versions, complete=False) == {
+        MISSING: Introduction(MIDDLE, at_or_before=True)
+    }
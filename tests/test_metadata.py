@@ -0,0 +1,149 @@
+from pathlib import Path
+
+import pytest
+from validate_git_blame_ignore_revs.comments import MatchMode
+from validate_git_blame_ignore_revs.config import load_config
+from validate_git_blame_ignore_revs.metadata import MetadataPolicy, check_metadata, parse_metadata
+
+POLICY = MetadataPolicy(
+    required_keys=["Tool", "PR"], formats={"Tool": "black|ruff", "PR": "#[0-9]+"}
+)
+
+
+def test_parse_metadata() -> None:
+    comments = ["Apply black to the whole repository", "Tool: black", "PR:  #123 "]
+
+    assert parse_metadata(comments) == {"Tool": "black", "PR": "#123"}
+
+
+@pytest.mark.parametrize(
+    ("comments", "problems"),
+    [
+        (["Tool: black", "PR: #123"], []),
+        (["Tool: black"], ["missing PR"]),
+        (
+            ["Tool: yapf", "PR: 123"],
+            ["Tool 'yapf' does not match 'black|ruff'", "PR '123' does not match '#[0-9]+'"],
+        ),
+        ([], ["missing Tool", "missing PR"]),
+    ],
+)
+def test_check_metadata(comments: list[str], problems: list[str]) -> None:
+    assert check_metadata(comments, POLICY) == problems
+
+
+def test_load_config(tmp_path: Path) -> None:
+    config_path = tmp_path / "config.cfg"
+    config_path.write_text(
+        "[metadata]\n"
+        "required_keys = Tool, PR\n"
+        "\n"
+        "[metadata.formats]\n"
+        "Tool = black|ruff\n"
+        "PR = #[0-9]+\n",
+        encoding="utf-8",
+    )
+
+    assert load_config(config_path) == {
+        "metadata": POLICY,
+        "ticket_pattern": None,
+        "comment_match": MatchMode.Prefix,
+        "similarity_threshold": 0.8,
+        "comment_template": None,
+        "normalization": None,
+        "casefold": False,
+        "empty_file": "warn",
+        "file_name": ".git-blame-ignore-revs",
+        "trailer": None,
+        "reachable_from": [],
+        "denylist": None,
+        "spelling_words": [],
+    }
+
+
+def test_load_config_invalid_format(tmp_path: Path) -> None:
+    config_path = tmp_path / "config.cfg"
+    config_path.write_text("[metadata.formats]\nTool = (black\n", encoding="utf-8")
+
+    with pytest.raises(ValueError, match="Invalid regular expression for Tool"):
+        load_config(config_path)
+
+
+def test_load_config_comments(tmp_path: Path) -> None:
+    config_path = tmp_path / "config.cfg"
+    config_path.write_text(
+        "[comments]\n"
+        "ticket_pattern = JIRA-[0-9]+\n"
+        "match = similar\n"
+        "similarity_threshold = 0.7\n"
+        "template = {subject} ({pr})\n"
+        "normalize = NFKC\n"
+        "casefold = true\n",
+        encoding="utf-8",
+    )
+
+    assert load_config(config_path) == {
+        "metadata": None,
+        "ticket_pattern": "JIRA-[0-9]+",
+        "comment_match": MatchMode.Similar,
+        "similarity_threshold": 0.7,
+        "comment_template": "{subject} ({pr})",
+        "normalization": "NFKC",
+        "casefold": True,
+        "empty_file": "warn",
+        "file_name": ".git-blame-ignore-revs",
+        "trailer": None,
+        "reachable_from": [],
+        "denylist": None,
+        "spelling_words": [],
+    }
+
+
+def test_load_config_unknown_template_field(tmp_path: Path) -> None:
+    config_path = tmp_path / "config.cfg"
+    config_path.write_text("[comments]\ntemplate = {subject} by {committer}\n", encoding="utf-8")
+
+    with pytest.raises(ValueError, match=r"Invalid template .*: Unknown field \{committer\}"):
+        load_config(config_path)
+
+
+def test_load_config_unknown_match(tmp_path: Path) -> None:
+    config_path = tmp_path / "config.cfg"
+    config_path.write_text("[comments]\nmatch = loose\n", encoding="utf-8")
+
+    with pytest.raises(ValueError, match=r"Unknown match in \[comments\]: 'loose'"):
+        load_config(config_path)
+
+
+def test_load_config_invalid_trailer(tmp_path: Path) -> None:
+    config_path = tmp_path / "config.cfg"
+    config_path.write_text("[commits]\ntrailer = Blame Ignore: true\n", encoding="utf-8")
+
+    with pytest.raises(ValueError, match=r"Invalid trailer in \[commits\]: 'Blame Ignore: true'"):
+        load_config(config_path)
+
+
+def test_load_config_denylist(tmp_path: Path) -> None:
+    config_path = tmp_path / "config.cfg"
+    config_path.write_text(
+        "[denylist]\ncommits = 0123abcd, 4567ef01\nsubjects =\n  ^Add\\b\n  x{1,3}\n",
+        encoding="utf-8",
+    )
+
+    assert load_config(config_path)["denylist"] == {
+        "commits": ["0123abcd", "4567ef01"],
+        "subjects": ["^Add\\b", "x{1,3}"],
+        "authors": [],
+    }
+
+    config_path.write_text("[denylist]\ncommits = HEAD\n", encoding="utf-8")
+
+    with pytest.raises(ValueError, match=r"Invalid commit in \[denylist\]: 'HEAD'"):
+        load_config(config_path)
+
+
+def test_load_config_spelling(tmp_path: Path) -> None:
+    config_path = tmp_path / "config.cfg"
+    config_path.write_text("[spelling]\nwords = isort, rustfmt\n  Berquist\n", encoding="utf-8")
+
+    assert load_config(config_path)["spelling_words"] == ["isort", "rustfmt", "Berquist"]
@@ -0,0 +1,116 @@
+"""Property-style tests of the parser over randomly generated ignore files.
+
+Each seed generates a file deterministically, so failures are reproducible.
+"""
+
+import io
+import random
+import string
+from pathlib import Path
+
+import pytest
+from validate_git_blame_ignore_revs.lib import (
+    LineKind,
+    ValidationOptions,
+    parse_lines,
+    validate_git_blame_ignore_revs,
+)
+
+SEEDS = range(200)
+
+
+def random_hash(rng: random.Random) -> str:
+    return f"{rng.getrandbits(160):040x}"
+
+
+def random_padding(rng: random.Random) -> str:
+    return rng.choice(["", " ", "  ", "\t"])
+
+
+def well_formed_lines(rng: random.Random) -> list[str]:
+    """Blocks of comments followed by a hash, separated by blank lines."""
+    lines: list[str] = []
+    for _ in range(rng.randint(0, 20)):
+        for _ in range(rng.randint(0, 3)):
+            comment = "".join(rng.choices(string.printable.strip(), k=rng.randint(0, 30)))
+            lines.append(f"{random_padding(rng)}#{comment}")
+        lines.append(f"{random_padding(rng)}{random_hash(rng)}{random_padding(rng)}")
+        lines.extend("" for _ in range(rng.randint(0, 2)))
+    return lines
+
+
+def corrupt(rng: random.Random, lines: list[str]) -> tuple[list[str], set[int]]:
+    """Insert invalid lines, returning the new lines and the line numbers of the invalid ones."""
+    corruptions = [
+        lambda: random_hash(rng)[:39],
+        lambda: random_hash(rng) + "0",
+        lambda: random_hash(rng).upper(),
+        lambda: random_hash(rng)[:20] + "g" + random_hash(rng)[:19],
+        lambda: "".join(rng.choices(string.ascii_letters + string.digits, k=rng.randint(1, 50))),
+        lambda: f"{random_hash(rng)} # trailing comment",
+    ]
+    marked = [(line, False) for line in lines]
+    for _ in range(rng.randint(1, 5)):
+        marked.insert(rng.randint(0, len(marked)), (rng.choice(corruptions)(), True))
+    lines = [line for line, _ in marked]
+    invalid = {line_number for line_number, (_, bad) in enumerate(marked, start=1) if bad}
+    return lines, invalid
+
+
+def write(tmp_path: Path, lines: list[str]) -> Path:
+    file_path = tmp_path / ".git-blame-ignore-revs"
+    file_path.write_text("\n".join(lines) + "\n", encoding="utf-8")
+    return file_path
+
+
+@pytest.mark.parametrize("seed", SEEDS)
+def test_every_line_classified_once(seed: int) -> None:
+    rng = random.Random(seed)
+    lines, _ = corrupt(rng, well_formed_lines(rng))
+
+    parsed_lines = list(parse_lines(io.StringIO("".join(f"{line}\n" for line in lines))))
+
+    assert [parsed_line.line_number for parsed_line in parsed_lines] == list(
+        range(1, len(lines) + 1)
+    )
+    for line, parsed_line in zip(lines, parsed_lines):
+        assert parsed_line.text == line.strip()
+        if not line.strip():
+            assert parsed_line.kind is LineKind.Blank
+        elif line.strip().startswith("#"):
+            assert parsed_line.kind is LineKind.Comment
+        else:
+            assert parsed_line.kind in (LineKind.Hash, LineKind.Invalid)
+
+
+@pytest.mark.parametrize("seed", SEEDS)
+def test_well_formed_files_are_valid(seed: int, tmp_path: Path) -> None:
+    rng = random.Random(seed)
+    lines = well_formed_lines(rng)
+
+    result = validate_git_blame_ignore_revs(
+        write(tmp_path, lines), ValidationOptions(strict_comments=True)
+    )
+
+    assert not result["errors"]
+    assert list(result["valid_hashes"].values()) == [
+        line.strip() for line in lines if line.strip() and not line.strip().startswith("#")
+    ]
+    for line_number, commit_hash in result["valid_hashes"].items():
+        assert lines[line_number - 1].strip() == commit_hash
+
+
+@pytest.mark.parametrize("seed", SEEDS)
+def test_corrupted_lines_are_errors(seed: int, tmp_path: Path) -> None:
+    rng = random.Random(seed)
+    lines, invalid = corrupt(rng, well_formed_lines(rng))
+
+    result = validate_git_blame_ignore_revs(
+        write(tmp_path, lines), ValidationOptions(strict_comments=True)
+    )
+
+    assert set(result["errors"]) == invalid
+    assert not set(result["errors"]) & set(result["valid_hashes"])
+    assert set(result["strict_comment_errors"]) <= set(result["valid_hashes"])
+    for line_number in (*result["errors"], *result["valid_hashes"]):
+        assert 1 <= line_number <= len(lines)
@@ -0,0 +1,100 @@
+import os
+from pathlib import Path
+
+import pytest
+from validate_git_blame_ignore_revs import git, paths
+from validate_git_blame_ignore_revs.git import git_executable, ignore_rule
+from validate_git_blame_ignore_revs.paths import (
+    find_executable,
+    is_git_entry,
+    path_key,
+    strip_long_path_prefix,
+)
+from validate_git_blame_ignore_revs.workspace import find_repositories
+
+
+@pytest.mark.parametrize(
+    ("path", "expected"),
+    [
+        ("\\\\?\\C:\\repo\\.git-blame-ignore-revs", "C:\\repo\\.git-blame-ignore-revs"),
+        ("\\\\?\\UNC\\server\\share\\repo", "\\\\server\\share\\repo"),
+        ("//?/C:/repo", "C:/repo"),
+        ("\\\\server\\share\\repo", "\\\\server\\share\\repo"),
+        ("C:\\repo", "C:\\repo"),
+        ("/home/user/repo", "/home/user/repo"),
+    ],
+)
+def test_strip_long_path_prefix(path: str, expected: str) -> None:
+    assert strip_long_path_prefix(path) == expected
+
+
+def test_path_key(tmp_path: Path, monkeypatch: pytest.MonkeyPatch) -> None:
+    monkeypatch.chdir(tmp_path)
+
+    assert path_key("repo/../File") == path_key(tmp_path / "File")
+    assert path_key("File") != path_key("file")
+
+    monkeypatch.setattr(paths, "WINDOWS", True)
+
+    assert path_key("File") == path_key("file")
+
+
+def test_find_repositories_ignores_case_on_windows(
+    tmp_path: Path, monkeypatch: pytest.MonkeyPatch
+) -> None:
+    (tmp_path / "app" / ".GIT").mkdir(parents=True)
+    (tmp_path / "lib" / ".git").mkdir(parents=True)
+
+    assert not is_git_entry(".GIT")
+    assert find_repositories(tmp_path) == [tmp_path / "lib"]
+
+    monkeypatch.setattr(paths, "WINDOWS", True)
+
+    assert is_git_entry(".GIT")
+    assert find_repositories(tmp_path) == [tmp_path / "app", tmp_path / "lib"]
+
+
+def test_find_executable(tmp_path: Path, monkeypatch: pytest.MonkeyPatch) -> None:
+    # A program in the repository being checked is never run
+    monkeypatch.chdir(tmp_path)
+    (tmp_path / "git.exe").touch()
+    cmd = tmp_path / "Git" / "cmd"
+    cmd.mkdir(parents=True)
+    (cmd / "git.cmd").touch()
+    extensions = os.pathsep.join([".EXE", ".CMD"])
+    search_path = os.pathsep.join([".", "", f'"{tmp_path / "missing"}"', str(cmd)])
+
+    assert find_executable("git", search_path, extensions) == str(cmd / "git.cmd")
+    assert find_executable("git", ".", extensions) is None
+
+    (cmd / "git.exe").touch()
+
+    # Extensions are tried in the order of PATHEXT
+    assert find_executable("git", str(cmd), extensions) == str(cmd / "git.exe")
+
+
+def test_git_executable(tmp_path: Path, monkeypatch: pytest.MonkeyPatch) -> None:
+    (tmp_path / "git.bat").touch()
+    monkeypatch.setenv("PATH", str(tmp_path))
+    monkeypatch.delenv("PATHEXT", raising=False)
+
+    assert git_executable() == "git"
+
+    monkeypatch.setattr(git, "WINDOWS", True)
+
+    assert git_executable() == str(tmp_path / "git.bat")
+
+    monkeypatch.setenv("PATH", "")
+
+    assert git_executable() == "git"
+
+
+def test_ignore_rule_with_drive_letter(monkeypatch: pytest.MonkeyPatch) -> None:
+    output = "C:/Users/dev/.config/git/ignore:3:!.git-blame-ignore-revs\t.git-blame-ignore-revs"
+    monkeypatch.setattr(git, "run_command", lambda command: output)
+
+    assert ignore_rule(".git-blame-ignore-revs") is None
+
+    output = "C:/Users/dev/.config/git/ignore:2:.git-*\t.git-blame-ignore-revs"
+
+    assert ignore_rule(".git-blame-ignore-revs") == "C:/Users/dev/.config/git/ignore:2:.git-*"
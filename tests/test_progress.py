@@ -0,0 +1,41 @@
+import io
+
+from conftest import GitRepo
+from validate_git_blame_ignore_revs.lib import ValidationOptions, validate_git_blame_ignore_revs
+from validate_git_blame_ignore_revs.progress import ProgressBar, progress_bar
+
+
+def test_progress_bar() -> None:
+    stream = io.StringIO()
+    bar = ProgressBar(stream)
+    bar.width = 4
+
+    bar("Looking up commits", 1, 2)
+    bar("Looking up commits", 2, 2)
+
+    assert stream.getvalue() == (
+        "\r\x1b[KLooking up commits [##--] 1/2"
+        "\r\x1b[KLooking up commits [####] 2/2"
+        "\r\x1b[K"
+    )
+    # Output that isn't to a terminal, such as a CI log, gets no progress bar
+    assert progress_bar(stream) is None
+
+
+def test_progress(git_repo: GitRepo, history: dict[str, str]) -> None:
+    file_path = git_repo.write_ignore_file(f"{history['black']}\n{history['feature']}\n")
+    calls: list[tuple[str, int, int]] = []
+
+    validate_git_blame_ignore_revs(
+        file_path,
+        ValidationOptions(call_git=True, pre_commit_ci=True),
+        progress=lambda label, done, total: calls.append((label, done, total)),
+    )
+
+    assert calls == [
+        ("Looking up commits", 0, 2),
+        ("Looking up commits", 1, 2),
+        ("Looking up commits", 2, 2),
+        ("Scanning history for pre-commit-ci commits", 0, 1),
+        ("Scanning history for pre-commit-ci commits", 1, 1),
+    ]
@@ -157,8 +157,6 @@ def test_validate_git_blame_ignore_revs_strict_comments_git(
         11: ("ruff for all but cclib/parser", "apply ruff to all but cclib/parser"),
         13: ("ruff for cclib/parser", "apply ruff to cclib/parser"),
         15: ("isort", "apply isort"),
-        # TODO fix this side effect of --strict-comments
-        16: ("3c499da3e44e4a8ae983407c0f07c71996d202d8", "isort: fix for circular import"),
         18: ("pre-commit autofix", "[pre-commit.ci] auto fixes from pre-commit.com hooks"),
         20: ("pre-commit autofix", "[pre-commit.ci] auto fixes from pre-commit.com hooks"),
         22: ("pre-commit autofix", "[pre-commit.ci] auto fixes from pre-commit.com hooks"),
@@ -218,8 +216,6 @@ def test_validate_git_blame_ignore_revs_all(
         11: ("ruff for all but cclib/parser", "apply ruff to all but cclib/parser"),
         13: ("ruff for cclib/parser", "apply ruff to cclib/parser"),
         15: ("isort", "apply isort"),
-        # TODO fix this side effect of --strict-comments
-        16: ("3c499da3e44e4a8ae983407c0f07c71996d202d8", "isort: fix for circular import"),
         18: ("pre-commit autofix", "[pre-commit.ci] auto fixes from pre-commit.com hooks"),
         20: ("pre-commit autofix", "[pre-commit.ci] auto fixes from pre-commit.com hooks"),
         22: ("pre-commit autofix", "[pre-commit.ci] auto fixes from pre-commit.com hooks"),
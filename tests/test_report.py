@@ -0,0 +1,325 @@
+import json
+import xml.etree.ElementTree as ET
+
+import pytest
+from validate_git_blame_ignore_revs.formats import render_json, render_junit, render_sarif
+from validate_git_blame_ignore_revs.i18n import translator
+from validate_git_blame_ignore_revs.report import (
+    FileReport,
+    Finding,
+    Section,
+    Severity,
+    exit_code,
+    hyperlink,
+    line_finding,
+    new_findings,
+    render_report,
+)
+
+SECTIONS = [
+    Section(
+        "Missing commits",
+        Severity.Error,
+        4,
+        [
+            line_finding(5, "bbbb", "bbbb", reason="object-not-found"),
+            line_finding(2, "cccc", "cccc"),
+        ],
+        "All commits are present in the Git history!",
+    ),
+    Section(
+        "Comment diffs",
+        Severity.Warning,
+        16,
+        [line_finding(2, "cccc", details=("Comment: Apply black", "Commit message: Add g"))],
+        "All comments match the corresponding commit messages!",
+    ),
+    Section(
+        "Missing pre-commit-ci commits",
+        Severity.Warning,
+        32,
+        [Finding("Commit aaaa", "aaaa", "[pre-commit.ci] auto fixes")],
+        "All pre-commit-ci commits are present in the file!",
+    ),
+    Section(
+        "Unused entries", Severity.Warning, 64, [], "All entries still affect the blame output!"
+    ),
+]
+
+
+@pytest.mark.parametrize(
+    ("group_by", "sort", "expected"),
+    [
+        (
+            "check",
+            "hash",
+            [
+                "\nMissing commits (2):",
+                "  Line 5: bbbb",
+                "  Line 2: cccc",
+                "\nComment diffs (1):",
+                "  Line 2:",
+                "    Comment: Apply black",
+                "    Commit message: Add g",
+                "\nMissing pre-commit-ci commits (1):",
+                "  Commit aaaa: [pre-commit.ci] auto fixes",
+                "\nAll entries still affect the blame output!",
+            ],
+        ),
+        (
+            "line",
+            "line",
+            [
+                "\nLine 2:",
+                "  Missing commits: cccc",
+                "  Comment diffs:",
+                "    Comment: Apply black",
+                "    Commit message: Add g",
+                "\nLine 5:",
+                "  Missing commits: bbbb",
+                "\nCommit aaaa:",
+                "  Missing pre-commit-ci commits: [pre-commit.ci] auto fixes",
+            ],
+        ),
+        (
+            "severity",
+            "line",
+            [
+                "\nErrors (2):",
+                "  Line 2: Missing commits: cccc",
+                "  Line 5: Missing commits: bbbb",
+                "\nWarnings (2):",
+                "  Line 2: Comment diffs:",
+                "    Comment: Apply black",
+                "    Commit message: Add g",
+                "  Commit aaaa: Missing pre-commit-ci commits: [pre-commit.ci] auto fixes",
+            ],
+        ),
+    ],
+)
+def test_render_report(group_by: str, sort: str, expected: list[str]) -> None:
+    assert render_report(SECTIONS, group_by, sort) == expected
+
+
+def test_render_report_without_findings() -> None:
+    sections = [SECTIONS[-1]]
+
+    assert render_report(sections, "line") == ["\nNo problems found!"]
+    assert exit_code(sections) == 0
+
+
+def test_render_report_hyperlinks() -> None:
+    url = "https://github.com/owner/name/commit/aaaa"
+    sections = [SECTIONS[2]._replace(findings=[SECTIONS[2].findings[0]._replace(url=url)])]
+
+    assert render_report(sections, hyperlinks=True) == [
+        "\nMissing pre-commit-ci commits (1):",
+        f"  Commit {hyperlink('aaaa', url)}: [pre-commit.ci] auto fixes",
+    ]
+    assert hyperlink("aaaa", url) == f"\x1b]8;;{url}\x1b\\aaaa\x1b]8;;\x1b\\"
+    assert render_report(sections)[1] == "  Commit aaaa: [pre-commit.ci] auto fixes"
+
+
+def test_render_report_translated() -> None:
+    translate = translator("de")
+
+    assert render_report(SECTIONS[:1] + SECTIONS[3:], translate=translate) == [
+        "\nFehlende Commits (2):",
+        "  Zeile 2: cccc",
+        "  Zeile 5: bbbb",
+        "\nAlle Einträge wirken sich noch auf die Ausgabe von git blame aus!",
+    ]
+    assert render_report(SECTIONS[:1], "severity", translate=translate) == [
+        "\nFehler (2):",
+        "  Zeile 2: Fehlende Commits: cccc",
+        "  Zeile 5: Fehlende Commits: bbbb",
+    ]
+
+
+def test_exit_code() -> None:
+    assert exit_code(SECTIONS) == 4 | 16 | 32
+
+
+def test_new_findings() -> None:
+    # The same problems, on lines that moved down when an entry was added above them
+    base_sections = [
+        SECTIONS[0]._replace(findings=[line_finding(1, "cccc", "cccc")]),
+        SECTIONS[1]._replace(findings=[line_finding(1, "cccc")]),
+    ]
+
+    sections, existing = new_findings(SECTIONS, base_sections)
+
+    assert existing == 2
+    assert [section.findings for section in sections] == [
+        [SECTIONS[0].findings[0]],
+        [],
+        SECTIONS[2].findings,
+        [],
+    ]
+    assert exit_code(sections) == 4 | 32
+
+
+FILES = [
+    FileReport(".git-blame-ignore-revs", SECTIONS, [], 52),
+    FileReport("vendor/lib/.git-blame-ignore-revs", [], ["No such file or directory"], 1),
+]
+
+
+def test_render_json() -> None:
+    document = json.loads(render_json(FILES))
+
+    assert document["exit_code"] == 53
+    assert document["summary"] == {
+        "files": 2,
+        "files_with_problems": 2,
+        "errors": 2,
+        "warnings": 2,
+        "info": 0,
+    }
+    checks = document["files"][0]["checks"]
+    assert [check["id"] for check in checks] == [
+        "missing-commits",
+        "comment-diffs",
+        "missing-pre-commit-ci-commits",
+        "unused-entries",
+    ]
+    assert checks[1]["findings"] == [
+        {
+            "line": 2,
+            "commit": "cccc",
+            "label": "Line 2",
+            "text": "",
+            "details": ["Comment: Apply black", "Commit message: Add g"],
+            "url": None,
+            "reason": None,
+        }
+    ]
+    assert document["files"][1] == {
+        "file": "vendor/lib/.git-blame-ignore-revs",
+        "exit_code": 1,
+        "checks": [],
+        "notes": ["No such file or directory"],
+    }
+
+
+def test_render_sarif() -> None:
+    log = json.loads(render_sarif(FILES))
+    run = log["runs"][0]
+
+    assert log["version"] == "2.1.0"
+    assert len(run["tool"]["driver"]["rules"]) == 4
+    assert [artifact["location"]["uri"] for artifact in run["artifacts"]] == [
+        ".git-blame-ignore-revs",
+        "vendor/lib/.git-blame-ignore-revs",
+    ]
+    assert not run["invocations"][0]["executionSuccessful"]
+    assert run["invocations"][0]["toolExecutionNotifications"] == [
+        {
+            "message": {"text": "No such file or directory"},
+            "locations": [
+                {
+                    "physicalLocation": {
+                        "artifactLocation": {"uri": "vendor/lib/.git-blame-ignore-revs", "index": 1}
+                    }
+                }
+            ],
+        }
+    ]
+    assert [(result["ruleId"], result["level"]) for result in run["results"]] == [
+        ("missing-commits", "error"),
+        ("missing-commits", "error"),
+        ("comment-diffs", "warning"),
+        ("missing-pre-commit-ci-commits", "warning"),
+    ]
+    assert run["results"][2]["message"]["text"] == (
+        "Line 2\nComment: Apply black\nCommit message: Add g"
+    )
+    assert run["results"][0]["locations"][0]["physicalLocation"] == {
+        "artifactLocation": {"uri": ".git-blame-ignore-revs", "index": 0},
+        "region": {"startLine": 5},
+    }
+    assert run["results"][0]["properties"] == {"reason": "object-not-found"}
+    assert "properties" not in run["results"][1]
+    # Commits that aren't in the file are located at the file as a whole
+    assert "region" not in run["results"][3]["locations"][0]["physicalLocation"]
+
+
+def test_render_junit() -> None:
+    testsuites = ET.fromstring(render_junit(FILES))
+
+    assert testsuites.attrib == {
+        "name": "validate-git-blame-ignore-revs",
+        "tests": "5",
+        "failures": "3",
+        "errors": "1",
+    }
+    checked, unreadable = testsuites.findall("testsuite")
+    assert checked.attrib == {
+        "name": ".git-blame-ignore-revs",
+        "tests": "4",
+        "failures": "3",
+        "errors": "0",
+    }
+    failures = [
+        testcase.get("name") for testcase in checked if testcase.find("failure") is not None
+    ]
+    assert failures == ["Missing commits", "Comment diffs", "Missing pre-commit-ci commits"]
+    error = unreadable.find("testcase/error")
+    assert error is not None
+    assert error.text == "No such file or directory"
+
+
+@pytest.mark.parametrize(
+    ("group_by", "expected"),
+    [
+        (
+            "check",
+            [
+                "\nMissing commits (2):",
+                "  Line 2: cccc",
+                "  Line 5: bbbb",
+                "\n... and 2 more not shown.",
+            ],
+        ),
+        (
+            "line",
+            [
+                "\nLine 2:",
+                "  Missing commits: cccc",
+                "  Comment diffs:",
+                "    Comment: Apply black",
+                "    Commit message: Add g",
+                "\n... and 2 more not shown.",
+            ],
+        ),
+    ],
+)
+def test_render_report_max_findings(group_by: str, expected: list[str]) -> None:
+    assert render_report(SECTIONS, group_by, max_findings=2) == expected
+    assert exit_code(SECTIONS) == 4 | 16 | 32
+
+
+def test_info_findings() -> None:
+    sections = [
+        Section(
+            "Possible misspellings",
+            Severity.Info,
+            0,
+            [line_finding(1, "Aply", "Aply")],
+            "No misspellings in the comments!",
+        )
+    ]
+    files = [FileReport(".git-blame-ignore-revs", sections, [], 0)]
+
+    # Info never fails the run
+    assert exit_code(sections) == 0
+    assert render_report(sections, "severity") == [
+        "\nInfo (1):",
+        "  Line 1: Possible misspellings: Aply",
+    ]
+    assert json.loads(render_json(files))["summary"]["info"] == 1
+    assert json.loads(render_sarif(files))["runs"][0]["results"][0]["level"] == "note"
+    testsuite = ET.fromstring(render_junit(files)).find("testsuite")
+    assert testsuite is not None
+    assert testsuite.get("failures") == "0"
+    assert testsuite.findtext("testcase/system-out") == "Line 1: Aply"
@@ -0,0 +1,177 @@
+import json
+import threading
+from collections.abc import Iterator
+from subprocess import CalledProcessError
+from typing import Any, Optional
+from urllib.error import HTTPError
+from urllib.request import Request, urlopen
+
+import pytest
+from validate_git_blame_ignore_revs.cache import CacheStats
+from validate_git_blame_ignore_revs.git import GitNotFoundError
+from validate_git_blame_ignore_revs.metrics import CONTENT_TYPE, Metrics
+from validate_git_blame_ignore_revs.report import Section, Severity, line_finding
+from validate_git_blame_ignore_revs.server import BadRequest, ValidationServer
+
+
+def validator(repository: Optional[str], content: str) -> str:
+    if repository == "unknown":
+        raise BadRequest("Unknown repository: unknown")
+    if repository == "broken":
+        raise CalledProcessError(128, ["git", "rev-parse", "HEAD"])
+    if repository == "gitless":
+        raise GitNotFoundError("git is not installed")
+    return json.dumps({"repository": repository, "lines": len(content.splitlines())})
+
+
+@pytest.fixture
+def metrics() -> Metrics:
+    return Metrics()
+
+
+@pytest.fixture
+def url(metrics: Metrics) -> Iterator[str]:
+    server = ValidationServer(("127.0.0.1", 0), validator, metrics)
+    thread = threading.Thread(target=server.serve_forever)
+    thread.start()
+    try:
+        host, port = server.server_address[:2]
+        yield f"http://{host}:{port}"
+    finally:
+        server.shutdown()
+        thread.join()
+        server.server_close()
+
+
+def post(url: str, body: bytes) -> tuple[int, Any]:
+    request = Request(url, data=body, headers={"Content-Type": "application/json"})
+    try:
+        with urlopen(request, timeout=10) as response:
+            return response.status, json.load(response)
+    except HTTPError as e:
+        return e.code, json.load(e)
+
+
+def test_validate(url: str) -> None:
+    body = json.dumps({"repository": "app", "content": "# Comment\nnot-a-hash\n"}).encode()
+
+    assert post(f"{url}/validate", body) == (200, {"repository": "app", "lines": 2})
+
+    body = json.dumps({"content": ""}).encode()
+
+    assert post(f"{url}/validate", body) == (200, {"repository": None, "lines": 0})
+
+
+@pytest.mark.parametrize(
+    ("path", "body", "expected"),
+    [
+        ("/other", b"{}", (404, {"error": "No such endpoint: /other"})),
+        ("/validate", b"{", (400, None)),
+        ("/validate", b"[]", (400, {"error": '"content" must be a string'})),
+        (
+            "/validate",
+            b'{"content": "", "repository": 1}',
+            (400, {"error": '"repository" must be a string'}),
+        ),
+        (
+            "/validate",
+            b'{"content": "", "repository": "unknown"}',
+            (400, {"error": "Unknown repository: unknown"}),
+        ),
+        (
+            "/validate",
+            b'{"content": "", "repository": "broken"}',
+            (
+                500,
+                {
+                    "error": "Command '['git', 'rev-parse', 'HEAD']' returned non-zero exit "
+                    "status 128."
+                },
+            ),
+        ),
+        (
+            "/validate",
+            b'{"content": "", "repository": "gitless"}',
+            (500, {"error": "git is not installed"}),
+        ),
+    ],
+)
+def test_bad_requests(url: str, path: str, body: bytes, expected: tuple[int, Any]) -> None:
+    status, response = post(f"{url}{path}", body)
+
+    assert status == expected[0]
+    if expected[1] is None:
+        assert response["error"].startswith("Invalid JSON: ")
+    else:
+        assert response == expected[1]
+
+
+def test_metrics(url: str) -> None:
+    post(f"{url}/validate", json.dumps({"content": ""}).encode())
+    post(f"{url}/validate", json.dumps({"content": "", "repository": "unknown"}).encode())
+
+    with urlopen(f"{url}/metrics", timeout=10) as response:
+        content_type = response.headers["Content-Type"]
+        lines = response.read().decode().splitlines()
+
+    assert content_type == CONTENT_TYPE
+    assert 'validate_git_blame_ignore_revs_requests_total{status="200"} 1' in lines
+    assert 'validate_git_blame_ignore_revs_requests_total{status="400"} 1' in lines
+    assert 'validate_git_blame_ignore_revs_request_duration_seconds_bucket{le="+Inf"} 2' in lines
+    assert "validate_git_blame_ignore_revs_request_duration_seconds_count 2" in lines
+    assert lines[-1] == "# EOF"
+
+
+def test_render_metrics() -> None:
+    cache_stats = CacheStats()
+    metrics = Metrics(cache_stats)
+    finding = line_finding(2, "not-a-hash")
+    sections = [
+        Section('Invalid "hashes"', Severity.Error, 1, [finding, finding], None),
+        Section("Missing commits", Severity.Error, 2, [], "All commits are present!"),
+    ]
+
+    metrics.record_validation("app", sections, 1)
+    metrics.record_validation(None, [], 0)
+    metrics.record_request(200, 0.02)
+    metrics.record_request(200, 60.0)
+    cache_stats.hits = 3
+    cache_stats.misses = 1
+
+    # Every name has the same prefix
+    lines = metrics.render().replace("validate_git_blame_ignore_revs_", "").splitlines()
+
+    assert lines == [
+        "# TYPE validations counter",
+        "# HELP validations Files validated, by repository and outcome.",
+        'validations_total{repository="",outcome="passed"} 1',
+        'validations_total{repository="app",outcome="failed"} 1',
+        "# TYPE findings counter",
+        "# HELP findings Findings reported, by check and severity.",
+        'findings_total{check="Invalid \\"hashes\\"",severity="error"} 2',
+        "# TYPE requests counter",
+        "# HELP requests Requests to /validate, by HTTP status.",
+        'requests_total{status="200"} 2',
+        "# TYPE request_duration_seconds histogram",
+        "# HELP request_duration_seconds How long requests to /validate take.",
+        'request_duration_seconds_bucket{le="0.005"} 0',
+        'request_duration_seconds_bucket{le="0.01"} 0',
+        'request_duration_seconds_bucket{le="0.025"} 1',
+        'request_duration_seconds_bucket{le="0.05"} 1',
+        'request_duration_seconds_bucket{le="0.1"} 1',
+        'request_duration_seconds_bucket{le="0.25"} 1',
+        'request_duration_seconds_bucket{le="0.5"} 1',
+        'request_duration_seconds_bucket{le="1.0"} 1',
+        'request_duration_seconds_bucket{le="2.5"} 1',
+        'request_duration_seconds_bucket{le="5.0"} 1',
+        'request_duration_seconds_bucket{le="10.0"} 1',
+        'request_duration_seconds_bucket{le="30.0"} 1',
+        'request_duration_seconds_bucket{le="+Inf"} 2',
+        "request_duration_seconds_sum 60.02",
+        "request_duration_seconds_count 2",
+        "# TYPE cache_lookups counter",
+        "# HELP cache_lookups Lookups of cached results, by result.",
+        'cache_lookups_total{result="hit"} 3',
+        'cache_lookups_total{result="miss"} 1',
+        "# EOF",
+    ]
@@ -0,0 +1,76 @@
+"""Snapshot tests of the reports printed by the command line interface.
+
+The snapshots live in `tests/snapshots`. After an intended change to the
+output, regenerate them with `UPDATE_SNAPSHOTS=1` and review the diff.
+"""
+
+from collections.abc import Callable
+
+import pytest
+from conftest import GitRepo, run
+from validate_git_blame_ignore_revs.formats import FORMATS
+
+MISSING_COMMIT = "0123456789abcdef0123456789abcdef01234567"
+
+ALL_CHECKS = [
+    "--call-git",
+    "--strict-comments",
+    "--strict-comments-git",
+    "--pre-commit-ci",
+    "--report-unused-entries",
+    "--check-semantic-changes",
+]
+
+
+def snapshot_name(name: str, format: str) -> str:
+    # The human-readable report keeps the name it had before the other formats were added
+    return name if format == "human" else f"{name}_{format}"
+
+
+@pytest.mark.parametrize("format", FORMATS)
+def test_all_checks_pass(
+    git_repo: GitRepo,
+    history: dict[str, str],
+    capsys: pytest.CaptureFixture[str],
+    snapshot: Callable[[str, str], None],
+    format: str,
+) -> None:
+    git_repo.write_ignore_file(f"# [pre-commit.ci] auto fixes\n{history['bot']}\n")
+
+    # Relative to the repository, so the reports that name the file don't depend on where it is
+    _, out = run([".git-blame-ignore-revs", *ALL_CHECKS, "--format", format], capsys)
+
+    snapshot(snapshot_name("all_checks_pass", format), out)
+
+
+@pytest.mark.parametrize("format", FORMATS)
+def test_all_checks_fail(
+    git_repo: GitRepo,
+    history: dict[str, str],
+    capsys: pytest.CaptureFixture[str],
+    snapshot: Callable[[str, str], None],
+    format: str,
+) -> None:
+    git_repo.write_ignore_file(
+        f"{history['initial']}\n"
+        f"# Run the formatter\n{history['feature']}\n"
+        f"# Not in history\n{MISSING_COMMIT}\n"
+        "not-a-hash\n"
+    )
+
+    _, out = run([".git-blame-ignore-revs", *ALL_CHECKS, "--format", format], capsys)
+
+    snapshot(snapshot_name("all_checks_fail", format), out)
+
+
+def test_explain(
+    git_repo: GitRepo,
+    history: dict[str, str],
+    capsys: pytest.CaptureFixture[str],
+    snapshot: Callable[[str, str], None],
+) -> None:
+    file_path = git_repo.write_ignore_file(f"# Apply black\n{history['black']}\n")
+
+    _, out = run(["explain", str(file_path), history["black"]], capsys)
+
+    snapshot("explain", out)
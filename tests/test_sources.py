@@ -0,0 +1,49 @@
+from pathlib import Path
+
+import pytest
+from conftest import GitRepo
+from validate_git_blame_ignore_revs.lib import validate_git_blame_ignore_revs
+from validate_git_blame_ignore_revs.sources import GitIndex, GitRevision, InMemory, WorkingTree
+
+HASH = "0123456789abcdef0123456789abcdef01234567"
+
+
+def test_in_memory(tmp_path: Path) -> None:
+    file_path = tmp_path / ".git-blame-ignore-revs"
+    source = InMemory({file_path: f"# Unsaved\n{HASH}\r\nnot-a-hash\n"})
+
+    result = validate_git_blame_ignore_revs(file_path, source=source)
+
+    assert not file_path.exists()
+    assert result["valid_hashes"] == {2: HASH}
+    assert result["errors"] == {3: "not-a-hash"}
+
+    with pytest.raises(FileNotFoundError):
+        validate_git_blame_ignore_revs(tmp_path / "other", source=source)
+
+
+def test_open_lines(tmp_path: Path) -> None:
+    file_path = tmp_path / ".git-blame-ignore-revs"
+    source = InMemory({file_path: f"# Unsaved\r\n{HASH}\r\n\r\n".encode("utf-8")})
+
+    with source.open_lines(file_path) as lines:
+        assert list(lines) == ["# Unsaved\n", f"{HASH}\n", "\n"]
+        lines.seek(0)
+        assert lines.readline() == "# Unsaved\n"
+
+    with pytest.raises(FileNotFoundError):
+        source.open_lines(tmp_path / "other")
+
+
+def test_git_sources(git_repo: GitRepo) -> None:
+    file_path = git_repo.write_ignore_file(f"{HASH}\n")
+    file_path.write_text(f"{HASH}\nstaged\n", encoding="utf-8")
+    git_repo.git("add", ".git-blame-ignore-revs")
+    file_path.write_text(f"{HASH}\nstaged\nworking\n", encoding="utf-8")
+
+    assert WorkingTree().read_text(file_path) == f"{HASH}\nstaged\nworking\n"
+    assert GitIndex().read_text(file_path) == f"{HASH}\nstaged\n"
+    assert GitRevision("HEAD").read_text(file_path) == f"{HASH}\n"
+
+    with pytest.raises(FileNotFoundError):
+        GitRevision("HEAD").read_bytes(git_repo.path / "missing")
@@ -0,0 +1,28 @@
+from pathlib import Path
+
+from validate_git_blame_ignore_revs.spelling import load_dictionary, misspelled_words
+
+DICTIONARY = frozenset({"apply", "black", "format", "the", "code", "with", "to"})
+
+
+def test_misspelled_words() -> None:
+    text = "Aply black to the codee with ruff's formatter, codee"
+
+    assert misspelled_words(text, DICTIONARY) == ["Aply", "codee", "ruff's", "formatter"]
+    assert misspelled_words(text, DICTIONARY, ["ruff", "Formatter", "aply", "codee"]) == []
+
+
+def test_misspelled_words_skips_names_and_code() -> None:
+    text = (
+        "Format #123 https://example.com/pull/1 @octocat a@b.org `fmt_all` "
+        "py39 snake_case CI GitHub black-format don’t"
+    )
+
+    assert misspelled_words(text, DICTIONARY) == ["don’t"]
+
+
+def test_load_dictionary(tmp_path: Path) -> None:
+    path = tmp_path / "words"
+    path.write_text("Apply\n\nblack\n", encoding="utf-8")
+
+    assert load_dictionary(path) == {"apply", "black"}
@@ -0,0 +1,39 @@
+from typing import Optional
+
+import pytest
+from validate_git_blame_ignore_revs.suggestions import NearMiss, near_miss
+
+HASH = "0123456789abcdef0123456789abcdef01234567"
+
+
+@pytest.mark.parametrize(
+    ("text", "expected"),
+    [
+        (HASH[:39], NearMiss("hash is 39 characters, expected 40", (HASH[:39],))),
+        (
+            HASH + "8",
+            NearMiss("hash is 41 characters, expected 40", (HASH, HASH[1:] + "8")),
+        ),
+        (
+            "O" + HASH[1:],
+            NearMiss("character 1 is 'O', which looks like '0'", (HASH,)),
+        ),
+        (
+            "O" + HASH[1:17] + "l" + HASH[18:],
+            NearMiss("character 1 is 'O', which looks like '0' and 1 more", (HASH,)),
+        ),
+        (
+            HASH[:10] + "g" + HASH[11:],
+            NearMiss("character 11 is 'g', which isn't hexadecimal", (HASH[:10],)),
+        ),
+        (
+            "012g" + HASH[4:],
+            NearMiss("character 4 is 'g', which isn't hexadecimal", ()),
+        ),
+        (HASH[:20], None),
+        ("not-a-hash", None),
+        (HASH[:38] + "gg", None),
+    ],
+)
+def test_near_miss(text: str, expected: Optional[NearMiss]) -> None:
+    assert near_miss(text) == expected